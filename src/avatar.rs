@@ -0,0 +1,366 @@
+//! Avatars embedded in cards.
+//!
+//! Cards show the owner's avatar instead of a generic icon. Avatars are
+//! downloaded from GitHub's avatar host, downscaled to a small square and
+//! embedded in the SVG as a base64 data URI, so rendered cards never reference
+//! external resources.
+//!
+//! Avatar URLs come from API responses, so downloads are treated as untrusted:
+//! only `https://avatars.githubusercontent.com` is contacted, redirects are not
+//! followed, responses must be a PNG, JPEG, GIF or WebP image under a fixed
+//! byte limit, and decoding is bounded in dimensions and memory. Processed
+//! avatars are kept in their own cache, capped by total size.
+//!
+//! Avatars are decorative: failures are logged and cached briefly, and the card
+//! falls back to its icon.
+
+use crate::errors::AvatarError;
+use crate::github::Contributor;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use image::{DynamicImage, ImageFormat, ImageReader, Limits};
+use moka::future::Cache;
+use moka::Expiry;
+use once_cell::sync::Lazy;
+use reqwest::{header, redirect, Client, Url};
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::{debug, instrument, warn};
+
+/// Width and height of embedded avatars in pixels, enough for the largest
+/// avatar shown on any built-in layout.
+pub const AVATAR_SIZE: u32 = 160;
+
+/// Host serving GitHub avatars; no other host is contacted.
+const AVATAR_HOST: &str = "avatars.githubusercontent.com";
+
+/// Maximum size of a downloaded avatar in bytes.
+pub const MAX_AVATAR_BYTES: usize = 1024 * 1024;
+
+/// Maximum width or height of an avatar before it is decoded.
+pub const MAX_AVATAR_DIMENSION: u32 = 2048;
+
+/// Maximum memory the decoder may allocate for one avatar.
+const MAX_DECODE_ALLOC: u64 = 32 * 1024 * 1024;
+
+/// Timeout for a whole avatar request, including the body.
+const AVATAR_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Total size of the cached data URIs in bytes.
+const AVATAR_CACHE_BYTES: u64 = 32 * 1024 * 1024;
+
+/// How long a processed avatar is cached.
+const AVATAR_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// How long a failed avatar download is cached before it is retried.
+const FAILURE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Maximum number of avatars downloaded at once by [`AvatarCache::get_all`].
+const MAX_CONCURRENT_DOWNLOADS: usize = 4;
+
+/// JPEG quality for opaque avatars.
+const JPEG_QUALITY: u8 = 85;
+
+/// Content types accepted from the avatar host, and the format each is decoded as.
+const ACCEPTED_TYPES: [(&str, ImageFormat); 4] = [
+    ("image/png", ImageFormat::Png),
+    ("image/jpeg", ImageFormat::Jpeg),
+    ("image/gif", ImageFormat::Gif),
+    ("image/webp", ImageFormat::WebP),
+];
+
+/// A 1x1 PNG, used as the avatar of sample cards when validating templates.
+pub(crate) const SAMPLE_AVATAR: &str = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==";
+
+// Global avatar cache instance
+pub static AVATARS: Lazy<AvatarCache> = Lazy::new(AvatarCache::new);
+
+/// Cached avatar: a data URI, or None if the download failed.
+type AvatarEntry = Option<Arc<str>>;
+
+/// Expires failed downloads sooner than processed avatars.
+struct AvatarExpiry;
+
+impl Expiry<String, AvatarEntry> for AvatarExpiry {
+    fn expire_after_create(
+        &self,
+        _key: &String,
+        value: &AvatarEntry,
+        _created_at: Instant,
+    ) -> Option<Duration> {
+        Some(if value.is_some() {
+            AVATAR_TTL
+        } else {
+            FAILURE_TTL
+        })
+    }
+}
+
+/// Downloads, downscales and caches avatars.
+#[derive(Clone)]
+pub struct AvatarCache {
+    /// HTTP client for the avatar host, without GitHub API credentials
+    http_client: Client,
+    /// Data URIs keyed by the original avatar URL, weighed by their length
+    cache: Cache<String, AvatarEntry>,
+}
+
+impl AvatarCache {
+    /// Creates an empty avatar cache.
+    pub fn new() -> Self {
+        let http_client = Client::builder()
+            .user_agent(format!("glim/{}", env!("CARGO_PKG_VERSION")))
+            .timeout(AVATAR_TIMEOUT)
+            .redirect(redirect::Policy::none())
+            .build()
+            .expect("Failed to create HTTP client");
+
+        let cache = Cache::builder()
+            .max_capacity(AVATAR_CACHE_BYTES)
+            .weigher(|_url: &String, entry: &AvatarEntry| {
+                entry
+                    .as_ref()
+                    .map_or(1, |uri| u32::try_from(uri.len()).unwrap_or(u32::MAX))
+            })
+            .expire_after(AvatarExpiry)
+            .build();
+
+        Self { http_client, cache }
+    }
+
+    /// Returns the avatar at `url` as a data URI, fetching it on a cache miss.
+    ///
+    /// Concurrent requests for the same avatar share a single download.
+    ///
+    /// # Arguments
+    /// * `url` - Avatar URL from a GitHub API response
+    ///
+    /// # Returns
+    /// The avatar as a data URI, or None if it is missing or could not be fetched
+    pub async fn get(&self, url: &str) -> Option<String> {
+        if url.is_empty() {
+            return None;
+        }
+
+        self.cache
+            .get_with(url.to_string(), async {
+                match self.fetch(url).await {
+                    Ok(uri) => Some(Arc::from(uri)),
+                    Err(error) => {
+                        warn!("Failed to fetch avatar {}: {}", url, error);
+                        None
+                    }
+                }
+            })
+            .await
+            .map(|uri| uri.to_string())
+    }
+
+    /// Returns several avatars as data URIs, fetching those missing from the cache concurrently.
+    ///
+    /// At most a few downloads run at once, so a long list doesn't flood the
+    /// avatar host.
+    ///
+    /// # Arguments
+    /// * `urls` - Avatar URLs from GitHub API responses
+    ///
+    /// # Returns
+    /// Each avatar in the order of `urls`, None for those missing or failed
+    pub async fn get_all(&self, urls: &[&str]) -> Vec<Option<String>> {
+        let permits = Arc::new(Semaphore::new(MAX_CONCURRENT_DOWNLOADS));
+        let mut downloads = JoinSet::new();
+        for (index, url) in urls.iter().enumerate() {
+            let cache = self.clone();
+            let url = url.to_string();
+            let permits = permits.clone();
+            downloads.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                (index, cache.get(&url).await)
+            });
+        }
+
+        let mut avatars = vec![None; urls.len()];
+        while let Some(result) = downloads.join_next().await {
+            match result {
+                Ok((index, avatar)) => avatars[index] = avatar,
+                Err(error) => warn!("Avatar download task failed: {}", error),
+            }
+        }
+        avatars
+    }
+
+    /// Fills in the avatars of repository contributors, see [`AvatarCache::get_all`].
+    pub async fn fill_contributors(&self, contributors: &mut [Contributor]) {
+        let urls: Vec<&str> = contributors
+            .iter()
+            .map(|contributor| contributor.avatar_url.as_str())
+            .collect();
+        let avatars = self.get_all(&urls).await;
+        for (contributor, avatar) in contributors.iter_mut().zip(avatars) {
+            contributor.avatar = avatar;
+        }
+    }
+
+    /// Downloads an avatar and encodes it as a data URI, bypassing the cache.
+    ///
+    /// # Errors
+    /// Returns an error if the URL is not a GitHub avatar, the request fails, or
+    /// the response is not an accepted image within the size limits.
+    #[instrument(skip(self))]
+    pub async fn fetch(&self, url: &str) -> Result<String, AvatarError> {
+        let url = sized_avatar_url(url, AVATAR_SIZE)?;
+        let mut response = self.http_client.get(url).send().await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(AvatarError::Status(status.as_u16()));
+        }
+
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        let format = accepted_format(content_type)
+            .ok_or_else(|| AvatarError::UnsupportedType(content_type.to_string()))?;
+
+        // Reject early when the declared length is too large, then enforce the
+        // limit while reading in case the header is missing or wrong
+        if response
+            .content_length()
+            .is_some_and(|length| length > MAX_AVATAR_BYTES as u64)
+        {
+            return Err(AvatarError::TooLarge {
+                limit: MAX_AVATAR_BYTES,
+            });
+        }
+
+        let mut data = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if data.len() + chunk.len() > MAX_AVATAR_BYTES {
+                return Err(AvatarError::TooLarge {
+                    limit: MAX_AVATAR_BYTES,
+                });
+            }
+            data.extend_from_slice(&chunk);
+        }
+        debug!("Downloaded {} byte avatar", data.len());
+
+        encode_avatar(&data, format, AVATAR_SIZE)
+    }
+}
+
+impl Default for AvatarCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Validates a GitHub avatar URL and requests a specific size from the avatar host.
+///
+/// # Arguments
+/// * `url` - Avatar URL from a GitHub API response
+/// * `size` - Requested width and height in pixels
+///
+/// # Errors
+/// Returns an error unless the URL is an `https` URL on the GitHub avatar host,
+/// without credentials or a custom port.
+pub fn sized_avatar_url(url: &str, size: u32) -> Result<Url, AvatarError> {
+    let invalid = || AvatarError::InvalidUrl(url.to_string());
+    let mut parsed = Url::parse(url).map_err(|_| invalid())?;
+
+    if parsed.scheme() != "https"
+        || parsed.host_str() != Some(AVATAR_HOST)
+        || parsed.port().is_some()
+        || !parsed.username().is_empty()
+        || parsed.password().is_some()
+    {
+        return Err(invalid());
+    }
+
+    let query: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(key, _)| key != "s")
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    parsed
+        .query_pairs_mut()
+        .clear()
+        .extend_pairs(query)
+        .append_pair("s", &size.to_string());
+
+    Ok(parsed)
+}
+
+/// Decodes an avatar, crops it to a centered square no larger than `size` and
+/// encodes it as a data URI.
+///
+/// Opaque avatars are encoded as JPEG to keep cards small; avatars with
+/// transparency are encoded as PNG.
+///
+/// # Arguments
+/// * `data` - Encoded image
+/// * `format` - Format the image is declared as
+/// * `size` - Maximum width and height of the embedded avatar
+///
+/// # Errors
+/// Returns an error if the image does not decode as `format`, or exceeds the
+/// dimension or memory limits.
+pub fn encode_avatar(data: &[u8], format: ImageFormat, size: u32) -> Result<String, AvatarError> {
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_AVATAR_DIMENSION);
+    limits.max_image_height = Some(MAX_AVATAR_DIMENSION);
+    limits.max_alloc = Some(MAX_DECODE_ALLOC);
+
+    let mut reader = ImageReader::with_format(Cursor::new(data), format);
+    reader.limits(limits);
+    let image = reader.decode()?;
+
+    // Crop to the centered square, then downscale
+    let side = image.width().min(image.height());
+    let image = image.crop_imm(
+        (image.width() - side) / 2,
+        (image.height() - side) / 2,
+        side,
+        side,
+    );
+    let target = side.min(size);
+    let image = if target < side {
+        image.resize_exact(target, target, image::imageops::FilterType::Lanczos3)
+    } else {
+        image
+    };
+
+    let rgba = image.into_rgba8();
+    let opaque = rgba.pixels().all(|pixel| pixel.0[3] == u8::MAX);
+
+    let mut encoded = Vec::new();
+    let mime = if opaque {
+        let rgb = DynamicImage::ImageRgba8(rgba).into_rgb8();
+        rgb.write_with_encoder(image::codecs::jpeg::JpegEncoder::new_with_quality(
+            &mut encoded,
+            JPEG_QUALITY,
+        ))?;
+        "image/jpeg"
+    } else {
+        rgba.write_with_encoder(image::codecs::png::PngEncoder::new(&mut encoded))?;
+        "image/png"
+    };
+
+    Ok(format!(
+        "data:{};base64,{}",
+        mime,
+        STANDARD.encode(&encoded)
+    ))
+}
+
+/// Returns the image format for an accepted content type, ignoring parameters and case.
+fn accepted_format(content_type: &str) -> Option<ImageFormat> {
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+    ACCEPTED_TYPES
+        .iter()
+        .find(|(accepted, _)| mime.eq_ignore_ascii_case(accepted))
+        .map(|(_, format)| *format)
+}
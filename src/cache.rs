@@ -26,6 +26,7 @@
 //!         owner: "rust-lang".to_string(),
 //!         repo: "rust".to_string(),
 //!         theme: "dark".to_string(),
+//!         variant: "default:png@1".to_string(),
 //!     };
 //!
 //!     let image_data = cache()
@@ -108,6 +109,11 @@ pub fn cache() -> &'static CacheManager<RepositoryCard> {
     CACHE.get().expect("Cache has not been initialized")
 }
 
+/// Returns a handle to the global cache, or None if it has not been initialized.
+pub fn try_cache() -> Option<&'static CacheManager<RepositoryCard>> {
+    CACHE.get()
+}
+
 /// Configuration for the caching system.
 #[derive(Debug, Clone)]
 pub struct CacheConfig {
@@ -228,12 +234,19 @@ pub trait Cacheable {
 pub struct RepositoryCard {
     pub owner: String,
     pub repo: String,
+    /// Color theme the card was rendered with (see `Theme::key`)
     pub theme: String,
+    /// Template and output options (format, scale) that distinguish renders of the same theme,
+    /// followed by a hash of the rendered SVG so changed repository data is a new entry
+    pub variant: String,
 }
 
 impl Cacheable for RepositoryCard {
     fn cache_key(&self) -> String {
-        format!("{}:{}/{}:{}", self.owner, self.repo, self.theme, "v1")
+        format!(
+            "{}:{}/{}:{}:{}",
+            self.owner, self.repo, self.theme, self.variant, "v2"
+        )
     }
 
     fn owner(&self) -> &str {
@@ -0,0 +1,2100 @@
+//! Card rendering shared by the CLI, the server and library users.
+//!
+//! A [`Card`] combines repository data with presentation options and renders
+//! to an SVG document through a named template (see [`crate::template`]),
+//! which the encoders then rasterize into other formats. The [`Layout`]
+//! selects between the regular card, a compact badge and the social
+//! preview image. [`Section`]s toggle individual pieces of content, from the
+//! stats row to topic chips, the language breakdown bar, the star history
+//! and commit activity graphs and a row of contributor avatars. Cards can
+//! also be rendered as a sequence of frames, with the counters ticking up and
+//! the description fading in, for animated formats.
+//!
+//! # Example
+//!
+//! ```rust
+//! use glim::card::{Card, CardOptions, Render};
+//! use glim::github::Repository;
+//! use glim::theme::Theme;
+//!
+//! let repository = Repository {
+//!     name: "glim".to_string(),
+//!     description: Some("Generate beautiful GitHub repository cards".to_string()),
+//!     language: Some("Rust".to_string()),
+//!     stargazers_count: 1200,
+//!     forks_count: 34,
+//!     ..Default::default()
+//! };
+//!
+//! let options = CardOptions {
+//!     theme: Theme::dark(),
+//!     ..Default::default()
+//! };
+//!
+//! let svg = Card::new(repository, options).render().unwrap();
+//! assert!(svg.contains("1.2k"));
+//! ```
+
+use crate::encode::{EncodeOptions, ImageFormat};
+use crate::errors::TemplateError;
+use crate::github::{
+    CommitWeek, Contributor, LanguageBytes, License, Owner, Parent, Repository, StarPoint,
+};
+use crate::template::{
+    Sample, TemplateEngine, BADGE_TEMPLATE, DEFAULT_TEMPLATE, ICON_TEMPLATE, SOCIAL_TEMPLATE,
+};
+use crate::text::{strip_control_chars, FontStyle, TextMeasurer};
+use crate::theme::Theme;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Number of frames rendered for an animated card.
+pub const ANIMATION_FRAMES: usize = 24;
+
+/// Time for the counters of an animated card to reach their totals.
+pub const ANIMATION_DURATION: Duration = Duration::from_millis(1200);
+
+/// Color used for the language dot when the language has no Linguist color.
+pub(crate) const FALLBACK_LANGUAGE_COLOR: &str = "#f1e05a";
+
+/// Color of the language bar segment grouping the smallest languages.
+const OTHER_LANGUAGE_COLOR: &str = "#8b949e";
+
+/// Maximum number of languages in the language bar legend.
+pub const MAX_LEGEND: usize = 6;
+
+/// Maximum number of avatars in the contributors row.
+pub const MAX_CONTRIBUTORS: usize = 16;
+
+/// Number of avatars in the contributors row unless requested otherwise.
+pub const DEFAULT_CONTRIBUTORS: usize = 8;
+
+/// Maximum number of named segments in the language bar; the rest are grouped.
+const MAX_LANGUAGE_SEGMENTS: usize = 8;
+
+/// Share of the code below which a language is grouped into "Other".
+const MIN_LANGUAGE_SHARE: f64 = 0.01;
+
+/// Text metrics for a layout, matching the fonts and spacing of its template.
+#[derive(Debug)]
+struct LayoutMetrics {
+    /// Font of the repository name
+    name_font: FontStyle,
+    /// Space available for the repository name
+    name_max_width: f32,
+    /// Font of the description
+    description_font: FontStyle,
+    /// Space available for each description line
+    description_max_width: f32,
+    /// Maximum number of description lines before clamping with an ellipsis
+    description_max_lines: usize,
+    /// Font of the stats row
+    stats_font: FontStyle,
+    /// Space available for the stats row
+    stats_max_width: f32,
+    /// Distance from the start of a stat to its label, past the icon or dot
+    stat_label_offset: f32,
+    /// Minimum space taken by the language, keeping the stats after it aligned
+    language_slot: f32,
+    /// Minimum space taken by every other stat
+    stat_slot: f32,
+    /// Minimum space between the end of a label and the next stat
+    stat_gap: f32,
+    /// Font of the topic chips
+    topic_font: FontStyle,
+    /// Height of a topic chip
+    topic_height: f64,
+    /// Space between a chip's edge and its text
+    topic_padding: f32,
+    /// Space between topic chips
+    topic_gap: f32,
+    /// Left edge of the repository name
+    name_x: f32,
+    /// Distance from the name baseline up to the top of the status pills
+    pill_rise: f64,
+    /// Font of the status pills
+    pill_font: FontStyle,
+    /// Height of a status pill
+    pill_height: f64,
+    /// Space between a pill's edge and its text
+    pill_padding: f32,
+    /// Space between the name and each status pill
+    pill_gap: f32,
+    /// Font of the "forked from" line
+    subtitle_font: FontStyle,
+    /// Diameter of a contributor avatar
+    contributor_size: f64,
+    /// Distance between the left edges of overlapping contributor avatars
+    contributor_step: f64,
+}
+
+/// Metrics of the standard card, matching `card.svg`.
+const CARD_METRICS: LayoutMetrics = LayoutMetrics {
+    name_font: FontStyle {
+        family: "Inter",
+        size: 16.0,
+        weight: 700,
+    },
+    // Between the repo icon and the right padding
+    name_max_width: 344.0,
+    description_font: FontStyle {
+        family: "Inter",
+        size: 12.0,
+        weight: 400,
+    },
+    // Between the left and right padding
+    description_max_width: 368.0,
+    description_max_lines: 4,
+    stats_font: FontStyle {
+        family: "Inter",
+        size: 12.0,
+        weight: 400,
+    },
+    stats_max_width: 368.0,
+    stat_label_offset: 20.0,
+    language_slot: 80.0,
+    stat_slot: 60.0,
+    stat_gap: 12.0,
+    topic_font: FontStyle {
+        family: "Inter",
+        size: 11.0,
+        weight: 400,
+    },
+    topic_height: 18.0,
+    topic_padding: 8.0,
+    topic_gap: 6.0,
+    name_x: 40.0,
+    pill_rise: 15.0,
+    pill_font: FontStyle {
+        family: "Inter",
+        size: 11.0,
+        weight: 400,
+    },
+    pill_height: 18.0,
+    pill_padding: 7.0,
+    pill_gap: 6.0,
+    subtitle_font: FontStyle {
+        family: "Inter",
+        size: 12.0,
+        weight: 400,
+    },
+    contributor_size: 24.0,
+    contributor_step: 18.0,
+};
+
+/// Metrics of the social preview, matching `social.svg`.
+const SOCIAL_METRICS: LayoutMetrics = LayoutMetrics {
+    name_font: FontStyle {
+        family: "Inter",
+        size: 64.0,
+        weight: 700,
+    },
+    // Between the left margin and the avatar
+    name_max_width: 920.0,
+    description_font: FontStyle {
+        family: "Inter",
+        size: 32.0,
+        weight: 400,
+    },
+    // Between the left and right margins
+    description_max_width: 1120.0,
+    description_max_lines: 3,
+    stats_font: FontStyle {
+        family: "Inter",
+        size: 32.0,
+        weight: 400,
+    },
+    stats_max_width: 1120.0,
+    stat_label_offset: 44.0,
+    language_slot: 360.0,
+    stat_slot: 200.0,
+    stat_gap: 32.0,
+    topic_font: FontStyle {
+        family: "Inter",
+        size: 24.0,
+        weight: 400,
+    },
+    topic_height: 40.0,
+    topic_padding: 16.0,
+    topic_gap: 12.0,
+    name_x: 80.0,
+    pill_rise: 43.0,
+    pill_font: FontStyle {
+        family: "Inter",
+        size: 24.0,
+        weight: 400,
+    },
+    pill_height: 40.0,
+    pill_padding: 16.0,
+    pill_gap: 16.0,
+    subtitle_font: FontStyle {
+        family: "Inter",
+        size: 24.0,
+        weight: 400,
+    },
+    contributor_size: 56.0,
+    contributor_step: 40.0,
+};
+
+/// Side of the square icon; ICO output renders it natively at each icon size.
+pub const ICON_SIZE: u32 = 64;
+
+/// Baseline of the icon's initials, above the avatar or language dot in the corner.
+const ICON_BASELINE: f64 = 38.0;
+
+/// Most letters in an icon's initials.
+const MAX_INITIALS: usize = 2;
+
+/// Badge dimensions, small enough for tables and lists.
+const BADGE_WIDTH: u32 = 250;
+const BADGE_HEIGHT: u32 = 28;
+
+/// Baseline of the badge's single row of text.
+const BADGE_BASELINE: f64 = 18.0;
+
+/// Font of the badge's repository name, matching `badge.svg`.
+const BADGE_NAME_FONT: FontStyle = FontStyle {
+    family: "Inter",
+    size: 12.0,
+    weight: 700,
+};
+
+/// Space for the badge's repository name, between the repo icon and the language dot.
+const BADGE_NAME_MAX_WIDTH: f32 = 146.0;
+
+/// Social preview dimensions recommended for Open Graph images.
+const SOCIAL_WIDTH: u32 = 1280;
+const SOCIAL_HEIGHT: u32 = 640;
+
+/// Baseline of the social preview stats row.
+const SOCIAL_STATS_Y: f64 = 560.0;
+
+/// Baseline of the social preview name, below the owner when there is one.
+const SOCIAL_NAME_BASELINE: f64 = 220.0;
+const SOCIAL_NAME_BASELINE_WITHOUT_OWNER: f64 = 180.0;
+
+/// Baseline of the first social preview description line.
+const SOCIAL_DESCRIPTION_BASELINE: f64 = 310.0;
+
+/// Top edge of the social preview topic chips, between the description and the stats.
+const SOCIAL_TOPICS_Y: f64 = 440.0;
+
+/// Card width in pixels.
+const CARD_WIDTH: u32 = 400;
+
+/// Baseline of the repository name.
+const NAME_BASELINE: f64 = 28.0;
+
+/// Baseline of the first description line.
+const DESCRIPTION_BASELINE: f64 = 50.0;
+
+/// Distance between description baselines (1.4em at 12px).
+const DESCRIPTION_LINE_HEIGHT: f64 = 16.8;
+
+/// Space the "forked from" line adds below the repository name.
+const SUBTITLE_ROW: f64 = 18.0;
+
+/// Distance from the last line of text to the stats row baseline.
+const STATS_GAP: f64 = 33.2;
+
+/// Distance from the stats row baseline to the bottom edge of the card.
+const BOTTOM_PADDING: f64 = 25.0;
+
+/// Distance from the last line of text to the top of the language bar.
+const LANGUAGE_BAR_OFFSET: f64 = 14.0;
+
+/// Height of the language bar on regular cards.
+const LANGUAGE_BAR_HEIGHT: f64 = 8.0;
+
+/// Space the language bar adds above the stats row.
+const LANGUAGE_BAR_ROW: f64 = 20.0;
+
+/// Space the language legend adds above the stats row.
+const LEGEND_ROW: f64 = 20.0;
+
+/// Distance from the last row above the stats to the top of the topic chips.
+const TOPICS_OFFSET: f64 = 12.0;
+
+/// Space the topic chips add above the stats row.
+const TOPICS_ROW: f64 = 26.0;
+
+/// Space each additional row of stats adds when the stats wrap.
+const STATS_ROW: f64 = 20.0;
+
+/// Height of the language bar along the bottom edge of social previews.
+const SOCIAL_LANGUAGE_BAR_HEIGHT: f64 = 16.0;
+
+/// Distance from the last line of text to the top of a graph (star history or activity).
+const GRAPH_OFFSET: f64 = 14.0;
+
+/// Height of a graph on regular cards.
+const GRAPH_HEIGHT: f64 = 28.0;
+
+/// Space a graph adds above the stats row.
+const GRAPH_ROW: f64 = 42.0;
+
+/// Height of the graphs behind the social preview stats, above the bottom strip.
+const SOCIAL_GRAPH_HEIGHT: f64 = 120.0;
+
+/// Space between the star history and activity graphs when a social preview shows both.
+const SOCIAL_GRAPH_GAP: f64 = 8.0;
+
+/// Share of each week's slot in the activity graph taken by its bar.
+const ACTIVITY_BAR_SHARE: f64 = 0.7;
+
+/// Distance from the last stats row baseline to the top of the contributor avatars.
+const CONTRIBUTORS_OFFSET: f64 = 14.0;
+
+/// Distance from the bottom of the contributor avatars to the bottom edge of the card.
+const CONTRIBUTORS_BOTTOM_PADDING: f64 = 16.0;
+
+/// Space between the stats and the contributor avatars on social previews.
+const SOCIAL_CONTRIBUTORS_GAP: f32 = 32.0;
+
+/// Distance from the social preview stats baseline up to the middle of the icons.
+const SOCIAL_STATS_MIDDLE: f64 = 11.0;
+
+/// Font of the language legend, matching the `.stats` class in `card.svg`.
+const LEGEND_FONT: FontStyle = FontStyle {
+    family: "Inter",
+    size: 12.0,
+    weight: 400,
+};
+
+/// Width of a legend dot and the space before its label.
+const LEGEND_DOT_WIDTH: f32 = 18.0;
+
+/// Space between legend entries.
+const LEGEND_GAP: f32 = 16.0;
+
+/// Overall shape of a rendered card.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Layout {
+    /// Repository card sized to its content (400 pixels wide)
+    #[default]
+    Card,
+    /// Open Graph social preview image (1280x640)
+    Social,
+    /// Single-line badge with the name, language dot and stars (250x28)
+    Badge,
+    /// Square icon with the repository's initials and the owner avatar or
+    /// language dot (64x64), used for favicons
+    Icon,
+}
+
+impl Layout {
+    /// All layouts, in the order they are listed to users.
+    pub const ALL: [Layout; 4] = [Layout::Card, Layout::Social, Layout::Badge, Layout::Icon];
+
+    /// Parses a layout name, case-insensitively.
+    ///
+    /// # Returns
+    /// Some(Layout) if the name is known, None otherwise
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|layout| layout.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the name used in query parameters and on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Layout::Card => "card",
+            Layout::Social => "social",
+            Layout::Badge => "badge",
+            Layout::Icon => "icon",
+        }
+    }
+
+    /// Returns the layout used when none is requested: the square icon for
+    /// ICO output, and a regular card otherwise.
+    pub fn for_format(format: ImageFormat) -> Self {
+        match format {
+            ImageFormat::Ico => Layout::Icon,
+            _ => Layout::Card,
+        }
+    }
+
+    /// Returns the built-in template for this layout, or None to use the configured default.
+    pub fn template(&self) -> Option<&'static str> {
+        match self {
+            Layout::Card => None,
+            Layout::Social => Some(SOCIAL_TEMPLATE),
+            Layout::Badge => Some(BADGE_TEMPLATE),
+            Layout::Icon => Some(ICON_TEMPLATE),
+        }
+    }
+
+    /// Returns the exact output dimensions for layouts with a fixed size.
+    pub fn fixed_size(&self) -> Option<(u32, u32)> {
+        match self {
+            Layout::Card | Layout::Badge | Layout::Icon => None,
+            Layout::Social => Some((SOCIAL_WIDTH, SOCIAL_HEIGHT)),
+        }
+    }
+
+    /// Whether the layout only has room for the name and a few stats, leaving
+    /// out every optional section.
+    pub fn is_compact(&self) -> bool {
+        matches!(self, Layout::Badge | Layout::Icon)
+    }
+
+    /// Returns the rasterization options for this layout.
+    ///
+    /// Fixed-size layouts ignore the scale factor. Only regular cards get a
+    /// transparent margin; badges, icons and social previews are rendered edge to edge.
+    pub fn encode_options(&self, scale: Option<f64>) -> EncodeOptions {
+        match self {
+            Layout::Card => EncodeOptions::with_scale(scale),
+            Layout::Badge | Layout::Icon => EncodeOptions {
+                scale,
+                padding: false,
+                ..Default::default()
+            },
+            Layout::Social => EncodeOptions {
+                scale: None,
+                padding: false,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Returns the text metrics of the layout's built-in template.
+    ///
+    /// Badges and icons only draw the name, so they have none.
+    fn metrics(&self) -> Option<&'static LayoutMetrics> {
+        match self {
+            Layout::Card => Some(&CARD_METRICS),
+            Layout::Social => Some(&SOCIAL_METRICS),
+            Layout::Badge | Layout::Icon => None,
+        }
+    }
+}
+
+/// A piece of content that can be shown on or hidden from a card.
+///
+/// The language, stars and forks are shown unless hidden; every other
+/// section is only added when requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Section {
+    /// Primary language, with its color
+    Language,
+    /// Number of stars
+    Stars,
+    /// Number of forks
+    Forks,
+    /// Number of open issues and pull requests
+    Issues,
+    /// Number of watchers
+    Watchers,
+    /// SPDX identifier of the license
+    License,
+    /// Name of the default branch
+    Branch,
+    /// Time since the last push, e.g. `updated 3 days ago`
+    Updated,
+    /// Repository topics, as chips above the stats
+    Topics,
+    /// Stacked bar of the languages in the repository, by bytes of code
+    Languages,
+    /// Sparkline of the number of stars over time
+    #[serde(rename = "star_history")]
+    #[value(name = "star_history")]
+    StarHistory,
+    /// Bar graph of commits per week over the last year
+    Activity,
+    /// Avatars of the top contributors along the bottom
+    Contributors,
+}
+
+impl Section {
+    /// All sections, in the order they are listed to users and laid out in the stats row.
+    pub const ALL: [Section; 13] = [
+        Section::Language,
+        Section::Stars,
+        Section::Forks,
+        Section::Issues,
+        Section::Watchers,
+        Section::License,
+        Section::Branch,
+        Section::Updated,
+        Section::Topics,
+        Section::Languages,
+        Section::StarHistory,
+        Section::Activity,
+        Section::Contributors,
+    ];
+
+    /// Sections shown unless hidden.
+    pub const DEFAULT: [Section; 3] = [Section::Language, Section::Stars, Section::Forks];
+
+    /// Parses a section name, case-insensitively.
+    ///
+    /// # Returns
+    /// Some(Section) if the name is known, None otherwise
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|section| section.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Parses a comma-separated list of section names, ignoring blanks and duplicates.
+    ///
+    /// # Errors
+    /// Returns the first name that is not a known section.
+    pub fn parse_list(list: &str) -> Result<Vec<Self>, &str> {
+        let mut sections = Vec::new();
+        for name in list
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            let section = Self::parse(name).ok_or(name)?;
+            if !sections.contains(&section) {
+                sections.push(section);
+            }
+        }
+        Ok(sections)
+    }
+
+    /// Returns the name used in query parameters and on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Section::Language => "language",
+            Section::Stars => "stars",
+            Section::Forks => "forks",
+            Section::Issues => "issues",
+            Section::Watchers => "watchers",
+            Section::License => "license",
+            Section::Branch => "branch",
+            Section::Updated => "updated",
+            Section::Topics => "topics",
+            Section::Languages => "languages",
+            Section::StarHistory => "star_history",
+            Section::Activity => "activity",
+            Section::Contributors => "contributors",
+        }
+    }
+
+    /// Returns whether the section is shown unless hidden.
+    pub fn is_default(&self) -> bool {
+        Self::DEFAULT.contains(self)
+    }
+
+    /// Returns whether the section is an entry in the stats row.
+    fn is_stat(&self) -> bool {
+        !matches!(
+            self,
+            Section::Topics
+                | Section::Languages
+                | Section::StarHistory
+                | Section::Activity
+                | Section::Contributors
+        )
+    }
+}
+
+/// Presentation options for a card.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardOptions {
+    /// Color theme, with any per-request overrides already applied
+    pub theme: Theme,
+    /// Name of the template to render with
+    pub template: String,
+    /// Overall shape of the card
+    pub layout: Layout,
+    /// Sections to show in addition to the defaults
+    pub show: Vec<Section>,
+    /// Sections to hide, taking precedence over `show`
+    pub hide: Vec<Section>,
+    /// Number of languages listed beneath the language bar, at most [`MAX_LEGEND`]
+    pub legend: usize,
+    /// Number of avatars in the contributors row, at most [`MAX_CONTRIBUTORS`]
+    pub contributors: usize,
+}
+
+impl Default for CardOptions {
+    fn default() -> Self {
+        Self {
+            theme: Theme::default(),
+            template: DEFAULT_TEMPLATE.to_string(),
+            layout: Layout::default(),
+            show: Vec::new(),
+            hide: Vec::new(),
+            legend: 0,
+            contributors: DEFAULT_CONTRIBUTORS,
+        }
+    }
+}
+
+impl CardOptions {
+    /// Returns whether a section is shown, either by default or on request, and not hidden.
+    pub fn shows(&self, section: Section) -> bool {
+        !self.hide.contains(&section) && (section.is_default() || self.show.contains(&section))
+    }
+
+    /// Returns whether the card needs the repository's language breakdown.
+    pub fn needs_languages(&self) -> bool {
+        self.shows(Section::Languages) && !self.layout.is_compact()
+    }
+
+    /// Returns whether the card needs the repository's star history.
+    pub fn needs_star_history(&self) -> bool {
+        self.shows(Section::StarHistory) && !self.layout.is_compact()
+    }
+
+    /// Returns whether the card needs the repository's commit activity.
+    pub fn needs_activity(&self) -> bool {
+        self.shows(Section::Activity) && !self.layout.is_compact()
+    }
+
+    /// Returns whether the card needs the repository's contributors.
+    pub fn needs_contributors(&self) -> bool {
+        self.shows(Section::Contributors) && !self.layout.is_compact() && self.contributors > 0
+    }
+
+    /// Returns a key describing the requested content, for caching rendered cards.
+    ///
+    /// Options that select the same content give the same key regardless of
+    /// the order sections were listed in; the default content gives an empty key.
+    pub fn content_key(&self) -> String {
+        let mut key = String::new();
+        for section in Section::ALL {
+            let shown = self.shows(section);
+            if shown != section.is_default() {
+                key.push(if shown { '+' } else { '-' });
+                key.push_str(section.name());
+            }
+        }
+        if self.shows(Section::Languages) && self.legend > 0 {
+            key.push_str(&format!("+legend={}", self.legend));
+        }
+        if self.shows(Section::Contributors) && self.contributors != DEFAULT_CONTRIBUTORS {
+            key.push_str(&format!("+max={}", self.contributors));
+        }
+        key
+    }
+}
+
+/// A frame of an animated card, see [`Card::render_frames`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct AnimationFrame {
+    /// Position of the frame, from 0
+    pub index: usize,
+    /// Number of frames in the animation
+    pub count: usize,
+    /// Eased progress of the counters, from 0 on the first frame to 1 on the last
+    pub progress: f64,
+    /// Opacity of the description, fading in over the first half of the animation
+    pub fade: f64,
+}
+
+impl AnimationFrame {
+    /// Computes a frame's progress along an animation of `count` frames.
+    pub fn new(index: usize, count: usize) -> Self {
+        let t = if count > 1 {
+            (index as f64 / (count - 1) as f64).min(1.0)
+        } else {
+            1.0
+        };
+        // Ease out, so the counters slow down as they approach their totals
+        let ease = |t: f64| 1.0 - (1.0 - t).powi(3);
+        Self {
+            index,
+            count,
+            progress: ease(t),
+            fade: ease((t * 2.0).min(1.0)),
+        }
+    }
+
+    /// Scales a count to the frame's progress.
+    pub fn count(&self, total: u32) -> u32 {
+        (f64::from(total) * self.progress).round() as u32
+    }
+}
+
+/// Card geometry derived from the layout and the amount of content.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct CardLayout {
+    /// Layout the geometry was computed for
+    pub kind: Layout,
+    /// Card width in pixels
+    pub width: u32,
+    /// Card height in pixels
+    pub height: u32,
+    /// Baseline of the first description line
+    pub description_y: f64,
+    /// Baseline of the stats row
+    pub stats_y: f64,
+}
+
+/// A repository's language breakdown as a stacked bar, positioned for the layout.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LanguageBar {
+    /// Left edge of the bar
+    pub x: f64,
+    /// Top edge of the bar
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// Segments from left to right, largest language first
+    pub segments: Vec<LanguageSegment>,
+    /// Baseline of the legend row
+    pub legend_y: f64,
+    /// Largest languages that fit in the legend, empty unless requested
+    pub legend: Vec<LegendEntry>,
+}
+
+/// A language's share of the language bar.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LanguageSegment {
+    pub name: String,
+    pub color: String,
+    /// Share of the code in percent, rounded to one decimal
+    pub percent: f64,
+    /// Offset from the left edge of the bar
+    pub x: f64,
+    pub width: f64,
+}
+
+/// A language in the legend beneath the language bar.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LegendEntry {
+    pub name: String,
+    pub color: String,
+    /// Share of the code in percent, rounded to one decimal
+    pub percent: f64,
+    /// Name and share as displayed, e.g. `Rust 82.1%`
+    pub label: String,
+    /// Offset of the dot from the left edge of the bar
+    pub x: f32,
+}
+
+/// A repository's stars over time as a sparkline, positioned for the layout.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StarHistory {
+    /// Left edge of the sparkline
+    pub x: f64,
+    /// Top edge of the sparkline
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// Points of the line relative to the top left corner, for a `polyline`
+    pub line: String,
+    /// The line closed along the bottom edge, as path data for the filled area
+    pub area: String,
+    /// Month of the first star, e.g. `Mar 2019`
+    pub since: String,
+}
+
+/// A repository's commits per week as a bar graph, positioned for the layout.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActivityGraph {
+    /// Left edge of the graph
+    pub x: f64,
+    /// Top edge of the graph
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// Bars from the oldest week to the newest
+    pub bars: Vec<ActivityBar>,
+    /// Commits across all weeks
+    pub commits: u32,
+}
+
+/// A week's bar in the activity graph.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActivityBar {
+    /// Start of the week, e.g. `Jan 5, 2025`
+    pub week: String,
+    pub commits: u32,
+    /// Offset from the left edge of the graph
+    pub x: f64,
+    /// Offset from the top edge of the graph
+    pub y: f64,
+    pub width: f64,
+    /// Height of the bar, at least a pixel so empty weeks still show
+    pub height: f64,
+}
+
+/// Overlapping avatars of the top contributors.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContributorRow<'a> {
+    /// Left edge of the first avatar
+    pub x: f64,
+    /// Top edge of the avatars
+    pub y: f64,
+    /// Diameter of each avatar
+    pub size: f64,
+    /// Contributors from left to right, most commits first
+    pub contributors: Vec<ContributorAvatar<'a>>,
+}
+
+/// A contributor in the contributors row.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContributorAvatar<'a> {
+    pub login: Cow<'a, str>,
+    /// Avatar as a data URI, if available
+    pub avatar: Option<&'a str>,
+    /// Number of commits to the default branch
+    pub contributions: u32,
+    /// Offset of the avatar from the left edge of the row
+    pub x: f64,
+}
+
+/// An entry in the stats row, positioned by the layout.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Stat {
+    /// Section the stat shows, selecting its icon
+    pub section: Section,
+    /// Value as displayed, e.g. `1.2k` or `MIT`
+    pub label: String,
+    /// Offset from the start of the stats row
+    pub x: u32,
+    /// Offset from the stats row baseline, when the stats wrap onto more rows
+    pub y: u32,
+}
+
+/// Status pills shown after the repository name.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatusRow {
+    /// Top edge of the pills
+    pub y: f64,
+    /// Height of each pill
+    pub height: f64,
+    /// Pills from left to right
+    pub pills: Vec<StatusPill>,
+}
+
+/// A status pill such as `Archived` or `Fork`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatusPill {
+    pub label: &'static str,
+    /// Left edge of the pill
+    pub x: u32,
+    /// Width including padding
+    pub width: u32,
+}
+
+/// Topic chips in a single row, dropping those that don't fit.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TopicRow {
+    /// Top edge of the chips
+    pub y: f64,
+    /// Height of each chip
+    pub height: f64,
+    /// Chips from left to right, in the repository's order
+    pub chips: Vec<TopicChip>,
+}
+
+/// A topic chip in the topic row.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TopicChip {
+    pub name: String,
+    /// Offset from the left edge of the row
+    pub x: u32,
+    /// Width including padding
+    pub width: u32,
+}
+
+impl CardLayout {
+    /// Computes the geometry of a layout with the given number of description lines.
+    pub fn new(layout: Layout, description_lines: usize) -> Self {
+        match layout {
+            Layout::Card => Self::for_description_lines(description_lines),
+            Layout::Social => Self {
+                kind: Layout::Social,
+                width: SOCIAL_WIDTH,
+                height: SOCIAL_HEIGHT,
+                description_y: SOCIAL_DESCRIPTION_BASELINE,
+                stats_y: SOCIAL_STATS_Y,
+            },
+            Layout::Badge => Self {
+                kind: Layout::Badge,
+                width: BADGE_WIDTH,
+                height: BADGE_HEIGHT,
+                description_y: BADGE_BASELINE,
+                stats_y: BADGE_BASELINE,
+            },
+            Layout::Icon => Self {
+                kind: Layout::Icon,
+                width: ICON_SIZE,
+                height: ICON_SIZE,
+                description_y: ICON_BASELINE,
+                stats_y: ICON_BASELINE,
+            },
+        }
+    }
+
+    /// Computes the layout for a card with the given number of description lines.
+    ///
+    /// Two lines give the classic 400x125 card; fewer lines shrink the card
+    /// and more lines grow it, keeping the stats row clear of the text.
+    pub fn for_description_lines(lines: usize) -> Self {
+        let last_baseline = match lines {
+            0 => NAME_BASELINE,
+            n => DESCRIPTION_BASELINE + (n - 1) as f64 * DESCRIPTION_LINE_HEIGHT,
+        };
+        let stats_y = round_coordinate(last_baseline + STATS_GAP);
+
+        Self {
+            kind: Layout::Card,
+            width: CARD_WIDTH,
+            height: (stats_y + BOTTOM_PADDING).ceil() as u32,
+            description_y: DESCRIPTION_BASELINE,
+            stats_y,
+        }
+    }
+
+    /// Moves everything below the name down by `offset` pixels, growing the card.
+    fn shift_content(&mut self, offset: f64) {
+        self.description_y += offset;
+        self.stats_y += offset;
+        self.height += offset as u32;
+    }
+}
+
+/// A repository card ready to be rendered.
+#[derive(Debug, Clone)]
+pub struct Card {
+    /// Repository data shown on the card
+    pub repository: Repository,
+    /// Presentation options
+    pub options: CardOptions,
+    /// Owner avatar as a data URI, see [`crate::avatar`]
+    pub avatar: Option<String>,
+    /// Language breakdown, largest first, shown with [`Section::Languages`]
+    pub languages: Vec<LanguageBytes>,
+    /// Star counts over time, oldest first, shown with [`Section::StarHistory`]
+    pub star_history: Vec<StarPoint>,
+    /// Commits per week, oldest first, shown with [`Section::Activity`]
+    pub activity: Vec<CommitWeek>,
+    /// Top contributors with their avatars, shown with [`Section::Contributors`]
+    pub contributors: Vec<Contributor>,
+    /// Time the card is rendered at, used for [`Section::Updated`]
+    pub now: DateTime<Utc>,
+}
+
+/// A card rendered from an SVG template.
+///
+/// Each kind of card builds a context of the values its templates receive.
+/// Text fields in a context have control characters stripped; templates
+/// escape them on output.
+pub trait Render {
+    /// Values exposed to the card's templates
+    type Context<'a>: Serialize
+    where
+        Self: 'a;
+
+    /// Returns the presentation options, including the template to render with.
+    fn options(&self) -> &CardOptions;
+
+    /// Builds the values exposed to the card template.
+    fn context(&self) -> Self::Context<'_>;
+
+    /// Renders the card to an SVG document using the built-in templates.
+    ///
+    /// # Errors
+    /// Returns an error if the selected template does not exist or fails to render.
+    fn render(&self) -> Result<String, TemplateError> {
+        self.render_with(TemplateEngine::builtin())
+    }
+
+    /// Renders the card to an SVG document using templates from `engine`.
+    ///
+    /// # Errors
+    /// Returns an error if the selected template does not exist or fails to render.
+    fn render_with(&self, engine: &TemplateEngine) -> Result<String, TemplateError> {
+        engine.render(&self.options().template, self.context())
+    }
+}
+
+/// Theme colors exposed to every card template.
+#[derive(Debug, Serialize)]
+pub struct ThemeColors<'a> {
+    pub title_color: &'a str,
+    pub text_color: &'a str,
+    pub icon_color: &'a str,
+    pub bg_color: &'a str,
+    pub border_color: &'a str,
+}
+
+impl<'a> From<&'a Theme> for ThemeColors<'a> {
+    fn from(theme: &'a Theme) -> Self {
+        Self {
+            title_color: &theme.title_color,
+            text_color: &theme.text_color,
+            icon_color: &theme.icon_color,
+            bg_color: &theme.bg_color,
+            border_color: &theme.border_color,
+        }
+    }
+}
+
+/// Values exposed to card templates.
+#[derive(Debug, Serialize)]
+pub struct CardContext<'a> {
+    /// Login of the repository owner
+    pub owner: Cow<'a, str>,
+    /// Owner avatar as a data URI, if available
+    pub avatar: Option<&'a str>,
+    pub name: Cow<'a, str>,
+    /// Name truncated with an ellipsis to fit the layout, leaving room for the status pills
+    pub display_name: String,
+    /// Status pills after the name, if the repository has a status and the layout has room
+    pub status: Option<StatusRow>,
+    /// First letters of the name's words, e.g. `RL` for `rust-lang`, shown on icons
+    pub initials: String,
+    pub archived: bool,
+    pub fork: bool,
+    pub is_template: bool,
+    pub mirror: bool,
+    /// Full name of the repository a fork was created from, empty otherwise
+    pub parent: Cow<'a, str>,
+    /// The "forked from owner/repo" line, truncated to fit, empty unless shown
+    pub forked_from: String,
+    pub description: Cow<'a, str>,
+    /// Description wrapped to the card width, ending in an ellipsis if clamped
+    pub description_lines: Vec<String>,
+    /// Card dimensions and row positions for the wrapped description
+    pub layout: CardLayout,
+    /// Language breakdown bar, if requested and available for the layout
+    pub language_bar: Option<LanguageBar>,
+    /// Star history sparkline, if requested and available for the layout
+    pub star_history: Option<StarHistory>,
+    /// Commit activity graph, if requested and available for the layout
+    pub activity: Option<ActivityGraph>,
+    /// Contributor avatars, if requested and available for the layout
+    pub contributor_row: Option<ContributorRow<'a>>,
+    /// Topic chips, if requested and available for the layout
+    pub topic_row: Option<TopicRow>,
+    /// Whether each section is shown, by name
+    pub show: BTreeMap<&'static str, bool>,
+    /// Progress of the frame, if the card is rendered as an animation
+    pub frame: Option<AnimationFrame>,
+    /// Shown stats with data, positioned along the stats row
+    pub stats: Vec<Stat>,
+    pub language: Cow<'a, str>,
+    pub language_color: String,
+    pub stars: u32,
+    pub forks: u32,
+    /// Number of open issues, including pull requests
+    pub issues: u32,
+    pub watchers: u32,
+    /// SPDX identifier of the license, empty if unknown
+    pub license: Cow<'a, str>,
+    pub topics: Vec<Cow<'a, str>>,
+    pub default_branch: Cow<'a, str>,
+    /// Time since the last push, e.g. `updated 3 days ago`, empty if unknown
+    pub updated: String,
+    pub private: bool,
+    /// Theme colors, exposed as top-level variables
+    #[serde(flatten)]
+    pub colors: ThemeColors<'a>,
+}
+
+impl Card {
+    /// Creates a card from repository data and presentation options.
+    pub fn new(repository: Repository, options: CardOptions) -> Self {
+        Self {
+            repository,
+            options,
+            avatar: None,
+            languages: Vec::new(),
+            star_history: Vec::new(),
+            activity: Vec::new(),
+            contributors: Vec::new(),
+            now: Utc::now(),
+        }
+    }
+
+    /// Sets the owner avatar, as returned by [`AvatarCache::get`](crate::avatar::AvatarCache::get).
+    pub fn with_avatar(mut self, avatar: Option<String>) -> Self {
+        self.avatar = avatar;
+        self
+    }
+
+    /// Sets the language breakdown, as returned by
+    /// [`GitHubClient::get_repository_languages`](crate::github::GitHubClient::get_repository_languages).
+    pub fn with_languages(mut self, languages: Vec<LanguageBytes>) -> Self {
+        self.languages = languages;
+        self
+    }
+
+    /// Sets the star history, as returned by
+    /// [`GitHubClient::get_star_history`](crate::github::GitHubClient::get_star_history).
+    ///
+    /// The line is extended to the repository's current star count at the time
+    /// the card is rendered.
+    pub fn with_star_history(mut self, star_history: Vec<StarPoint>) -> Self {
+        self.star_history = star_history;
+        self
+    }
+
+    /// Sets the commit activity, as returned by
+    /// [`GitHubClient::get_commit_activity`](crate::github::GitHubClient::get_commit_activity).
+    pub fn with_activity(mut self, activity: Vec<CommitWeek>) -> Self {
+        self.activity = activity;
+        self
+    }
+
+    /// Sets the top contributors, as returned by
+    /// [`GitHubClient::get_contributors`](crate::github::GitHubClient::get_contributors)
+    /// with their avatars filled in.
+    pub fn with_contributors(mut self, contributors: Vec<Contributor>) -> Self {
+        self.contributors = contributors;
+        self
+    }
+
+    /// Sets the time the card is rendered at, instead of the time it was created.
+    pub fn at(mut self, now: DateTime<Utc>) -> Self {
+        self.now = now;
+        self
+    }
+
+    /// Builds the values exposed to the card template for a frame of an animation.
+    ///
+    /// Stats are positioned for their final values, so the row doesn't shift
+    /// as the counters tick up.
+    fn frame_context(&self, frame: Option<AnimationFrame>) -> CardContext<'_> {
+        let repo = &self.repository;
+        let theme = &self.options.theme;
+
+        let measurer = TextMeasurer::shared();
+        let metrics = self.options.layout.metrics();
+
+        let name = strip_control_chars(&repo.name);
+        let description = strip_control_chars(repo.description.as_deref().unwrap_or_default());
+        let language = strip_control_chars(repo.language.as_deref().unwrap_or_default());
+        let language_color = crate::colors::get_color(&language)
+            .unwrap_or_else(|| FALLBACK_LANGUAGE_COLOR.to_string());
+
+        let description_lines = match metrics {
+            Some(metrics) => measurer.wrap(
+                &description,
+                &metrics.description_font,
+                metrics.description_max_width,
+                metrics.description_max_lines,
+            ),
+            None => Vec::new(),
+        };
+
+        let license = Cow::Borrowed(
+            repo.license
+                .as_ref()
+                .and_then(License::spdx)
+                .unwrap_or_default(),
+        );
+        let default_branch = strip_control_chars(&repo.default_branch);
+        let updated = repo
+            .pushed_at
+            .map(|pushed_at| updated_label(pushed_at, self.now))
+            .unwrap_or_default();
+
+        let parent = repo
+            .parent
+            .as_ref()
+            .map(|parent| strip_control_chars(&parent.full_name))
+            .unwrap_or_default();
+        let forked_from = match metrics {
+            Some(metrics) if repo.fork && !parent.is_empty() => measurer.truncate(
+                &format!("forked from {}", parent),
+                &metrics.subtitle_font,
+                metrics.name_max_width,
+            ),
+            _ => String::new(),
+        };
+        let (display_name, status) = self.name_and_status(&name, measurer);
+
+        let mut layout = CardLayout::new(self.options.layout, description_lines.len());
+        if !forked_from.is_empty() && layout.kind == Layout::Card {
+            layout.shift_content(SUBTITLE_ROW);
+        }
+        let language_bar = self.language_bar(&mut layout, measurer);
+        let (star_history, activity) = self.graphs(&mut layout);
+        let topic_row = self.topic_row(&mut layout, measurer);
+
+        // Labels of the shown stats that have data, in row order
+        let labels: Vec<(Section, String)> = Section::ALL
+            .into_iter()
+            .filter(|section| section.is_stat() && self.options.shows(*section))
+            .filter_map(|section| {
+                let label = match section {
+                    Section::Language => language.to_string(),
+                    Section::Stars => format_count(repo.stargazers_count),
+                    Section::Forks => format_count(repo.forks_count),
+                    Section::Issues => format_count(repo.open_issues_count),
+                    Section::Watchers => format_count(repo.subscribers_count),
+                    Section::License => license.to_string(),
+                    Section::Branch => default_branch.to_string(),
+                    Section::Updated => updated.clone(),
+                    Section::Topics
+                    | Section::Languages
+                    | Section::StarHistory
+                    | Section::Activity
+                    | Section::Contributors => String::new(),
+                };
+                (!label.is_empty()).then_some((section, label))
+            })
+            .collect();
+        let contributors = self.contributor_avatars(&layout);
+        // Social previews put the avatars at the end of the stats row
+        let reserved = match (layout.kind, contributors.last()) {
+            (Layout::Social, Some(last)) => {
+                (last.x + SOCIAL_METRICS.contributor_size) as f32 + SOCIAL_CONTRIBUTORS_GAP
+            }
+            _ => 0.0,
+        };
+        let mut stats = layout_stats(&mut layout, measurer, labels, reserved);
+        let contributor_row = place_contributors(&mut layout, contributors);
+
+        let count = |total: u32| frame.map_or(total, |frame| frame.count(total));
+        if frame.is_some() {
+            for stat in &mut stats {
+                let total = match stat.section {
+                    Section::Stars => repo.stargazers_count,
+                    Section::Forks => repo.forks_count,
+                    Section::Issues => repo.open_issues_count,
+                    Section::Watchers => repo.subscribers_count,
+                    _ => continue,
+                };
+                stat.label = format_count(count(total));
+            }
+        }
+
+        CardContext {
+            owner: strip_control_chars(&repo.owner.login),
+            avatar: self.avatar.as_deref(),
+            display_name,
+            initials: initials(&name),
+            name,
+            status,
+            archived: repo.archived,
+            fork: repo.fork,
+            is_template: repo.is_template,
+            mirror: repo
+                .mirror_url
+                .as_deref()
+                .is_some_and(|url| !url.is_empty()),
+            parent,
+            forked_from,
+            layout,
+            language_bar,
+            star_history,
+            activity,
+            contributor_row,
+            topic_row,
+            show: Section::ALL
+                .into_iter()
+                .map(|section| (section.name(), self.options.shows(section)))
+                .collect(),
+            frame,
+            stats,
+            description_lines,
+            description,
+            language,
+            language_color,
+            stars: count(repo.stargazers_count),
+            forks: count(repo.forks_count),
+            issues: count(repo.open_issues_count),
+            watchers: count(repo.subscribers_count),
+            license,
+            topics: repo
+                .topics
+                .iter()
+                .map(|topic| strip_control_chars(topic))
+                .collect(),
+            default_branch,
+            updated,
+            private: repo.private,
+            colors: ThemeColors::from(theme),
+        }
+    }
+
+    /// Renders the card as the frames of an animation using the built-in templates.
+    ///
+    /// The counters tick up from zero and the description fades in; the last
+    /// frame is the same as the still card.
+    ///
+    /// # Errors
+    /// Returns an error if the selected template does not exist or fails to render.
+    pub fn render_frames(&self, count: usize) -> Result<Vec<String>, TemplateError> {
+        self.render_frames_with(TemplateEngine::builtin(), count)
+    }
+
+    /// Renders the card as the frames of an animation using templates from `engine`.
+    ///
+    /// # Errors
+    /// Returns an error if the selected template does not exist or fails to render.
+    pub fn render_frames_with(
+        &self,
+        engine: &TemplateEngine,
+        count: usize,
+    ) -> Result<Vec<String>, TemplateError> {
+        (0..count)
+            .map(|index| {
+                let frame = AnimationFrame::new(index, count);
+                engine.render(&self.options.template, self.frame_context(Some(frame)))
+            })
+            .collect()
+    }
+
+    /// Returns the repository's status labels, in display order.
+    fn status_labels(&self) -> Vec<&'static str> {
+        let repo = &self.repository;
+        [
+            (repo.archived, "Archived"),
+            (repo.fork, "Fork"),
+            (repo.is_template, "Template"),
+            (
+                repo.mirror_url
+                    .as_deref()
+                    .is_some_and(|url| !url.is_empty()),
+                "Mirror",
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(status, label)| status.then_some(label))
+        .collect()
+    }
+
+    /// Truncates the name to leave room for the status pills, then positions the pills after it.
+    ///
+    /// # Returns
+    /// The display name, and the status pills unless the repository has no
+    /// status or the layout has no room for them
+    fn name_and_status(&self, name: &str, measurer: &TextMeasurer) -> (String, Option<StatusRow>) {
+        let Some(metrics) = self.options.layout.metrics() else {
+            // Icons only show the name in their title, so it is kept whole
+            let display_name = match self.options.layout {
+                Layout::Badge => measurer.truncate(name, &BADGE_NAME_FONT, BADGE_NAME_MAX_WIDTH),
+                _ => name.to_string(),
+            };
+            return (display_name, None);
+        };
+        let labels = self.status_labels();
+
+        let widths: Vec<f32> = labels
+            .iter()
+            .map(|label| {
+                (measurer.width(label, &metrics.pill_font) + 2.0 * metrics.pill_padding).ceil()
+            })
+            .collect();
+        let reserved: f32 = widths.iter().map(|width| width + metrics.pill_gap).sum();
+        let display_name =
+            measurer.truncate(name, &metrics.name_font, metrics.name_max_width - reserved);
+        if labels.is_empty() {
+            return (display_name, None);
+        }
+
+        let mut x = metrics.name_x + measurer.width(&display_name, &metrics.name_font);
+        let mut pills = Vec::new();
+        for (label, width) in labels.into_iter().zip(widths) {
+            x += metrics.pill_gap;
+            pills.push(StatusPill {
+                label,
+                x: x.ceil() as u32,
+                width: width as u32,
+            });
+            x += width;
+        }
+
+        let baseline = match self.options.layout {
+            Layout::Social if self.repository.owner.login.is_empty() => {
+                SOCIAL_NAME_BASELINE_WITHOUT_OWNER
+            }
+            Layout::Social => SOCIAL_NAME_BASELINE,
+            Layout::Card | Layout::Badge | Layout::Icon => NAME_BASELINE,
+        };
+        let status = StatusRow {
+            y: baseline - metrics.pill_rise,
+            height: metrics.pill_height,
+            pills,
+        };
+        (display_name, Some(status))
+    }
+
+    /// Builds the language bar for the layout, making room for it in `layout`.
+    ///
+    /// # Returns
+    /// None unless the bar was requested, the layout has room for it and the
+    /// repository has language data
+    fn language_bar(
+        &self,
+        layout: &mut CardLayout,
+        measurer: &TextMeasurer,
+    ) -> Option<LanguageBar> {
+        if !self.options.shows(Section::Languages) || self.languages.is_empty() {
+            return None;
+        }
+
+        match layout.kind {
+            Layout::Badge | Layout::Icon => None,
+            // Replaces the accent strip along the bottom edge
+            Layout::Social => {
+                let width = f64::from(layout.width);
+                Some(LanguageBar {
+                    x: 0.0,
+                    y: f64::from(layout.height) - SOCIAL_LANGUAGE_BAR_HEIGHT,
+                    width,
+                    height: SOCIAL_LANGUAGE_BAR_HEIGHT,
+                    segments: language_segments(&self.languages, width),
+                    legend_y: 0.0,
+                    legend: Vec::new(),
+                })
+            }
+            // Inserted between the description and the stats row
+            Layout::Card => {
+                let width = f64::from(CARD_METRICS.description_max_width);
+                let y = layout.stats_y - STATS_GAP + LANGUAGE_BAR_OFFSET;
+                let segments = language_segments(&self.languages, width);
+                let legend = layout_legend(
+                    measurer,
+                    &segments[..segments.len().min(self.options.legend.min(MAX_LEGEND))],
+                    CARD_METRICS.description_max_width,
+                );
+
+                let extra = LANGUAGE_BAR_ROW + if legend.is_empty() { 0.0 } else { LEGEND_ROW };
+                layout.stats_y += extra;
+                layout.height += extra as u32;
+
+                Some(LanguageBar {
+                    x: 16.0,
+                    y,
+                    width,
+                    height: LANGUAGE_BAR_HEIGHT,
+                    segments,
+                    legend_y: y + LANGUAGE_BAR_HEIGHT + LEGEND_ROW,
+                    legend,
+                })
+            }
+        }
+    }
+
+    /// Builds the star history and commit activity graphs for the layout,
+    /// making room for them in `layout`.
+    ///
+    /// Regular cards stack the graphs below the description. Social previews
+    /// draw them behind the stats, splitting the space between them when both
+    /// are shown, with the star history on top.
+    ///
+    /// # Returns
+    /// Each graph, or None unless it was requested, the layout has room for
+    /// it and it has data
+    fn graphs(&self, layout: &mut CardLayout) -> (Option<StarHistory>, Option<ActivityGraph>) {
+        let Some(bounds) = graph_bounds(layout) else {
+            return (None, None);
+        };
+        let star_history = self.star_history(bounds);
+        if star_history.is_some() {
+            grow_for_graph(layout);
+        }
+
+        if layout.kind != Layout::Social {
+            let activity = graph_bounds(layout).and_then(|bounds| self.activity_graph(bounds));
+            if activity.is_some() {
+                grow_for_graph(layout);
+            }
+            return (star_history, activity);
+        }
+
+        let activity = self.activity_graph(bounds);
+        if star_history.is_none() || activity.is_none() {
+            return (star_history, activity);
+        }
+        let (x, y, width, height) = bounds;
+        let half = (height - SOCIAL_GRAPH_GAP) / 2.0;
+        (
+            self.star_history((x, y, width, half)),
+            self.activity_graph((x, y + half + SOCIAL_GRAPH_GAP, width, half)),
+        )
+    }
+
+    /// Builds the star history sparkline within a box.
+    ///
+    /// # Arguments
+    /// * `bounds` - Left and top edges, width and height of the box
+    ///
+    /// # Returns
+    /// None unless the star history was requested and spans some time
+    fn star_history(&self, bounds: (f64, f64, f64, f64)) -> Option<StarHistory> {
+        if !self.options.shows(Section::StarHistory) {
+            return None;
+        }
+
+        let mut points = self.star_history.clone();
+        // Sampling stops short of the newest stars; end the line at the current count
+        if points.last().is_some_and(|last| last.time < self.now) {
+            points.push(StarPoint {
+                time: self.now,
+                stars: self.repository.stargazers_count,
+            });
+        }
+
+        let (x, y, width, height) = bounds;
+        let (line, area) = sparkline(&points, width, height)?;
+
+        Some(StarHistory {
+            x,
+            y,
+            width,
+            height,
+            line,
+            area,
+            since: points[0].time.format("%b %Y").to_string(),
+        })
+    }
+
+    /// Builds the commit activity graph within a box.
+    ///
+    /// # Arguments
+    /// * `bounds` - Left and top edges, width and height of the box
+    ///
+    /// # Returns
+    /// None unless the activity was requested and is available
+    fn activity_graph(&self, bounds: (f64, f64, f64, f64)) -> Option<ActivityGraph> {
+        if !self.options.shows(Section::Activity) || self.activity.is_empty() {
+            return None;
+        }
+
+        let (x, y, width, height) = bounds;
+        let max = self.activity.iter().map(|week| week.total).max()?.max(1);
+        let slot = width / self.activity.len() as f64;
+        let bar_width = round_coordinate(slot * ACTIVITY_BAR_SHARE);
+
+        let bars = self
+            .activity
+            .iter()
+            .enumerate()
+            .map(|(index, week)| {
+                let bar_height =
+                    round_coordinate(f64::from(week.total) / f64::from(max) * height).max(1.0);
+                ActivityBar {
+                    week: DateTime::from_timestamp(week.week, 0)
+                        .map(|start| start.format("%b %-d, %Y").to_string())
+                        .unwrap_or_default(),
+                    commits: week.total,
+                    x: round_coordinate(index as f64 * slot + (slot - bar_width) / 2.0),
+                    y: round_coordinate(height - bar_height),
+                    width: bar_width,
+                    height: bar_height,
+                }
+            })
+            .collect();
+
+        Some(ActivityGraph {
+            x,
+            y,
+            width,
+            height,
+            bars,
+            commits: self.activity.iter().map(|week| week.total).sum(),
+        })
+    }
+
+    /// Lines up the avatars of the top contributors, overlapping each other.
+    ///
+    /// # Returns
+    /// The avatars with their offsets from the left edge of the row, empty
+    /// unless contributors were requested and the layout has room for them
+    fn contributor_avatars(&self, layout: &CardLayout) -> Vec<ContributorAvatar<'_>> {
+        let Some(metrics) = layout.kind.metrics() else {
+            return Vec::new();
+        };
+        if !self.options.shows(Section::Contributors) {
+            return Vec::new();
+        }
+
+        self.contributors
+            .iter()
+            .take(self.options.contributors.min(MAX_CONTRIBUTORS))
+            .enumerate()
+            .map(|(index, contributor)| ContributorAvatar {
+                login: strip_control_chars(&contributor.login),
+                avatar: contributor.avatar.as_deref(),
+                contributions: contributor.contributions,
+                x: index as f64 * metrics.contributor_step,
+            })
+            .collect()
+    }
+
+    /// Builds the topic chips for the layout, making room for them in `layout`.
+    ///
+    /// # Returns
+    /// None unless topics were requested, the layout has room for them and the
+    /// repository has topics
+    fn topic_row(&self, layout: &mut CardLayout, measurer: &TextMeasurer) -> Option<TopicRow> {
+        if !self.options.shows(Section::Topics) || self.repository.topics.is_empty() {
+            return None;
+        }
+
+        let metrics = layout.kind.metrics()?;
+        let y = match layout.kind {
+            Layout::Badge | Layout::Icon => return None,
+            // Between the description and the stats, which stay in place
+            Layout::Social => SOCIAL_TOPICS_Y,
+            // Below the description and language bar, pushing the stats down
+            Layout::Card => layout.stats_y - STATS_GAP + TOPICS_OFFSET,
+        };
+
+        let chips: Vec<TopicChip> = layout_row(
+            self.repository
+                .topics
+                .iter()
+                .map(|topic| strip_control_chars(topic)),
+            metrics.description_max_width,
+            metrics.topic_gap,
+            |name| (measurer.width(name, &metrics.topic_font) + 2.0 * metrics.topic_padding).ceil(),
+        )
+        .into_iter()
+        .map(|(name, x, width)| TopicChip {
+            name: name.into_owned(),
+            x: x as u32,
+            width: width as u32,
+        })
+        .collect();
+        if chips.is_empty() {
+            return None;
+        }
+
+        if layout.kind == Layout::Card {
+            layout.stats_y += TOPICS_ROW;
+            layout.height += TOPICS_ROW as u32;
+        }
+
+        Some(TopicRow {
+            y,
+            height: metrics.topic_height,
+            chips,
+        })
+    }
+}
+
+impl Render for Card {
+    type Context<'a> = CardContext<'a>;
+
+    fn options(&self) -> &CardOptions {
+        &self.options
+    }
+
+    fn context(&self) -> CardContext<'_> {
+        self.frame_context(None)
+    }
+}
+
+impl Sample for Card {
+    fn sample() -> Self {
+        let repository = Repository {
+            name: "glim".to_string(),
+            owner: Owner {
+                login: "Xevion".to_string(),
+                avatar_url: String::new(),
+            },
+            description: Some(
+                "Generate <beautiful> GitHub repository cards & serve them over HTTP".to_string(),
+            ),
+            language: Some("Rust".to_string()),
+            stargazers_count: 1234,
+            forks_count: 56,
+            private: false,
+            open_issues_count: 7,
+            subscribers_count: 12,
+            license: Some(License {
+                spdx_id: Some("MIT".to_string()),
+            }),
+            topics: vec!["github".to_string(), "svg".to_string()],
+            default_branch: "master".to_string(),
+            pushed_at: Some(sample_time()),
+            archived: true,
+            fork: true,
+            parent: Some(Parent {
+                full_name: "octocat/glim".to_string(),
+            }),
+            is_template: false,
+            mirror_url: None,
+        };
+
+        let options = CardOptions {
+            show: Section::ALL.to_vec(),
+            legend: 3,
+            ..Default::default()
+        };
+        let languages = crate::github::sort_languages([
+            ("Rust".to_string(), 52_000),
+            ("HTML".to_string(), 9_000),
+            ("Dockerfile".to_string(), 1_200),
+            ("Shell".to_string(), 300),
+        ]);
+
+        let star_history = [(0, 1), (120, 300), (400, 900), (700, 1100)]
+            .into_iter()
+            .map(|(days, stars)| StarPoint {
+                time: sample_time() - chrono::Duration::days(days),
+                stars,
+            })
+            .rev()
+            .collect();
+
+        let activity = (0..52)
+            .map(|week| CommitWeek {
+                week: sample_time().timestamp() - (52 - week) * 7 * 24 * 60 * 60,
+                total: (week * 7 % 11) as u32,
+            })
+            .collect();
+
+        let contributors = ["Xevion", "octocat"]
+            .into_iter()
+            .zip([Some(crate::avatar::SAMPLE_AVATAR.to_string()), None])
+            .map(|(login, avatar)| Contributor {
+                login: login.to_string(),
+                contributions: 12,
+                avatar,
+                ..Default::default()
+            })
+            .collect();
+
+        Self::new(repository, options)
+            .with_avatar(Some(crate::avatar::SAMPLE_AVATAR.to_string()))
+            .with_contributors(contributors)
+            .with_languages(languages)
+            .with_star_history(star_history)
+            .with_activity(activity)
+            .at(sample_time() + chrono::Duration::days(3))
+    }
+}
+
+/// Returns a fixed time for sample cards, so validation doesn't depend on the clock.
+fn sample_time() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()
+}
+
+/// Returns up to [`MAX_INITIALS`] uppercase letters starting the words of a
+/// name, splitting words on punctuation and camelCase humps.
+fn initials(name: &str) -> String {
+    let mut initials = String::new();
+    let mut previous: Option<char> = None;
+
+    for c in name.chars() {
+        let starts_word = c.is_alphanumeric()
+            && previous.is_none_or(|previous| {
+                !previous.is_alphanumeric() || (previous.is_lowercase() && c.is_uppercase())
+            });
+        if starts_word {
+            initials.extend(c.to_uppercase());
+            if initials.chars().count() >= MAX_INITIALS {
+                break;
+            }
+        }
+        previous = Some(c);
+    }
+
+    initials
+}
+
+/// Abbreviates a count the way the `format_count` template filter does.
+fn format_count(count: u32) -> String {
+    crate::image::format_count(&count.to_string())
+}
+
+/// Describes how long ago a repository was last pushed to.
+///
+/// # Arguments
+/// * `pushed_at` - Time of the last push
+/// * `now` - Time the card is rendered at
+///
+/// # Returns
+/// A label such as `updated today`, `updated 3 days ago` or `updated 2 years ago`
+pub fn updated_label(pushed_at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    age_label("updated", pushed_at, now)
+}
+
+/// Describes how long ago something happened, in days, months or years.
+///
+/// # Arguments
+/// * `action` - What happened, e.g. `updated` or `opened`
+/// * `time` - When it happened
+/// * `now` - Time the card is rendered at
+///
+/// # Returns
+/// A label such as `opened today`, `opened yesterday` or `opened 3 days ago`
+pub fn age_label(action: &str, time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    // Times in the future (clock skew) count as today
+    let days = (now - time).num_days().max(0);
+    let (count, unit) = match days {
+        0 => return format!("{} today", action),
+        1 => return format!("{} yesterday", action),
+        2..=59 => (days, "day"),
+        60..=729 => (days / 30, "month"),
+        _ => (days / 365, "year"),
+    };
+    format!("{} {} {}s ago", action, count, unit)
+}
+
+/// Positions the contributor avatars, making room for them in `layout`.
+///
+/// Regular cards get a row below the stats; social previews put the avatars
+/// at the right end of the stats row, where [`layout_stats`] left room for them.
+///
+/// # Returns
+/// None if there are no avatars to show
+fn place_contributors<'a>(
+    layout: &mut CardLayout,
+    contributors: Vec<ContributorAvatar<'a>>,
+) -> Option<ContributorRow<'a>> {
+    let metrics = layout.kind.metrics()?;
+    let width = contributors.last()?.x + metrics.contributor_size;
+
+    let (x, y) = match layout.kind {
+        Layout::Badge | Layout::Icon => return None,
+        Layout::Social => (
+            f64::from(layout.width) - f64::from(metrics.name_x) - width,
+            layout.stats_y - SOCIAL_STATS_MIDDLE - metrics.contributor_size / 2.0,
+        ),
+        Layout::Card => {
+            // Below the last row of stats, which may have wrapped
+            let y = f64::from(layout.height) - BOTTOM_PADDING + CONTRIBUTORS_OFFSET;
+            let bottom = y + metrics.contributor_size + CONTRIBUTORS_BOTTOM_PADDING;
+            layout.height = bottom.ceil() as u32;
+            (16.0, y)
+        }
+    };
+
+    Some(ContributorRow {
+        x,
+        y,
+        size: metrics.contributor_size,
+        contributors,
+    })
+}
+
+/// Returns where the next graph goes: its left and top edges, width and height.
+///
+/// On regular cards graphs are stacked below the description and language
+/// bar; on social previews they share the space behind the stats, above the
+/// accent strip or language bar. Badges and icons have no room for graphs.
+fn graph_bounds(layout: &CardLayout) -> Option<(f64, f64, f64, f64)> {
+    match layout.kind {
+        Layout::Badge | Layout::Icon => None,
+        Layout::Social => {
+            let y = f64::from(layout.height) - SOCIAL_LANGUAGE_BAR_HEIGHT - SOCIAL_GRAPH_HEIGHT;
+            Some((0.0, y, f64::from(layout.width), SOCIAL_GRAPH_HEIGHT))
+        }
+        Layout::Card => Some((
+            16.0,
+            layout.stats_y - STATS_GAP + GRAPH_OFFSET,
+            f64::from(CARD_METRICS.description_max_width),
+            GRAPH_HEIGHT,
+        )),
+    }
+}
+
+/// Pushes the stats down to make room for a graph on regular cards.
+fn grow_for_graph(layout: &mut CardLayout) {
+    if layout.kind == Layout::Card {
+        layout.stats_y += GRAPH_ROW;
+        layout.height += GRAPH_ROW as u32;
+    }
+}
+
+/// Scales star counts over time to fit a box, from zero stars at the bottom
+/// edge to the most stars at the top.
+///
+/// # Arguments
+/// * `points` - Star counts over time, oldest first
+/// * `width` - Width of the box in pixels
+/// * `height` - Height of the box in pixels
+///
+/// # Returns
+/// The `polyline` points and the closed area path data, or None if the points
+/// span no time or have no stars
+fn sparkline(points: &[StarPoint], width: f64, height: f64) -> Option<(String, String)> {
+    let (first, last) = (points.first()?, points.last()?);
+    let span = (last.time - first.time).num_seconds() as f64;
+    let max = points.iter().map(|point| point.stars).max()?;
+    if span <= 0.0 || max == 0 {
+        return None;
+    }
+
+    let coordinates: Vec<String> = points
+        .iter()
+        .map(|point| {
+            let x = (point.time - first.time).num_seconds() as f64 / span * width;
+            let y = height - f64::from(point.stars) / f64::from(max) * height;
+            format!("{},{}", round_coordinate(x), round_coordinate(y))
+        })
+        .collect();
+    let line = coordinates.join(" ");
+    let area = format!("M0,{} L{} L{},{} Z", height, line, width, height);
+
+    Some((line, area))
+}
+
+/// Rounds a coordinate to a tenth of a pixel to keep documents small.
+pub(crate) fn round_coordinate(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// Positions stats along the stats row, making room for extra rows in `layout`.
+///
+/// Each stat takes at least its layout's slot width so the default stats
+/// line up regardless of their values. Regular cards wrap stats that don't
+/// fit onto more rows below; other layouts drop them.
+///
+/// # Arguments
+/// * `reserved` - Space to leave free at the end of the row
+fn layout_stats(
+    layout: &mut CardLayout,
+    measurer: &TextMeasurer,
+    labels: Vec<(Section, String)>,
+    reserved: f32,
+) -> Vec<Stat> {
+    let Some(metrics) = layout.kind.metrics() else {
+        return Vec::new();
+    };
+
+    let mut stats = Vec::new();
+    let (mut x, mut row) = (0.0, 0);
+    for (section, label) in labels {
+        let slot = match section {
+            Section::Language => metrics.language_slot,
+            _ => metrics.stat_slot,
+        };
+        let width = metrics.stat_label_offset + measurer.width(&label, &metrics.stats_font);
+        if x > 0.0 && x + width > metrics.stats_max_width - reserved {
+            if layout.kind != Layout::Card {
+                break;
+            }
+            x = 0.0;
+            row += 1;
+        }
+
+        stats.push(Stat {
+            section,
+            label,
+            x: x.ceil() as u32,
+            y: row * STATS_ROW as u32,
+        });
+        x += slot.max(width + metrics.stat_gap);
+    }
+
+    let extra = f64::from(row) * STATS_ROW;
+    layout.height += extra as u32;
+    stats
+}
+
+/// Splits a bar of `width` pixels between languages in proportion to their bytes of code.
+///
+/// The largest languages get their own segment; languages beyond
+/// [`MAX_LANGUAGE_SEGMENTS`] or under [`MIN_LANGUAGE_SHARE`] of the code are
+/// grouped into a final "Other" segment.
+fn language_segments(languages: &[LanguageBytes], width: f64) -> Vec<LanguageSegment> {
+    let total: u64 = languages.iter().map(|language| language.bytes).sum();
+    if total == 0 {
+        return Vec::new();
+    }
+
+    let mut shares: Vec<(Cow<'_, str>, String, u64)> = Vec::new();
+    let mut other = 0;
+    for language in languages {
+        let share = language.bytes as f64 / total as f64;
+        if shares.len() < MAX_LANGUAGE_SEGMENTS && share >= MIN_LANGUAGE_SHARE {
+            let name = strip_control_chars(&language.name);
+            let color = crate::colors::get_color(&name)
+                .unwrap_or_else(|| FALLBACK_LANGUAGE_COLOR.to_string());
+            shares.push((name, color, language.bytes));
+        } else {
+            other += language.bytes;
+        }
+    }
+    if other > 0 {
+        shares.push((
+            Cow::Borrowed("Other"),
+            OTHER_LANGUAGE_COLOR.to_string(),
+            other,
+        ));
+    }
+
+    // Place edges on a running total so the segments always fill the bar exactly
+    let round = |value: f64| (value * 100.0).round() / 100.0;
+    let mut running = 0;
+    shares
+        .into_iter()
+        .map(|(name, color, bytes)| {
+            let x = round(width * running as f64 / total as f64);
+            running += bytes;
+            let end = round(width * running as f64 / total as f64);
+            LanguageSegment {
+                name: name.into_owned(),
+                color,
+                percent: (bytes as f64 / total as f64 * 1000.0).round() / 10.0,
+                x,
+                width: round(end - x),
+            }
+        })
+        .collect()
+}
+
+/// Builds the legend entries that fit on one row, see [`layout_row`].
+fn layout_legend(
+    measurer: &TextMeasurer,
+    segments: &[LanguageSegment],
+    max_width: f32,
+) -> Vec<LegendEntry> {
+    layout_row(
+        segments.iter().map(|segment| {
+            let label = format!("{} {:.1}%", segment.name, segment.percent);
+            (segment, label)
+        }),
+        max_width,
+        LEGEND_GAP,
+        |(_, label)| LEGEND_DOT_WIDTH + measurer.width(label, &LEGEND_FONT),
+    )
+    .into_iter()
+    .map(|((segment, label), x, _)| LegendEntry {
+        name: segment.name.clone(),
+        color: segment.color.clone(),
+        percent: segment.percent,
+        label,
+        x,
+    })
+    .collect()
+}
+
+/// Positions items left to right, dropping those that don't fit on the row.
+///
+/// Items are placed in order and the row ends at the first one that would
+/// overflow, so later, narrower items never jump ahead of it.
+///
+/// # Arguments
+/// * `max_width` - Space available for the row
+/// * `gap` - Space between items
+/// * `width` - Width of an item, including its icon or padding
+///
+/// # Returns
+/// The items that fit, each with its offset from the left edge of the row and its width
+pub(crate) fn layout_row<T>(
+    items: impl IntoIterator<Item = T>,
+    max_width: f32,
+    gap: f32,
+    width: impl Fn(&T) -> f32,
+) -> Vec<(T, f32, f32)> {
+    let mut row = Vec::new();
+    let mut x = 0.0;
+
+    for item in items {
+        let item_width = width(&item);
+        if x + item_width > max_width {
+            break;
+        }
+
+        row.push((item, x, item_width));
+        x += item_width + gap;
+    }
+
+    row
+}
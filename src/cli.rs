@@ -2,16 +2,27 @@
 //!
 //! Handles CLI argument parsing and execution logic for generating repository cards.
 
-use crate::errors::Result;
-use clap::Parser;
+use crate::errors::{ImageError, Result};
+use clap::{Parser, Subcommand};
 use std::fs::File;
 use std::io::BufWriter;
 use std::path::PathBuf;
 use tracing::Level;
 
 use crate::{
-    encode::{create_encoder, Encoder, ImageFormat},
+    avatar,
+    card::{
+        Card, CardOptions, Layout, Render, Section, ANIMATION_DURATION, ANIMATION_FRAMES,
+        DEFAULT_CONTRIBUTORS, MAX_CONTRIBUTORS, MAX_LEGEND,
+    },
+    config::Config,
+    encode::{create_encoder, Animation, Compression, EncodeOptions, Encoder, ImageFormat},
     github,
+    issue::IssueCard,
+    profile::{OrgCard, UserCard},
+    release::ReleaseCard,
+    template::{TemplateEngine, TemplateKind},
+    theme::{ColorOverrides, ThemeRegistry},
 };
 
 /// Command-line arguments for glim.
@@ -21,12 +32,15 @@ pub struct Cli {
     /// The repository to generate a card for, in the format `owner/repo`.
     pub repository: Option<String>,
 
-    /// The output path for the generated card.
-    #[arg(short, long)]
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// The output path for the generated card; its extension selects the format (PNG by default).
+    #[arg(short, long, global = true)]
     pub output: Option<PathBuf>,
 
     /// GitHub token to use for API requests.
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub token: Option<String>,
 
     /// Start the HTTP server.
@@ -40,86 +54,360 @@ pub struct Cli {
     pub server: Option<Option<String>>,
 
     /// Set the logging level.
-    #[arg(long, short = 'L', value_name = "LEVEL", default_value_t = if cfg!(debug_assertions) { Level::DEBUG } else { Level::INFO })]
+    #[arg(long, short = 'L', value_name = "LEVEL", global = true, default_value_t = if cfg!(debug_assertions) { Level::DEBUG } else { Level::INFO })]
     pub log_level: Level,
 
     /// Port to use for the server (defaults to 8080).
     #[arg(short, long)]
     pub port: Option<u16>,
+
+    /// Color theme for the card (light, dark, high-contrast, or a custom theme).
+    #[arg(long, value_name = "THEME", global = true)]
+    pub theme: Option<String>,
+
+    /// Repository name color override (hex, e.g. `#58a6ff`).
+    #[arg(long, value_name = "HEX", global = true)]
+    pub title_color: Option<String>,
+
+    /// Description and stats text color override (hex).
+    #[arg(long, value_name = "HEX", global = true)]
+    pub text_color: Option<String>,
+
+    /// Icon color override (hex).
+    #[arg(long, value_name = "HEX", global = true)]
+    pub icon_color: Option<String>,
+
+    /// Background color override (hex).
+    #[arg(long, value_name = "HEX", global = true)]
+    pub bg_color: Option<String>,
+
+    /// Border color override (hex).
+    #[arg(long, value_name = "HEX", global = true)]
+    pub border_color: Option<String>,
+
+    /// Card template to render with (default, or a template from the templates directory).
+    #[arg(long, value_name = "NAME", global = true)]
+    pub template: Option<String>,
+
+    /// Directory of additional card templates, one `<name>.svg` file per template.
+    #[arg(long, value_name = "DIR", global = true)]
+    pub templates: Option<PathBuf>,
+
+    /// Card layout: a regular card, a compact badge, a 1280x640 social preview image, or a square icon (the default for `.ico` output).
+    #[arg(long, value_enum)]
+    pub layout: Option<Layout>,
+
+    /// Sections to add to the card, comma-separated (language, stars and forks are shown by default).
+    #[arg(long, value_enum, value_name = "SECTION", value_delimiter = ',')]
+    pub show: Vec<Section>,
+
+    /// Sections to remove from the card, comma-separated; takes precedence over `--show`.
+    #[arg(long, value_enum, value_name = "SECTION", value_delimiter = ',')]
+    pub hide: Vec<Section>,
+
+    /// Number of languages listed beneath the language bar (with `--show languages`).
+    #[arg(long, value_name = "N", default_value_t = 0, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(0..=MAX_LEGEND as u64))]
+    pub legend: usize,
+
+    /// Number of contributor avatars shown (with `--show contributors`).
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_CONTRIBUTORS, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(0..=MAX_CONTRIBUTORS as u64))]
+    pub max: usize,
+
+    /// Animate the card, counting up the stats and fading in the description (PNG, GIF and WebP only).
+    #[arg(long)]
+    pub animate: bool,
+
+    /// Encoder quality for JPEG, AVIF and lossy WebP output (defaults to 90, 60 and 80).
+    #[arg(long, value_name = "N", global = true, value_parser = clap::value_parser!(u8).range(0..=100))]
+    pub quality: Option<u8>,
+
+    /// Encode WebP output losslessly; WebP is lossless unless a quality is given.
+    #[arg(long, value_name = "BOOL", global = true, num_args = 0..=1, default_missing_value = "true")]
+    pub lossless: Option<bool>,
+
+    /// PNG and ICO compression level; `best` is smallest but slowest to encode.
+    #[arg(long, value_enum, value_name = "LEVEL", global = true)]
+    pub compression: Option<Compression>,
+
+    /// Write PNGs in the smallest lossless color type (palette, grayscale or RGB); on by default.
+    #[arg(long, value_name = "BOOL", global = true, num_args = 0..=1, default_missing_value = "true")]
+    pub optimize: Option<bool>,
+
+    /// Path to a YAML configuration file (defaults to $GLIM_CONFIG).
+    #[arg(short, long, value_name = "FILE", global = true)]
+    pub config: Option<PathBuf>,
 }
 
-/// Formats the SVG template with repository data.
-///
-/// # Arguments
-/// * `name` - Repository name
-/// * `description` - Repository description
-/// * `language` - Primary programming language
-/// * `stars` - Star count as string
-/// * `forks` - Fork count as string
-///
-/// # Returns
-/// Formatted SVG string
-fn format_svg_template(
-    name: &str,
-    description: &str,
-    language: &str,
-    stars: &str,
-    forks: &str,
-) -> String {
-    let svg_template = include_str!("../card.svg");
-    let wrapped_description = crate::image::wrap_text(description, 65);
-    let language_color =
-        crate::colors::get_color(language).unwrap_or_else(|| "#f1e05a".to_string());
-
-    let formatted_stars = crate::image::format_count(stars);
-    let formatted_forks = crate::image::format_count(forks);
-
-    svg_template
-        .replace("{{name}}", name)
-        .replace("{{description}}", &wrapped_description)
-        .replace("{{language}}", language)
-        .replace("{{language_color}}", &language_color)
-        .replace("{{stars}}", &formatted_stars)
-        .replace("{{forks}}", &formatted_forks)
+/// Cards other than repository cards.
+#[derive(Subcommand)]
+pub enum Command {
+    /// Generate a profile card for a GitHub user.
+    User {
+        /// The user's login.
+        login: String,
+    },
+    /// Generate a profile card for a GitHub organization.
+    Org {
+        /// The organization's login.
+        org: String,
+    },
+    /// Generate a card for a repository's latest release.
+    Release {
+        /// The repository, in the format `owner/repo`.
+        repository: String,
+    },
+    /// Generate a card for an issue or pull request.
+    #[command(visible_alias = "pr")]
+    Issue {
+        /// The repository, in the format `owner/repo`.
+        repository: String,
+        /// The issue or pull request number.
+        number: u64,
+    },
 }
 
-/// Executes the CLI command to generate a repository card.
+/// Executes the CLI command to generate a repository, user, organization, release or issue card.
 ///
 /// # Arguments
 /// * `cli` - Parsed command-line arguments
+/// * `config` - Application configuration
 ///
 /// # Returns
 /// Result indicating success or failure of card generation
-pub async fn run(cli: Cli) -> Result<()> {
-    let repo_path = cli.repository.as_ref().unwrap();
-    let repo = github::GITHUB_CLIENT.get_repository_info(repo_path).await?;
+pub async fn run(cli: Cli, config: Config) -> Result<()> {
+    // Resolve the theme and templates before hitting the API so typos fail fast
+    let overrides = ColorOverrides {
+        title_color: cli.title_color.clone(),
+        text_color: cli.text_color.clone(),
+        icon_color: cli.icon_color.clone(),
+        bg_color: cli.bg_color.clone(),
+        border_color: cli.border_color.clone(),
+    };
+    let themes = ThemeRegistry::from_config(config.card_config())?;
+    let theme = themes.build(cli.theme.as_deref(), &overrides)?;
+    let templates = TemplateEngine::from_config(config.card_config())?;
+
+    let format = cli
+        .output
+        .as_deref()
+        .and_then(|path| path.extension())
+        .and_then(|extension| extension.to_str())
+        .and_then(crate::image::parse_extension)
+        .unwrap_or(ImageFormat::Png);
+    let quality = EncodeOptions {
+        quality: cli.quality,
+        lossless: cli.lossless,
+        compression: cli.compression,
+        optimize: cli.optimize.unwrap_or(true),
+        ..EncodeOptions::default()
+    };
+    quality.check(format)?;
+    if cli.animate && !format.supports_animation() {
+        return Err(ImageError::Animation(format!(
+            "animated cards are available as PNG, GIF or WebP, not {}",
+            format.extension()
+        ))
+        .into());
+    }
+
+    // Start timing for image generation once the API data is available
+    let start_time;
+    let (card_path, formatted_svg, animation, layout) = match &cli.command {
+        Some(Command::User { login }) => {
+            let template = templates
+                .resolve_kind(TemplateKind::User, cli.template.as_deref())?
+                .to_string();
+            let user = github::GITHUB_CLIENT.get_user_info(login).await?;
+            let avatar = avatar::AVATARS.get(&user.avatar_url).await;
+
+            start_time = std::time::Instant::now();
+            let options = CardOptions {
+                theme,
+                template,
+                layout: Layout::Card,
+                ..Default::default()
+            };
+            let svg = UserCard::new(user, options)
+                .with_avatar(avatar)
+                .render_with(&templates)?;
+            (format!("users/{}", login), svg, None, Layout::Card)
+        }
+        Some(Command::Org { org }) => {
+            let template = templates
+                .resolve_kind(TemplateKind::Org, cli.template.as_deref())?
+                .to_string();
+            let organization = github::GITHUB_CLIENT.get_org_info(org).await?;
+            let avatar = avatar::AVATARS.get(&organization.avatar_url).await;
+
+            start_time = std::time::Instant::now();
+            let options = CardOptions {
+                theme,
+                template,
+                layout: Layout::Card,
+                ..Default::default()
+            };
+            let svg = OrgCard::new(organization, options)
+                .with_avatar(avatar)
+                .render_with(&templates)?;
+            (format!("orgs/{}", org), svg, None, Layout::Card)
+        }
+        Some(Command::Release { repository }) => {
+            let template = templates
+                .resolve_kind(TemplateKind::Release, cli.template.as_deref())?
+                .to_string();
+            // The releases endpoint 404s for missing repositories too, so look up the repository first
+            let repo = github::GITHUB_CLIENT
+                .get_repository_info(repository)
+                .await?;
+            let release = github::GITHUB_CLIENT.get_latest_release(repository).await?;
+            if release.is_none() {
+                tracing::warn!("{} has no releases", repository);
+            }
+            let avatar = avatar::AVATARS.get(&repo.owner.avatar_url).await;
+
+            start_time = std::time::Instant::now();
+            let options = CardOptions {
+                theme,
+                template,
+                layout: Layout::Card,
+                ..Default::default()
+            };
+            let svg = ReleaseCard::new(repo, release, options)
+                .with_avatar(avatar)
+                .render_with(&templates)?;
+            (format!("{}-release", repository), svg, None, Layout::Card)
+        }
+        Some(Command::Issue { repository, number }) => {
+            let template = templates
+                .resolve_kind(TemplateKind::Issue, cli.template.as_deref())?
+                .to_string();
+            // Look up the repository first so issues of private repositories are never shown
+            let repo = github::GITHUB_CLIENT
+                .get_repository_info(repository)
+                .await?;
+            let issue = github::GITHUB_CLIENT.get_issue(repository, *number).await?;
+            let avatar = match &issue.user {
+                Some(user) => avatar::AVATARS.get(&user.avatar_url).await,
+                None => None,
+            };
+
+            start_time = std::time::Instant::now();
+            let options = CardOptions {
+                theme,
+                template,
+                layout: Layout::Card,
+                ..Default::default()
+            };
+            let svg = IssueCard::new(repo, issue, options)
+                .with_avatar(avatar)
+                .render_with(&templates)?;
+            (
+                format!("{}-{}", repository, number),
+                svg,
+                None,
+                Layout::Card,
+            )
+        }
+        None => {
+            let repo_path = cli.repository.clone().unwrap_or_default();
+            let layout = cli.layout.unwrap_or(Layout::for_format(format));
+            let template = templates
+                .resolve(cli.template.as_deref().or(layout.template()))?
+                .to_string();
+            let options = CardOptions {
+                theme,
+                template,
+                layout,
+                show: cli.show.clone(),
+                hide: cli.hide.clone(),
+                legend: cli.legend,
+                contributors: cli.max,
+            };
+            let repo = github::GITHUB_CLIENT
+                .get_repository_info(&repo_path)
+                .await?;
+            let languages = if options.needs_languages() {
+                github::GITHUB_CLIENT
+                    .get_repository_languages(&repo_path)
+                    .await?
+            } else {
+                Vec::new()
+            };
+            let star_history = if options.needs_star_history() {
+                github::GITHUB_CLIENT
+                    .get_star_history(&repo_path, repo.stargazers_count)
+                    .await?
+            } else {
+                Vec::new()
+            };
+            let activity = if options.needs_activity() {
+                let activity = github::GITHUB_CLIENT
+                    .get_commit_activity(&repo_path)
+                    .await?;
+                if activity.is_none() {
+                    tracing::warn!(
+                        "GitHub is still computing the commit activity of {}; try again shortly",
+                        repo_path
+                    );
+                }
+                activity.unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+            let contributors = if options.needs_contributors() {
+                let mut contributors = github::GITHUB_CLIENT.get_contributors(&repo_path).await?;
+                contributors.truncate(options.contributors);
+                avatar::AVATARS.fill_contributors(&mut contributors).await;
+                contributors
+            } else {
+                Vec::new()
+            };
+            let avatar = avatar::AVATARS.get(&repo.owner.avatar_url).await;
+
+            start_time = std::time::Instant::now();
+            let card = Card::new(repo, options)
+                .with_avatar(avatar)
+                .with_languages(languages)
+                .with_star_history(star_history)
+                .with_activity(activity)
+                .with_contributors(contributors);
+            let svg = card.render_with(&templates)?;
+            let animation = if cli.animate {
+                Some(Animation {
+                    frames: card.render_frames_with(&templates, ANIMATION_FRAMES)?,
+                    duration: ANIMATION_DURATION,
+                })
+            } else {
+                None
+            };
+            (repo_path, svg, animation, layout)
+        }
+    };
 
     let output_path = match cli.output {
         Some(path) => path,
         None => {
-            let repo_name = repo_path.split('/').next_back().unwrap_or("card");
-            PathBuf::from(format!("{}.png", repo_name))
+            let name = card_path.split('/').next_back().unwrap_or("card");
+            PathBuf::from(format!("{}.{}", name, format.extension()))
         }
     };
 
     let file = File::create(&output_path)?;
     let mut writer = BufWriter::new(file);
 
-    // Start timing for image generation
-    let start_time = std::time::Instant::now();
-
-    // Format the SVG template
-    let formatted_svg = format_svg_template(
-        &repo.name,
-        &repo.description.unwrap_or_default(),
-        &repo.language.unwrap_or_default(),
-        &repo.stargazers_count.to_string(),
-        &repo.forks_count.to_string(),
-    );
-
     // Create encoder and encode
-    let encoder = create_encoder(ImageFormat::Png);
-    let encoding_timing = encoder.encode(&formatted_svg, &mut writer, None)?;
+    let encoder = create_encoder(format);
+    let options = EncodeOptions {
+        quality: quality.quality,
+        lossless: quality.lossless,
+        compression: quality.compression,
+        optimize: quality.optimize,
+        ..layout.encode_options(None)
+    };
+    let encoding_timing = match &animation {
+        Some(animation) => encoder.encode_animation(animation, &mut writer, &options)?,
+        None => encoder.encode(&formatted_svg, &mut writer, &options)?,
+    };
 
     // Calculate timing
     let duration = start_time.elapsed();
@@ -128,7 +416,7 @@ pub async fn run(cli: Cli) -> Result<()> {
     let svg_template_duration = duration - encoding_timing.total;
 
     tracing::debug!(
-        repo_path = repo_path,
+        card = card_path,
         svg_template_duration = ?svg_template_duration,
         rasterization_duration = ?encoding_timing.rasterization,
         encoding_duration = ?encoding_timing.encoding,
@@ -138,7 +426,7 @@ pub async fn run(cli: Cli) -> Result<()> {
 
     if duration_ms > 1000 {
         tracing::warn!(
-            repo_path = repo_path,
+            card = card_path,
             svg_template_duration = ?svg_template_duration,
             rasterization_duration = ?encoding_timing.rasterization,
             encoding_duration = ?encoding_timing.encoding,
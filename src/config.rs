@@ -3,7 +3,15 @@
 //! Centralizes all configuration options and provides a clean interface
 //! for accessing application settings.
 
+use crate::cache::CacheConfig;
+use crate::errors::ConfigError;
+use crate::theme::Theme;
+use saphyr::{LoadableYamlNode, Yaml};
 use std::net::{IpAddr, Ipv4Addr};
+use std::path::{Path, PathBuf};
+
+/// On-disk image cache size used when the configuration doesn't set one, in MiB
+pub const DEFAULT_CACHE_CAPACITY_MIB: u64 = 1024;
 
 /// Application configuration
 #[derive(Debug, Clone)]
@@ -14,6 +22,10 @@ pub struct Config {
     pub github: GitHubConfig,
     /// Rate limiting configuration
     pub rate_limit: RateLimitConfig,
+    /// Card rendering configuration
+    pub card: CardConfig,
+    /// On-disk image cache (disabled when not configured)
+    pub cache: Option<CacheConfig>,
 }
 
 /// Server configuration
@@ -51,6 +63,19 @@ pub struct RateLimitConfig {
     pub refill_interval: u64,
 }
 
+/// Card rendering configuration
+#[derive(Debug, Clone, Default)]
+pub struct CardConfig {
+    /// Theme used when a request does not specify one (optional)
+    pub default_theme: Option<String>,
+    /// Custom themes defined in the configuration file
+    pub themes: Vec<Theme>,
+    /// Template used when a request does not specify one (optional)
+    pub default_template: Option<String>,
+    /// Directory of additional card templates (optional)
+    pub templates_dir: Option<PathBuf>,
+}
+
 /// CLI configuration overrides
 #[derive(Debug, Clone)]
 pub struct CliOverrides {
@@ -58,6 +83,10 @@ pub struct CliOverrides {
     pub token: Option<String>,
     /// Port override
     pub port: Option<u16>,
+    /// Configuration file path override
+    pub config_file: Option<PathBuf>,
+    /// Template directory override
+    pub templates_dir: Option<PathBuf>,
 }
 
 impl Default for Config {
@@ -66,6 +95,8 @@ impl Default for Config {
             server: ServerConfig::default(),
             github: GitHubConfig::default(),
             rate_limit: RateLimitConfig::default(),
+            card: CardConfig::default(),
+            cache: None,
         }
     }
 }
@@ -102,6 +133,36 @@ impl Default for RateLimitConfig {
 }
 
 impl Config {
+    /// Load configuration with CLI overrides, then apply the configuration file.
+    ///
+    /// The file is taken from the CLI override, falling back to the `GLIM_CONFIG`
+    /// environment variable. Without either, this is equivalent to [`Config::load`].
+    ///
+    /// # Errors
+    /// Returns an error if the configuration file cannot be read or is invalid.
+    pub fn try_load(cli_overrides: Option<CliOverrides>) -> Result<Self, ConfigError> {
+        let config_file = cli_overrides
+            .as_ref()
+            .and_then(|overrides| overrides.config_file.clone())
+            .or_else(|| std::env::var_os("GLIM_CONFIG").map(PathBuf::from));
+
+        let templates_dir = cli_overrides
+            .as_ref()
+            .and_then(|overrides| overrides.templates_dir.clone());
+
+        let mut config = Self::load(cli_overrides);
+        if let Some(path) = config_file {
+            config.load_file(&path)?;
+        }
+
+        // The CLI takes precedence over the configuration file
+        if templates_dir.is_some() {
+            config.card.templates_dir = templates_dir;
+        }
+
+        Ok(config)
+    }
+
     /// Load configuration with CLI overrides
     pub fn load(cli_overrides: Option<CliOverrides>) -> Self {
         let mut config = Self::default();
@@ -114,6 +175,9 @@ impl Config {
             if let Some(port) = overrides.port {
                 config.server.default_port = port;
             }
+            if let Some(templates_dir) = overrides.templates_dir {
+                config.card.templates_dir = Some(templates_dir);
+            }
         }
 
         // Load from environment variables (CLI overrides take precedence)
@@ -164,11 +228,196 @@ impl Config {
     pub fn rate_limit_config(&self) -> &RateLimitConfig {
         &self.rate_limit
     }
+
+    /// Get the card rendering configuration
+    pub fn card_config(&self) -> &CardConfig {
+        &self.card
+    }
+
+    /// Apply settings from a YAML configuration file.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or contains invalid settings.
+    pub fn load_file(&mut self, path: &Path) -> Result<(), ConfigError> {
+        let source = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.display().to_string(),
+            source,
+        })?;
+
+        self.apply_yaml(&source)
+    }
+
+    /// Apply settings from YAML configuration source.
+    ///
+    /// Recognized keys:
+    /// - `theme`: name of the default theme
+    /// - `themes`: mapping of theme names to colors, optionally extending a `base` theme
+    /// - `template`: name of the default card template
+    /// - `templates`: directory of additional card templates
+    /// - `cache`: image cache `path` and `capacity` in MiB
+    ///
+    /// # Errors
+    /// Returns an error if the YAML is malformed or contains invalid settings.
+    pub fn apply_yaml(&mut self, source: &str) -> Result<(), ConfigError> {
+        let docs = Yaml::load_from_str(source).map_err(|e| ConfigError::Parse(e.to_string()))?;
+
+        // An empty file is a valid, empty configuration
+        let Some(root) = docs.first() else {
+            return Ok(());
+        };
+        let root = root.as_mapping().ok_or_else(|| ConfigError::Invalid {
+            key: "<root>".to_string(),
+            message: "expected a mapping".to_string(),
+        })?;
+
+        for (key, value) in root {
+            match yaml_key(key)?.as_str() {
+                "theme" => self.card.default_theme = Some(yaml_string("theme", value)?),
+                "themes" => self.card.themes.extend(parse_themes(value)?),
+                "template" => self.card.default_template = Some(yaml_string("template", value)?),
+                "templates" => {
+                    self.card.templates_dir = Some(PathBuf::from(yaml_string("templates", value)?))
+                }
+                "cache" => self.cache = Some(parse_cache(value)?),
+                other => tracing::warn!("Ignoring unknown configuration key: {}", other),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Extracts a mapping key as a string.
+fn yaml_key(key: &Yaml) -> Result<String, ConfigError> {
+    key.as_str()
+        .map(str::to_string)
+        .ok_or_else(|| ConfigError::Invalid {
+            key: format!("{:?}", key),
+            message: "expected a string key".to_string(),
+        })
+}
+
+/// Extracts a scalar string value for the given key.
+fn yaml_string(key: &str, value: &Yaml) -> Result<String, ConfigError> {
+    value
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| ConfigError::Invalid {
+            key: key.to_string(),
+            message: "expected a string (hex colors must be quoted)".to_string(),
+        })
+}
+
+/// Parses the `cache` mapping into the image cache configuration.
+///
+/// `path` is required; `capacity` defaults to [`DEFAULT_CACHE_CAPACITY_MIB`].
+fn parse_cache(value: &Yaml) -> Result<CacheConfig, ConfigError> {
+    let invalid = |key: &str, message: &str| ConfigError::Invalid {
+        key: key.to_string(),
+        message: message.to_string(),
+    };
+    let mapping = value
+        .as_mapping()
+        .ok_or_else(|| invalid("cache", "expected a mapping with a path"))?;
+
+    let mut disk_path = None;
+    let mut capacity = DEFAULT_CACHE_CAPACITY_MIB;
+    for (key, value) in mapping {
+        match yaml_key(key)?.as_str() {
+            "path" => disk_path = Some(yaml_string("cache.path", value)?),
+            "capacity" => {
+                capacity = value
+                    .as_integer()
+                    .and_then(|mib| u64::try_from(mib).ok())
+                    .filter(|&mib| mib > 0)
+                    .ok_or_else(|| invalid("cache.capacity", "expected a positive size in MiB"))?
+            }
+            other => tracing::warn!("Ignoring unknown configuration key: cache.{}", other),
+        }
+    }
+    let disk_path = disk_path.ok_or_else(|| invalid("cache.path", "missing cache directory"))?;
+
+    Ok(CacheConfig {
+        disk_capacity: capacity * 1024 * 1024,
+        disk_path,
+    })
+}
+
+/// Parses the `themes` mapping into custom themes.
+///
+/// Each theme starts from its `base` theme (light by default) and overrides
+/// the colors it lists.
+fn parse_themes(value: &Yaml) -> Result<Vec<Theme>, ConfigError> {
+    let mapping = value.as_mapping().ok_or_else(|| ConfigError::Invalid {
+        key: "themes".to_string(),
+        message: "expected a mapping of theme names".to_string(),
+    })?;
+
+    let builtin = crate::theme::ThemeRegistry::new();
+    let mut themes: Vec<Theme> = Vec::new();
+
+    for (name, fields) in mapping {
+        let name = yaml_key(name)?;
+        let name = name.as_str();
+        let fields = fields.as_mapping().ok_or_else(|| ConfigError::Invalid {
+            key: format!("themes.{}", name),
+            message: "expected a mapping of colors".to_string(),
+        })?;
+
+        let theme_error = |source| ConfigError::Theme {
+            name: name.to_string(),
+            source,
+        };
+
+        // Resolve the base first so field order in the file doesn't matter
+        let mut theme = Theme::light();
+        for (field, value) in fields {
+            if yaml_key(field)? == "base" {
+                let base = yaml_string(&format!("themes.{}.base", name), value)?;
+                // Custom themes may extend themes defined earlier in the same file
+                theme = match themes.iter().find(|t| t.name.eq_ignore_ascii_case(&base)) {
+                    Some(custom) => custom.clone(),
+                    None => builtin.get(&base).map_err(theme_error)?.clone(),
+                };
+            }
+        }
+        theme.name = name.to_string();
+
+        for (field, value) in fields {
+            let field = yaml_key(field)?;
+            if field == "base" {
+                continue;
+            }
+            let value = yaml_string(&format!("themes.{}.{}", name, field), value)?;
+            theme.set_color(&field, &value).map_err(theme_error)?;
+        }
+
+        themes.push(theme);
+    }
+
+    Ok(themes)
 }
 
 impl CliOverrides {
     /// Create CLI overrides from CLI arguments
     pub fn from_cli_args(token: Option<String>, port: Option<u16>) -> Self {
-        Self { token, port }
+        Self {
+            token,
+            port,
+            config_file: None,
+            templates_dir: None,
+        }
+    }
+
+    /// Set the configuration file to load
+    pub fn with_config_file(mut self, config_file: Option<PathBuf>) -> Self {
+        self.config_file = config_file;
+        self
+    }
+
+    /// Set the card template directory to load
+    pub fn with_templates_dir(mut self, templates_dir: Option<PathBuf>) -> Self {
+        self.templates_dir = templates_dir;
+        self
     }
 }
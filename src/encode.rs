@@ -1,10 +1,14 @@
 //! Image encoding support for different formats.
 //!
-//! This module provides encoders for PNG, WebP, JPEG, and SVG formats
-//! with consistent error handling and result types.
+//! This module provides encoders for PNG, WebP, JPEG, GIF, and SVG formats
+//! with consistent error handling and result types. PNG, GIF and WebP can
+//! also encode an [`Animation`] as APNG, animated GIF or animated WebP.
 
 use crate::errors::{GlimError, ImageError, Result};
 use image::{Rgba, RgbaImage};
+use resvg::tiny_skia;
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::io::Write;
 use std::time::Duration;
 use tracing::instrument;
@@ -17,15 +21,268 @@ pub struct EncodingTiming {
     pub total: Duration,
 }
 
+/// Options controlling how an SVG is rasterized and encoded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EncodeOptions {
+    /// Scale factor for the image (1.0 if unset)
+    pub scale: Option<f64>,
+    /// Whether to surround the image with a transparent margin
+    pub padding: bool,
+    /// Encoder quality within the format's [`QualityRange`], or its default if unset
+    pub quality: Option<u8>,
+    /// Whether to encode losslessly, for formats that support both; see [`ImageFormat::lossless`]
+    pub lossless: Option<bool>,
+    /// Deflate effort for PNG and ICO output, or [`Compression::Default`] if unset
+    pub compression: Option<Compression>,
+    /// Whether to write PNGs and ICO entries in the smallest color type that holds their pixels
+    pub optimize: bool,
+}
+
+impl Default for EncodeOptions {
+    fn default() -> Self {
+        Self {
+            scale: None,
+            padding: true,
+            quality: None,
+            lossless: None,
+            compression: None,
+            optimize: true,
+        }
+    }
+}
+
+impl EncodeOptions {
+    /// Creates padded options with the given scale factor.
+    pub fn with_scale(scale: Option<f64>) -> Self {
+        Self {
+            scale,
+            ..Default::default()
+        }
+    }
+
+    /// Checks the quality settings against what a format supports.
+    ///
+    /// # Errors
+    /// Returns [`ImageError::InvalidQuality`] if the format has no quality
+    /// setting or the quality is out of its range, if the format can't be
+    /// encoded as requested, or if a quality is given for lossless WebP.
+    /// Returns [`ImageError::InvalidCompression`] if a compression level is
+    /// given for a format other than PNG or ICO.
+    pub fn check(&self, format: ImageFormat) -> Result<()> {
+        let invalid = |message: String| Err(GlimError::Image(ImageError::InvalidQuality(message)));
+        let name = format.extension().to_uppercase();
+
+        if let (Some(lossless), Some(fixed)) = (self.lossless, format.lossless()) {
+            if lossless != fixed {
+                let kind = if fixed { "lossless" } else { "lossy" };
+                return invalid(format!("{} is always {}", name, kind));
+            }
+        }
+
+        if let Some(quality) = self.quality {
+            let Some(range) = format.quality_range() else {
+                return invalid(format!("{} has no quality setting", name));
+            };
+            if quality < range.min || quality > range.max {
+                return invalid(format!(
+                    "{} quality must be between {} and {}",
+                    name, range.min, range.max
+                ));
+            }
+            if format.lossless().is_none() && self.lossless == Some(true) {
+                return invalid(format!("quality only applies to lossy {}", name));
+            }
+        }
+
+        if self.compression.is_some() && !format.is_png() {
+            return Err(GlimError::Image(ImageError::InvalidCompression(format!(
+                "{} has no compression setting",
+                name
+            ))));
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether a format is encoded losslessly with these options.
+    ///
+    /// Formats that support both are lossless unless a quality is given or
+    /// `lossless` is turned off.
+    pub fn is_lossless(&self, format: ImageFormat) -> bool {
+        format
+            .lossless()
+            .unwrap_or_else(|| self.lossless.unwrap_or(self.quality.is_none()))
+    }
+
+    /// Returns the quality a format is encoded with, if it has a quality setting.
+    pub fn quality_for(&self, format: ImageFormat) -> Option<u8> {
+        format
+            .quality_range()
+            .map(|range| self.quality.unwrap_or(range.default))
+    }
+
+    /// Returns a key describing the quality and compression settings for a
+    /// format, for caching encoded cards; the format's defaults give an empty key.
+    pub fn quality_key(&self, format: ImageFormat) -> String {
+        if format.is_png() {
+            let mut key = String::new();
+            if let Some(compression) = self.compression.filter(|c| *c != Compression::Default) {
+                key.push_str(&format!("~{}", compression.name()));
+            }
+            if !self.optimize {
+                key.push_str("~rgba");
+            }
+            return key;
+        }
+
+        let Some(range) = format.quality_range() else {
+            return String::new();
+        };
+        if self.is_lossless(format) {
+            return String::new();
+        }
+
+        // Formats that are lossless by default always key their lossy mode
+        let quality = self.quality.unwrap_or(range.default);
+        if format.lossless().is_none() || quality != range.default {
+            format!("~q{}", quality)
+        } else {
+            String::new()
+        }
+    }
+}
+
+/// Accepted values and default of a format's quality setting, from low to high quality.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QualityRange {
+    pub min: u8,
+    pub max: u8,
+    pub default: u8,
+}
+
+/// Deflate effort for PNG and ICO output, trading encoding time for file size.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum Compression {
+    /// Quickest encoding, with larger files
+    Fast,
+    /// Balance of encoding time and size
+    #[default]
+    Default,
+    /// Smallest files, with adaptive row filtering
+    Best,
+}
+
+impl Compression {
+    /// All compression levels, from fastest to smallest.
+    pub const ALL: [Compression; 3] = [Compression::Fast, Compression::Default, Compression::Best];
+
+    /// Parses a compression level name, case-insensitively.
+    ///
+    /// # Returns
+    /// Some(Compression) if the name is known, None otherwise
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|compression| compression.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the name used in query parameters and on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Compression::Fast => "fast",
+            Compression::Default => "default",
+            Compression::Best => "best",
+        }
+    }
+}
+
+/// Longest an animation may run before settling on its last frame.
+pub const MAX_ANIMATION_DURATION: Duration = Duration::from_secs(5);
+
+/// Most frames encoded for an animation.
+pub const MAX_ANIMATION_FRAMES: usize = 30;
+
+/// Most pixels rasterized across all frames of an animation, so large cards
+/// get fewer frames rather than costing more to encode.
+const MAX_ANIMATION_PIXELS: u64 = 16_000_000;
+
+/// Frames of an animated card, played once and held on the last frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animation {
+    /// SVG documents of each frame, in order
+    pub frames: Vec<String>,
+    /// Time from the first frame to the last, at most [`MAX_ANIMATION_DURATION`]
+    pub duration: Duration,
+}
+
+/// Rasterizes the frames of an animation.
+///
+/// Frames are dropped evenly, always keeping the first and last, when there
+/// are more than [`MAX_ANIMATION_FRAMES`] or they would exceed the pixel budget.
+///
+/// # Returns
+/// The rasterized frames and the delay between them
+fn rasterize_animation(
+    rasterizer: &crate::image::Rasterizer,
+    animation: &Animation,
+    options: &EncodeOptions,
+) -> Result<(Vec<tiny_skia::Pixmap>, Duration)> {
+    let (Some(first), Some(last)) = (animation.frames.first(), animation.frames.last()) else {
+        return Err(GlimError::Image(ImageError::Animation(
+            "animation has no frames".to_string(),
+        )));
+    };
+
+    let first = rasterizer.render_with_options(first, options)?;
+    let frame_pixels = u64::from(first.width()) * u64::from(first.height());
+    let budget = (MAX_ANIMATION_PIXELS / frame_pixels.max(1)) as usize;
+    let count = animation
+        .frames
+        .len()
+        .min(MAX_ANIMATION_FRAMES)
+        .min(budget)
+        .max(1);
+
+    let mut pixmaps = vec![first];
+    if count > 1 {
+        let last_index = animation.frames.len() - 1;
+        for i in 1..count - 1 {
+            let index = (i * last_index + (count - 1) / 2) / (count - 1);
+            pixmaps.push(rasterizer.render_with_options(&animation.frames[index], options)?);
+        }
+        pixmaps.push(rasterizer.render_with_options(last, options)?);
+    }
+
+    if pixmaps.iter().any(|pixmap| {
+        pixmap.width() != pixmaps[0].width() || pixmap.height() != pixmaps[0].height()
+    }) {
+        return Err(GlimError::Image(ImageError::Animation(
+            "frames differ in size".to_string(),
+        )));
+    }
+
+    let delay = if pixmaps.len() > 1 {
+        animation.duration.min(MAX_ANIMATION_DURATION) / (pixmaps.len() - 1) as u32
+    } else {
+        Duration::ZERO
+    };
+
+    Ok((pixmaps, delay))
+}
+
 /// Helper function to rasterize SVG and convert to RgbaImage.
 /// This eliminates code duplication across encoders.
 fn rasterize_svg_to_rgba(
     rasterizer: &crate::image::Rasterizer,
     svg_data: &str,
-    scale: Option<f64>,
+    options: &EncodeOptions,
 ) -> Result<RgbaImage> {
-    let pixmap = rasterizer.render_with_scale(svg_data, scale)?;
+    let pixmap = rasterizer.render_with_options(svg_data, options)?;
+    Ok(pixmap_to_rgba(&pixmap))
+}
 
+/// Copies a rasterized pixmap into an image buffer.
+fn pixmap_to_rgba(pixmap: &tiny_skia::Pixmap) -> RgbaImage {
     let width = pixmap.width();
     let height = pixmap.height();
     let mut img = RgbaImage::new(width, height);
@@ -44,7 +301,7 @@ fn rasterize_svg_to_rgba(
         }
     }
 
-    Ok(img)
+    img
 }
 
 /// Supported image formats for encoding.
@@ -60,6 +317,54 @@ pub enum ImageFormat {
 }
 
 impl ImageFormat {
+    /// Returns the format's quality setting, or None if it has none.
+    ///
+    /// WebP's quality applies to its lossy mode only.
+    pub fn quality_range(&self) -> Option<QualityRange> {
+        match self {
+            ImageFormat::Jpeg => Some(QualityRange {
+                min: 1,
+                max: 100,
+                default: 90,
+            }),
+            ImageFormat::Avif => Some(QualityRange {
+                min: 1,
+                max: 100,
+                default: 60,
+            }),
+            ImageFormat::WebP => Some(QualityRange {
+                min: 0,
+                max: 100,
+                default: 80,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the format is always lossless or always lossy, or None
+    /// if it can be encoded either way.
+    pub fn lossless(&self) -> Option<bool> {
+        match self {
+            ImageFormat::Png | ImageFormat::Svg | ImageFormat::Ico => Some(true),
+            ImageFormat::Jpeg | ImageFormat::Avif | ImageFormat::Gif => Some(false),
+            ImageFormat::WebP => None,
+        }
+    }
+
+    /// Whether the format is written as PNG data, so it takes the PNG
+    /// compression and optimization settings; ICO files hold PNG entries.
+    pub fn is_png(&self) -> bool {
+        matches!(self, ImageFormat::Png | ImageFormat::Ico)
+    }
+
+    /// Whether the format can encode an [`Animation`].
+    pub fn supports_animation(&self) -> bool {
+        matches!(
+            self,
+            ImageFormat::Png | ImageFormat::Gif | ImageFormat::WebP
+        )
+    }
+
     /// Get the MIME type for this format.
     pub fn mime_type(&self) -> &'static str {
         match self {
@@ -94,7 +399,7 @@ pub trait Encoder {
     /// # Arguments
     /// * `svg_data` - The SVG data to encode
     /// * `writer` - Output writer for the encoded data
-    /// * `scale` - Optional scale factor for the image
+    /// * `options` - Rasterization options such as the scale factor
     ///
     /// # Returns
     /// Result with timing information indicating success or failure
@@ -102,8 +407,232 @@ pub trait Encoder {
         &self,
         svg_data: &str,
         writer: &mut dyn Write,
-        scale: Option<f64>,
+        options: &EncodeOptions,
     ) -> Result<EncodingTiming>;
+
+    /// Encode the frames of an animation to the target format.
+    ///
+    /// # Arguments
+    /// * `animation` - The SVG frames and their duration
+    /// * `writer` - Output writer for the encoded data
+    /// * `options` - Rasterization options such as the scale factor
+    ///
+    /// # Errors
+    /// Returns [`ImageError::Animation`] for formats that can't be animated,
+    /// see [`ImageFormat::supports_animation`].
+    fn encode_animation(
+        &self,
+        _animation: &Animation,
+        _writer: &mut dyn Write,
+        _options: &EncodeOptions,
+    ) -> Result<EncodingTiming> {
+        Err(GlimError::Image(ImageError::Animation(
+            "format does not support animation".to_string(),
+        )))
+    }
+}
+
+/// Most distinct colors written as an indexed PNG.
+const PNG_MAX_PALETTE: usize = 256;
+
+/// Most distinct shades an opaque grayscale image is indexed with; beyond
+/// this, plain 8-bit grayscale is as small and needs no palette.
+const PNG_MAX_GRAY_PALETTE: usize = 16;
+
+/// Pixel format of a PNG's frames.
+#[derive(Debug, Clone, PartialEq)]
+struct PngLayout {
+    color: png::ColorType,
+    depth: png::BitDepth,
+    /// Colors of an indexed image in palette order, translucent colors first
+    palette: Vec<[u8; 4]>,
+}
+
+impl PngLayout {
+    /// Plain 8-bit RGBA, written without inspecting the pixels.
+    fn rgba() -> Self {
+        Self {
+            color: png::ColorType::Rgba,
+            depth: png::BitDepth::Eight,
+            palette: Vec::new(),
+        }
+    }
+
+    /// Chooses the smallest lossless layout for the given RGBA frames.
+    ///
+    /// Images with few enough colors are indexed, at the lowest bit depth that
+    /// fits the palette. Otherwise the alpha channel is dropped when every
+    /// pixel is opaque, and the color channels when every pixel is gray.
+    fn optimized(frames: &[&[u8]]) -> Self {
+        let mut colors: Option<HashMap<[u8; 4], usize>> = Some(HashMap::new());
+        let mut gray = true;
+        let mut opaque = true;
+
+        for pixel in frames.iter().flat_map(|data| data.chunks_exact(4)) {
+            let pixel = [pixel[0], pixel[1], pixel[2], pixel[3]];
+            gray &= pixel[0] == pixel[1] && pixel[1] == pixel[2];
+            opaque &= pixel[3] == 255;
+            if let Some(seen) = &mut colors {
+                let order = seen.len();
+                seen.entry(pixel).or_insert(order);
+                if seen.len() > PNG_MAX_PALETTE {
+                    colors = None;
+                }
+            }
+        }
+
+        if let Some(colors) =
+            colors.filter(|colors| !(gray && opaque && colors.len() > PNG_MAX_GRAY_PALETTE))
+        {
+            // Translucent colors go first so the transparency chunk stays short
+            let mut palette: Vec<([u8; 4], usize)> = colors.into_iter().collect();
+            palette.sort_by_key(|(color, order)| (color[3] == 255, *order));
+            let depth = match palette.len() {
+                0..=2 => png::BitDepth::One,
+                3..=4 => png::BitDepth::Two,
+                5..=16 => png::BitDepth::Four,
+                _ => png::BitDepth::Eight,
+            };
+            return Self {
+                color: png::ColorType::Indexed,
+                depth,
+                palette: palette.into_iter().map(|(color, _)| color).collect(),
+            };
+        }
+
+        let color = match (gray, opaque) {
+            (true, true) => png::ColorType::Grayscale,
+            (true, false) => png::ColorType::GrayscaleAlpha,
+            (false, true) => png::ColorType::Rgb,
+            (false, false) => png::ColorType::Rgba,
+        };
+        Self {
+            color,
+            depth: png::BitDepth::Eight,
+            palette: Vec::new(),
+        }
+    }
+
+    /// Describes the layout for logging.
+    fn describe(&self) -> String {
+        match self.color {
+            png::ColorType::Indexed => format!(
+                "palette ({} colors, {}-bit)",
+                self.palette.len(),
+                self.depth as u8
+            ),
+            png::ColorType::Grayscale => "grayscale".to_string(),
+            png::ColorType::GrayscaleAlpha => "grayscale+alpha".to_string(),
+            png::ColorType::Rgb => "RGB".to_string(),
+            png::ColorType::Rgba => "RGBA".to_string(),
+        }
+    }
+
+    /// Sets the color type, palette and compression of a PNG encoder.
+    fn configure<W: Write>(&self, encoder: &mut png::Encoder<'_, W>, compression: Compression) {
+        encoder.set_color(self.color);
+        encoder.set_depth(self.depth);
+        encoder.set_compression(match compression {
+            Compression::Fast => png::Compression::Fast,
+            Compression::Default => png::Compression::Default,
+            Compression::Best => png::Compression::Best,
+        });
+
+        if self.color == png::ColorType::Indexed {
+            encoder.set_palette(
+                self.palette
+                    .iter()
+                    .flat_map(|color| [color[0], color[1], color[2]])
+                    .collect::<Vec<u8>>(),
+            );
+            let trns: Vec<u8> = self
+                .palette
+                .iter()
+                .map(|color| color[3])
+                .take_while(|alpha| *alpha < 255)
+                .collect();
+            if !trns.is_empty() {
+                encoder.set_trns(trns);
+            }
+            // Filtering rarely helps indexed rows
+            encoder.set_filter(png::FilterType::NoFilter);
+        } else if compression == Compression::Best {
+            encoder.set_adaptive_filter(png::AdaptiveFilterType::Adaptive);
+        }
+    }
+
+    /// Converts RGBA pixel rows into this layout's image data.
+    fn pack(&self, data: &[u8], width: u32) -> Vec<u8> {
+        let pixels = data.chunks_exact(4);
+        match self.color {
+            png::ColorType::Grayscale => pixels.map(|pixel| pixel[0]).collect(),
+            png::ColorType::GrayscaleAlpha => {
+                pixels.flat_map(|pixel| [pixel[0], pixel[3]]).collect()
+            }
+            png::ColorType::Rgb => pixels
+                .flat_map(|pixel| [pixel[0], pixel[1], pixel[2]])
+                .collect(),
+            png::ColorType::Indexed => {
+                let indices: HashMap<[u8; 4], u8> = self
+                    .palette
+                    .iter()
+                    .enumerate()
+                    .map(|(index, color)| (*color, index as u8))
+                    .collect();
+                let bits = self.depth as usize;
+                let mut packed = Vec::with_capacity(data.len() / 4 * bits / 8 + data.len() / 4);
+
+                // Each row starts on a byte boundary
+                for row in data.chunks((width as usize * 4).max(1)) {
+                    let mut byte = 0u8;
+                    let mut filled = 0;
+                    for pixel in row.chunks_exact(4) {
+                        let index = indices[&[pixel[0], pixel[1], pixel[2], pixel[3]]];
+                        byte = byte.checked_shl(bits as u32).unwrap_or(0) | index;
+                        filled += bits;
+                        if filled == 8 {
+                            packed.push(byte);
+                            byte = 0;
+                            filled = 0;
+                        }
+                    }
+                    if filled > 0 {
+                        packed.push(byte << (8 - filled));
+                    }
+                }
+                packed
+            }
+            _ => data.to_vec(),
+        }
+    }
+}
+
+/// Writes a pixmap as a PNG, in the smallest layout that holds its pixels
+/// unless optimization is turned off.
+///
+/// # Returns
+/// The layout the pixmap was written in
+fn write_png(
+    pixmap: &tiny_skia::Pixmap,
+    writer: &mut dyn Write,
+    options: &EncodeOptions,
+) -> Result<PngLayout> {
+    let png_error = |e: png::EncodingError| GlimError::Image(ImageError::PngWrite(e.to_string()));
+    let layout = if options.optimize {
+        PngLayout::optimized(&[pixmap.data()])
+    } else {
+        PngLayout::rgba()
+    };
+
+    let mut png_encoder = png::Encoder::new(writer, pixmap.width(), pixmap.height());
+    layout.configure(&mut png_encoder, options.compression.unwrap_or_default());
+    let mut png_writer = png_encoder.write_header().map_err(png_error)?;
+    png_writer
+        .write_image_data(&layout.pack(pixmap.data(), pixmap.width()))
+        .map_err(png_error)?;
+    png_writer.finish().map_err(png_error)?;
+
+    Ok(layout)
 }
 
 /// PNG encoder using the resvg library.
@@ -126,38 +655,27 @@ impl Encoder for PngEncoder {
         &self,
         svg_data: &str,
         writer: &mut dyn Write,
-        scale: Option<f64>,
+        options: &EncodeOptions,
     ) -> Result<EncodingTiming> {
         // Rasterization timing
         let rasterize_start = std::time::Instant::now();
-        let pixmap = self.rasterizer.render_with_scale(svg_data, scale)?;
+        let pixmap = self.rasterizer.render_with_options(svg_data, options)?;
         let rasterize_duration = rasterize_start.elapsed();
 
         // PNG encoding timing
         let encode_start = std::time::Instant::now();
-        let mut png_encoder = png::Encoder::new(writer, pixmap.width(), pixmap.height());
-        png_encoder.set_color(png::ColorType::Rgba);
-        png_encoder.set_depth(png::BitDepth::Eight);
-
-        let mut png_writer = png_encoder
-            .write_header()
-            .map_err(|e| GlimError::Image(ImageError::PngWrite(e.to_string())))?;
-
-        png_writer
-            .write_image_data(pixmap.data())
-            .map_err(|e| GlimError::Image(ImageError::PngWrite(e.to_string())))?;
-
-        png_writer
-            .finish()
-            .map_err(|e| GlimError::Image(ImageError::PngWrite(e.to_string())))?;
+        let layout = write_png(&pixmap, writer, options)?;
+        let compression = options.compression.unwrap_or_default();
         let encode_duration = encode_start.elapsed();
 
         let total_duration = rasterize_duration + encode_duration;
 
         tracing::debug!(
-            scale = ?scale,
+            scale = ?options.scale,
             width = pixmap.width(),
             height = pixmap.height(),
+            mode = %layout.describe(),
+            compression = compression.name(),
             rasterization_duration = ?rasterize_duration,
             encoding_duration = ?encode_duration,
             total_duration = ?total_duration,
@@ -166,7 +684,7 @@ impl Encoder for PngEncoder {
 
         if total_duration.as_millis() > 1000 {
             tracing::warn!(
-                scale = ?scale,
+                scale = ?options.scale,
                 width = pixmap.width(),
                 height = pixmap.height(),
                 rasterization_duration = ?rasterize_duration,
@@ -182,6 +700,72 @@ impl Encoder for PngEncoder {
             total: total_duration,
         })
     }
+
+    #[instrument(skip(self, writer, animation))]
+    fn encode_animation(
+        &self,
+        animation: &Animation,
+        writer: &mut dyn Write,
+        options: &EncodeOptions,
+    ) -> Result<EncodingTiming> {
+        let rasterize_start = std::time::Instant::now();
+        let (pixmaps, delay) = rasterize_animation(&self.rasterizer, animation, options)?;
+        let rasterize_duration = rasterize_start.elapsed();
+
+        let encode_start = std::time::Instant::now();
+        let png_error =
+            |e: png::EncodingError| GlimError::Image(ImageError::PngWrite(e.to_string()));
+        // Frames share one layout, so it must hold the pixels of all of them
+        let layout = if options.optimize {
+            PngLayout::optimized(
+                &pixmaps
+                    .iter()
+                    .map(|pixmap| pixmap.data())
+                    .collect::<Vec<_>>(),
+            )
+        } else {
+            PngLayout::rgba()
+        };
+        let compression = options.compression.unwrap_or_default();
+        let mut png_encoder = png::Encoder::new(writer, pixmaps[0].width(), pixmaps[0].height());
+        layout.configure(&mut png_encoder, compression);
+        // Played once, stopping on the last frame
+        png_encoder
+            .set_animated(pixmaps.len() as u32, 1)
+            .map_err(png_error)?;
+        png_encoder
+            .set_frame_delay(delay.as_millis() as u16, 1000)
+            .map_err(png_error)?;
+        png_encoder
+            .set_blend_op(png::BlendOp::Source)
+            .map_err(png_error)?;
+
+        let mut png_writer = png_encoder.write_header().map_err(png_error)?;
+        for pixmap in &pixmaps {
+            png_writer
+                .write_image_data(&layout.pack(pixmap.data(), pixmap.width()))
+                .map_err(png_error)?;
+        }
+        png_writer.finish().map_err(png_error)?;
+        let encode_duration = encode_start.elapsed();
+
+        tracing::debug!(
+            scale = ?options.scale,
+            frames = pixmaps.len(),
+            delay = ?delay,
+            mode = %layout.describe(),
+            compression = compression.name(),
+            rasterization_duration = ?rasterize_duration,
+            encoding_duration = ?encode_duration,
+            "APNG encoding completed"
+        );
+
+        Ok(EncodingTiming {
+            rasterization: rasterize_duration,
+            encoding: encode_duration,
+            total: rasterize_duration + encode_duration,
+        })
+    }
 }
 
 /// WebP encoder using the image crate.
@@ -200,13 +784,19 @@ impl Encoder for WebPEncoder {
         &self,
         svg_data: &str,
         writer: &mut dyn Write,
-        scale: Option<f64>,
+        options: &EncodeOptions,
     ) -> Result<EncodingTiming> {
         let rasterize_start = std::time::Instant::now();
-        let img = rasterize_svg_to_rgba(&crate::image::Rasterizer::new(), svg_data, scale)?;
+        let mut img = rasterize_svg_to_rgba(&crate::image::Rasterizer::new(), svg_data, options)?;
         let rasterize_duration = rasterize_start.elapsed();
 
         let encode_start = std::time::Instant::now();
+        if !options.is_lossless(ImageFormat::WebP) {
+            near_lossless(
+                &mut img,
+                options.quality_for(ImageFormat::WebP).unwrap_or(100),
+            );
+        }
         // Encode as WebP
         img.write_with_encoder(image::codecs::webp::WebPEncoder::new_lossless(writer))
             .map_err(|e| GlimError::Image(ImageError::WebPWrite(e.to_string())))?;
@@ -218,6 +808,163 @@ impl Encoder for WebPEncoder {
             total: rasterize_duration + encode_duration,
         })
     }
+
+    #[instrument(skip(writer, animation))]
+    fn encode_animation(
+        &self,
+        animation: &Animation,
+        writer: &mut dyn Write,
+        options: &EncodeOptions,
+    ) -> Result<EncodingTiming> {
+        let rasterize_start = std::time::Instant::now();
+        let (pixmaps, delay) =
+            rasterize_animation(&crate::image::Rasterizer::new(), animation, options)?;
+        let rasterize_duration = rasterize_start.elapsed();
+
+        let encode_start = std::time::Instant::now();
+        let webp_error = |e: String| GlimError::Image(ImageError::WebPWrite(e));
+        let (width, height) = (pixmaps[0].width(), pixmaps[0].height());
+
+        // VP8X header: animated with alpha, then the canvas size
+        let mut header = vec![0x02 | 0x10, 0, 0, 0];
+        header.extend_from_slice(&webp_u24(width - 1));
+        header.extend_from_slice(&webp_u24(height - 1));
+        let mut body = webp_chunk(b"VP8X", &header);
+        // ANIM: transparent background, played once
+        body.extend(webp_chunk(b"ANIM", &[0, 0, 0, 0, 1, 0]));
+
+        for pixmap in &pixmaps {
+            let mut img = pixmap_to_rgba(pixmap);
+            if !options.is_lossless(ImageFormat::WebP) {
+                near_lossless(
+                    &mut img,
+                    options.quality_for(ImageFormat::WebP).unwrap_or(100),
+                );
+            }
+            let mut still = Vec::new();
+            img.write_with_encoder(image::codecs::webp::WebPEncoder::new_lossless(&mut still))
+                .map_err(|e| webp_error(e.to_string()))?;
+
+            // Frame origin, size and duration, replacing the canvas rather than blending
+            let mut frame = Vec::new();
+            frame.extend_from_slice(&webp_u24(0));
+            frame.extend_from_slice(&webp_u24(0));
+            frame.extend_from_slice(&webp_u24(width - 1));
+            frame.extend_from_slice(&webp_u24(height - 1));
+            frame.extend_from_slice(&webp_u24(delay.as_millis() as u32));
+            frame.push(0x02);
+            frame.extend_from_slice(
+                webp_image_chunks(&still).ok_or_else(|| {
+                    webp_error("encoder produced an invalid WebP file".to_string())
+                })?,
+            );
+            body.extend(webp_chunk(b"ANMF", &frame));
+        }
+
+        let mut riff = Vec::with_capacity(body.len() + 12);
+        riff.extend_from_slice(b"RIFF");
+        riff.extend_from_slice(&(body.len() as u32 + 4).to_le_bytes());
+        riff.extend_from_slice(b"WEBP");
+        riff.extend(body);
+        writer
+            .write_all(&riff)
+            .map_err(|e| webp_error(e.to_string()))?;
+        let encode_duration = encode_start.elapsed();
+
+        tracing::debug!(
+            scale = ?options.scale,
+            frames = pixmaps.len(),
+            delay = ?delay,
+            rasterization_duration = ?rasterize_duration,
+            encoding_duration = ?encode_duration,
+            "Animated WebP encoding completed"
+        );
+
+        Ok(EncodingTiming {
+            rasterization: rasterize_duration,
+            encoding: encode_duration,
+            total: rasterize_duration + encode_duration,
+        })
+    }
+}
+
+/// Prepares an image for lossy WebP, in the manner of libwebp's near-lossless mode.
+///
+/// The lossless bitstream is kept, but pixels that differ sharply from a
+/// neighbor have their low bits rounded away so they compress better. Smooth
+/// areas, where rounding would show as banding, and the image border are left
+/// untouched.
+///
+/// # Arguments
+/// * `img` - The image to prepare in place
+/// * `quality` - From 0 (most rounding) to 100 (unchanged)
+fn near_lossless(img: &mut RgbaImage, quality: u8) {
+    let bits = 5 - u32::from(quality.min(100)) / 20;
+    if bits == 0 || img.width() < 3 || img.height() < 3 {
+        return;
+    }
+
+    let limit = 1i16 << bits;
+    let near = |a: &Rgba<u8>, b: &Rgba<u8>| {
+        (0..4).all(|c| (i16::from(a[c]) - i16::from(b[c])).abs() < limit)
+    };
+    let source = img.clone();
+    for y in 1..img.height() - 1 {
+        for x in 1..img.width() - 1 {
+            let pixel = source.get_pixel(x, y);
+            let smooth = near(pixel, source.get_pixel(x - 1, y))
+                && near(pixel, source.get_pixel(x + 1, y))
+                && near(pixel, source.get_pixel(x, y - 1))
+                && near(pixel, source.get_pixel(x, y + 1));
+            if !smooth {
+                img.put_pixel(x, y, Rgba(pixel.0.map(|value| discretize(value, bits))));
+            }
+        }
+    }
+}
+
+/// Rounds a channel to the nearest multiple of `1 << bits`, ties to even.
+fn discretize(value: u8, bits: u32) -> u8 {
+    let mask = (1u16 << bits) - 1;
+    let biased = u16::from(value) + (mask >> 1) + ((u16::from(value) >> bits) & 1);
+    if biased > 0xff {
+        0xff
+    } else {
+        biased as u8 & !(mask as u8)
+    }
+}
+
+/// Encodes a value as the 24-bit little-endian integer used in WebP headers.
+fn webp_u24(value: u32) -> [u8; 3] {
+    let [a, b, c, _] = value.to_le_bytes();
+    [a, b, c]
+}
+
+/// Builds a RIFF chunk, padded to an even length.
+fn webp_chunk(fourcc: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut chunk = Vec::with_capacity(data.len() + 9);
+    chunk.extend_from_slice(fourcc);
+    chunk.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    chunk.extend_from_slice(data);
+    if data.len() % 2 == 1 {
+        chunk.push(0);
+    }
+    chunk
+}
+
+/// Returns the image chunks of a still WebP file, skipping its header and any
+/// extended header, to be embedded in an animation frame.
+fn webp_image_chunks(file: &[u8]) -> Option<&[u8]> {
+    if file.len() < 12 || &file[..4] != b"RIFF" || &file[8..12] != b"WEBP" {
+        return None;
+    }
+    let chunks = &file[12..];
+    if chunks.starts_with(b"VP8X") {
+        let size = u32::from_le_bytes(chunks.get(4..8)?.try_into().ok()?) as usize;
+        chunks.get(8 + size + size % 2..)
+    } else {
+        Some(chunks)
+    }
 }
 
 /// JPEG encoder using the image crate.
@@ -236,10 +983,10 @@ impl Encoder for JpegEncoder {
         &self,
         svg_data: &str,
         writer: &mut dyn Write,
-        scale: Option<f64>,
+        options: &EncodeOptions,
     ) -> Result<EncodingTiming> {
         let rasterize_start = std::time::Instant::now();
-        let img = rasterize_svg_to_rgba(&crate::image::Rasterizer::new(), svg_data, scale)?;
+        let img = rasterize_svg_to_rgba(&crate::image::Rasterizer::new(), svg_data, options)?;
         let rasterize_duration = rasterize_start.elapsed();
 
         let encode_start = std::time::Instant::now();
@@ -247,8 +994,11 @@ impl Encoder for JpegEncoder {
         let rgb_img = image::DynamicImage::ImageRgba8(img).into_rgb8();
 
         // Encode as JPEG
+        let quality = options.quality_for(ImageFormat::Jpeg).unwrap_or_default();
         rgb_img
-            .write_with_encoder(image::codecs::jpeg::JpegEncoder::new(writer))
+            .write_with_encoder(image::codecs::jpeg::JpegEncoder::new_with_quality(
+                writer, quality,
+            ))
             .map_err(|e| GlimError::Image(ImageError::JpegWrite(e.to_string())))?;
         let encode_duration = encode_start.elapsed();
 
@@ -276,7 +1026,7 @@ impl Encoder for SvgEncoder {
         &self,
         svg_data: &str,
         writer: &mut dyn Write,
-        _scale: Option<f64>,
+        _options: &EncodeOptions,
     ) -> Result<EncodingTiming> {
         let encode_start = std::time::Instant::now();
         writer
@@ -308,16 +1058,17 @@ impl Encoder for AvifEncoder {
         &self,
         svg_data: &str,
         writer: &mut dyn Write,
-        scale: Option<f64>,
+        options: &EncodeOptions,
     ) -> Result<EncodingTiming> {
         let rasterize_start = std::time::Instant::now();
-        let img = rasterize_svg_to_rgba(&crate::image::Rasterizer::new(), svg_data, scale)?;
+        let img = rasterize_svg_to_rgba(&crate::image::Rasterizer::new(), svg_data, options)?;
         let rasterize_duration = rasterize_start.elapsed();
 
         let encode_start = std::time::Instant::now();
-        // Encode as AVIF with maximum speed settings (speed 10, quality 60)
+        // Encode as AVIF at maximum speed
+        let quality = options.quality_for(ImageFormat::Avif).unwrap_or_default();
         img.write_with_encoder(image::codecs::avif::AvifEncoder::new_with_speed_quality(
-            writer, 10, 60,
+            writer, 10, quality,
         ))
         .map_err(|e| GlimError::Image(ImageError::AvifWrite(e.to_string())))?;
         let encode_duration = encode_start.elapsed();
@@ -330,78 +1081,370 @@ impl Encoder for AvifEncoder {
     }
 }
 
-/// GIF encoder using the image crate.
-/// Note: GIF encoding is not currently supported in the image crate.
+/// Minimum alpha for a pixel to be drawn in a GIF, which only supports fully
+/// transparent pixels.
+const GIF_ALPHA_THRESHOLD: u8 = 128;
+
+/// Maximum number of colors for opaque pixels, leaving one palette entry for
+/// transparency.
+const GIF_MAX_COLORS: usize = 255;
+
+/// NeuQuant sampling factor, from 1 (slowest, best palette) to 30 (fastest).
+const GIF_SAMPLE_FACTOR: i32 = 10;
+
+/// An image reduced to a palette of at most 256 colors.
+struct IndexedImage {
+    /// RGB palette entries
+    palette: Vec<[u8; 3]>,
+    /// Palette index of each pixel, row by row
+    indices: Vec<u8>,
+    /// Palette index of transparent pixels, if there are any
+    transparent: Option<u8>,
+    /// Whether the colors were approximated with dithering
+    dithered: bool,
+}
+
+/// Reduces a pixmap to an indexed image.
+///
+/// Pixels are demultiplied first, so antialiased edges keep their own color
+/// rather than being darkened towards black, then drawn if they are at least
+/// half opaque. Cards are mostly flat colors, so images with few enough colors
+/// keep them exactly; otherwise the palette is chosen with NeuQuant, each entry
+/// snapped to the most common color it stands for, and the remaining error is
+/// spread with Floyd-Steinberg dithering.
+///
+/// # Arguments
+/// * `pixmap` - The rasterized image
+///
+/// # Returns
+/// The palette and pixel indices
+fn quantize(pixmap: &tiny_skia::Pixmap) -> IndexedImage {
+    let pixels: Vec<Option<[u8; 3]>> = pixmap
+        .pixels()
+        .iter()
+        .map(|pixel| {
+            let color = pixel.demultiply();
+            (color.alpha() >= GIF_ALPHA_THRESHOLD)
+                .then(|| [color.red(), color.green(), color.blue()])
+        })
+        .collect();
+
+    let mut histogram: HashMap<[u8; 3], u32> = HashMap::new();
+    for color in pixels.iter().flatten() {
+        *histogram.entry(*color).or_default() += 1;
+    }
+
+    let dithered = histogram.len() > GIF_MAX_COLORS;
+    let mut palette = if dithered {
+        neuquant_palette(&pixels, &histogram)
+    } else {
+        let mut colors: Vec<([u8; 3], u32)> = histogram.into_iter().collect();
+        colors.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        colors.into_iter().map(|(color, _)| color).collect()
+    };
+
+    let transparent = pixels.iter().any(Option::is_none).then(|| {
+        palette.push([0, 0, 0]);
+        (palette.len() - 1) as u8
+    });
+
+    let width = pixmap.width() as usize;
+    let mut nearest: HashMap<[u8; 3], u8> = HashMap::new();
+    let mut lookup = |color: [u8; 3], palette: &[[u8; 3]]| {
+        *nearest
+            .entry(color)
+            .or_insert_with(|| nearest_color(color, palette))
+    };
+
+    let opaque_colors = &palette[..palette.len() - transparent.map_or(0, |_| 1)];
+    let mut indices = Vec::with_capacity(pixels.len());
+    // Error carried to the current and next row, with a column of slack on either side
+    let mut errors = vec![[0i32; 3]; width + 2];
+    let mut next_errors = vec![[0i32; 3]; width + 2];
+
+    for row in pixels.chunks(width.max(1)) {
+        for (x, pixel) in row.iter().enumerate() {
+            let Some(color) = pixel else {
+                indices.push(transparent.unwrap_or_default());
+                continue;
+            };
+            if !dithered {
+                indices.push(lookup(*color, opaque_colors));
+                continue;
+            }
+
+            let error = errors[x + 1];
+            let target: [u8; 3] =
+                std::array::from_fn(|c| (color[c] as i32 + error[c] / 16).clamp(0, 255) as u8);
+            let index = lookup(target, opaque_colors);
+            indices.push(index);
+
+            let chosen = opaque_colors[index as usize];
+            for c in 0..3 {
+                let diff = target[c] as i32 - chosen[c] as i32;
+                errors[x + 2][c] += diff * 7;
+                next_errors[x][c] += diff * 3;
+                next_errors[x + 1][c] += diff * 5;
+                next_errors[x + 2][c] += diff;
+            }
+        }
+        std::mem::swap(&mut errors, &mut next_errors);
+        next_errors.fill([0; 3]);
+    }
+
+    IndexedImage {
+        palette,
+        indices,
+        transparent,
+        dithered,
+    }
+}
+
+/// Chooses a palette for an image with too many colors to keep them all.
+///
+/// Each NeuQuant entry is replaced by the most common color mapped to it, so
+/// large flat areas keep their exact color and aren't dithered.
+fn neuquant_palette(pixels: &[Option<[u8; 3]>], histogram: &HashMap<[u8; 3], u32>) -> Vec<[u8; 3]> {
+    let samples: Vec<u8> = pixels
+        .iter()
+        .flatten()
+        .flat_map(|[r, g, b]| [*r, *g, *b, 255])
+        .collect();
+    let quantizer = color_quant::NeuQuant::new(GIF_SAMPLE_FACTOR, GIF_MAX_COLORS, &samples);
+
+    let mut palette: Vec<[u8; 3]> = quantizer
+        .color_map_rgb()
+        .chunks_exact(3)
+        .map(|entry| [entry[0], entry[1], entry[2]])
+        .collect();
+
+    let mut best: Vec<Option<([u8; 3], u32)>> = vec![None; palette.len()];
+    for (&color, &count) in histogram {
+        let index = quantizer.index_of(&[color[0], color[1], color[2], 255]);
+        if best[index].is_none_or(|(_, most)| count > most) {
+            best[index] = Some((color, count));
+        }
+    }
+    for (entry, best) in palette.iter_mut().zip(best) {
+        if let Some((color, _)) = best {
+            *entry = color;
+        }
+    }
+
+    palette
+}
+
+/// Finds the palette entry closest to a color.
+fn nearest_color(color: [u8; 3], palette: &[[u8; 3]]) -> u8 {
+    palette
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, entry)| {
+            (0..3)
+                .map(|c| (color[c] as i32 - entry[c] as i32).pow(2))
+                .sum::<i32>()
+        })
+        .map_or(0, |(index, _)| index as u8)
+}
+
+/// GIF encoder using palette quantization.
 #[derive(Debug, Default)]
-pub struct GifEncoder;
+pub struct GifEncoder {
+    rasterizer: crate::image::Rasterizer,
+}
 
 impl GifEncoder {
     pub fn new() -> Self {
-        Self
+        Self {
+            rasterizer: crate::image::Rasterizer::new(),
+        }
     }
 }
 
 impl Encoder for GifEncoder {
-    #[instrument(skip(_svg_data, _writer))]
+    #[instrument(skip(self, writer, svg_data))]
     fn encode(
         &self,
-        _svg_data: &str,
-        _writer: &mut dyn Write,
-        _scale: Option<f64>,
+        svg_data: &str,
+        writer: &mut dyn Write,
+        options: &EncodeOptions,
     ) -> Result<EncodingTiming> {
-        // GIF encoding is not currently supported
-        Err(GlimError::Image(ImageError::GifWrite(
-            "GIF encoding is not implemented".to_string(),
-        )))
+        let rasterize_start = std::time::Instant::now();
+        let pixmap = self.rasterizer.render_with_options(svg_data, options)?;
+        let rasterize_duration = rasterize_start.elapsed();
+
+        let encode_start = std::time::Instant::now();
+        let (width, height) = gif_size(&pixmap)?;
+
+        let image = quantize(&pixmap);
+        let palette: Vec<u8> = image.palette.iter().flatten().copied().collect();
+
+        let mut gif_encoder = gif::Encoder::new(writer, width, height, &palette)
+            .map_err(|e| GlimError::Image(ImageError::GifWrite(e.to_string())))?;
+        gif_encoder
+            .write_frame(&gif::Frame {
+                width,
+                height,
+                transparent: image.transparent,
+                buffer: Cow::Borrowed(&image.indices),
+                ..Default::default()
+            })
+            .map_err(|e| GlimError::Image(ImageError::GifWrite(e.to_string())))?;
+        drop(gif_encoder);
+        let encode_duration = encode_start.elapsed();
+
+        tracing::debug!(
+            scale = ?options.scale,
+            width,
+            height,
+            colors = image.palette.len(),
+            dithered = image.dithered,
+            encoding_duration = ?encode_duration,
+            "GIF encoding completed"
+        );
+
+        Ok(EncodingTiming {
+            rasterization: rasterize_duration,
+            encoding: encode_duration,
+            total: rasterize_duration + encode_duration,
+        })
+    }
+
+    #[instrument(skip(self, writer, animation))]
+    fn encode_animation(
+        &self,
+        animation: &Animation,
+        writer: &mut dyn Write,
+        options: &EncodeOptions,
+    ) -> Result<EncodingTiming> {
+        let rasterize_start = std::time::Instant::now();
+        let (pixmaps, delay) = rasterize_animation(&self.rasterizer, animation, options)?;
+        let rasterize_duration = rasterize_start.elapsed();
+
+        let encode_start = std::time::Instant::now();
+        let gif_error =
+            |e: gif::EncodingError| GlimError::Image(ImageError::GifWrite(e.to_string()));
+        let (width, height) = gif_size(&pixmaps[0])?;
+        // GIF delays are in hundredths of a second
+        let delay_cs = (delay.as_millis() as u64).div_ceil(10) as u16;
+
+        // Without a repeat extension the animation plays once, stopping on the last frame
+        let mut gif_encoder = gif::Encoder::new(writer, width, height, &[]).map_err(gif_error)?;
+        for pixmap in &pixmaps {
+            // Each frame gets its own palette, so frames with few colors stay exact
+            let image = quantize(pixmap);
+            gif_encoder
+                .write_frame(&gif::Frame {
+                    width,
+                    height,
+                    delay: delay_cs,
+                    dispose: gif::DisposalMethod::Background,
+                    transparent: image.transparent,
+                    palette: Some(image.palette.iter().flatten().copied().collect()),
+                    buffer: Cow::Borrowed(&image.indices),
+                    ..Default::default()
+                })
+                .map_err(gif_error)?;
+        }
+        drop(gif_encoder);
+        let encode_duration = encode_start.elapsed();
+
+        tracing::debug!(
+            scale = ?options.scale,
+            frames = pixmaps.len(),
+            delay = ?delay,
+            rasterization_duration = ?rasterize_duration,
+            encoding_duration = ?encode_duration,
+            "Animated GIF encoding completed"
+        );
+
+        Ok(EncodingTiming {
+            rasterization: rasterize_duration,
+            encoding: encode_duration,
+            total: rasterize_duration + encode_duration,
+        })
     }
 }
 
+/// Checks that a pixmap fits in a GIF, which stores sizes as 16-bit integers.
+fn gif_size(pixmap: &tiny_skia::Pixmap) -> Result<(u16, u16)> {
+    let too_large = || {
+        GlimError::Image(ImageError::GifWrite(format!(
+            "{}x{} is too large",
+            pixmap.width(),
+            pixmap.height()
+        )))
+    };
+    Ok((
+        u16::try_from(pixmap.width()).map_err(|_| too_large())?,
+        u16::try_from(pixmap.height()).map_err(|_| too_large())?,
+    ))
+}
+
+/// Sizes of the entries in an ICO file, from favicons up to the largest size ICO holds.
+pub const ICO_SIZES: [u32; 6] = [16, 32, 48, 64, 128, 256];
+
 /// ICO encoder using the image crate.
+///
+/// Writes one PNG entry for each of [`ICO_SIZES`], each rendered natively
+/// from the SVG and fitted within a square. Cards are best encoded from the
+/// square [`Layout::Icon`](crate::card::Layout::Icon).
 #[derive(Debug, Default)]
-pub struct IcoEncoder;
+pub struct IcoEncoder {
+    rasterizer: crate::image::Rasterizer,
+}
 
 impl IcoEncoder {
     pub fn new() -> Self {
-        Self
+        Self {
+            rasterizer: crate::image::Rasterizer::new(),
+        }
     }
 }
 
 impl Encoder for IcoEncoder {
-    #[instrument(skip(writer, svg_data))]
+    #[instrument(skip(self, writer, svg_data))]
     fn encode(
         &self,
         svg_data: &str,
         writer: &mut dyn Write,
-        scale: Option<f64>,
+        options: &EncodeOptions,
     ) -> Result<EncodingTiming> {
         let rasterize_start = std::time::Instant::now();
-        let img = rasterize_svg_to_rgba(&crate::image::Rasterizer::new(), svg_data, scale)?;
+        let pixmaps = self.rasterizer.render_squares(svg_data, &ICO_SIZES)?;
         let rasterize_duration = rasterize_start.elapsed();
 
         let encode_start = std::time::Instant::now();
-        // Resize image to fit ICO requirements (max 256x256)
-        let width = img.width();
-        let height = img.height();
-        let max_size = 256;
-        let resized_img = if width > max_size || height > max_size {
-            let scale = max_size as f32 / width.max(height) as f32;
-            let new_width = (width as f32 * scale) as u32;
-            let new_height = (height as f32 * scale) as u32;
-            image::DynamicImage::ImageRgba8(img).resize(
-                new_width,
-                new_height,
-                image::imageops::FilterType::Lanczos3,
-            )
-        } else {
-            image::DynamicImage::ImageRgba8(img)
-        };
+        let ico_error = |e: String| GlimError::Image(ImageError::IcoWrite(e));
+        let mut modes = Vec::with_capacity(pixmaps.len());
+        let frames = pixmaps
+            .iter()
+            .map(|pixmap| {
+                let mut data = Vec::new();
+                modes.push(write_png(pixmap, &mut data, options)?.describe());
+                image::codecs::ico::IcoFrame::with_encoded(
+                    data,
+                    pixmap.width(),
+                    pixmap.height(),
+                    image::ExtendedColorType::Rgba8,
+                )
+                .map_err(|e| ico_error(e.to_string()))
+            })
+            .collect::<Result<Vec<_>>>()?;
 
-        // Encode as ICO
-        resized_img
-            .write_with_encoder(image::codecs::ico::IcoEncoder::new(writer))
-            .map_err(|e| GlimError::Image(ImageError::IcoWrite(e.to_string())))?;
+        image::codecs::ico::IcoEncoder::new(writer)
+            .encode_images(&frames)
+            .map_err(|e| ico_error(e.to_string()))?;
         let encode_duration = encode_start.elapsed();
 
+        tracing::debug!(
+            sizes = ?ICO_SIZES,
+            modes = ?modes,
+            rasterization_duration = ?rasterize_duration,
+            encoding_duration = ?encode_duration,
+            "ICO encoding completed"
+        );
+
         Ok(EncodingTiming {
             rasterization: rasterize_duration,
             encoding: encode_duration,
@@ -427,16 +1470,33 @@ impl Encoder for EncoderType {
         &self,
         svg_data: &str,
         writer: &mut dyn Write,
-        scale: Option<f64>,
+        options: &EncodeOptions,
+    ) -> Result<EncodingTiming> {
+        match self {
+            EncoderType::Png(encoder) => encoder.encode(svg_data, writer, options),
+            EncoderType::WebP(encoder) => encoder.encode(svg_data, writer, options),
+            EncoderType::Jpeg(encoder) => encoder.encode(svg_data, writer, options),
+            EncoderType::Svg(encoder) => encoder.encode(svg_data, writer, options),
+            EncoderType::Avif(encoder) => encoder.encode(svg_data, writer, options),
+            EncoderType::Gif(encoder) => encoder.encode(svg_data, writer, options),
+            EncoderType::Ico(encoder) => encoder.encode(svg_data, writer, options),
+        }
+    }
+
+    fn encode_animation(
+        &self,
+        animation: &Animation,
+        writer: &mut dyn Write,
+        options: &EncodeOptions,
     ) -> Result<EncodingTiming> {
         match self {
-            EncoderType::Png(encoder) => encoder.encode(svg_data, writer, scale),
-            EncoderType::WebP(encoder) => encoder.encode(svg_data, writer, scale),
-            EncoderType::Jpeg(encoder) => encoder.encode(svg_data, writer, scale),
-            EncoderType::Svg(encoder) => encoder.encode(svg_data, writer, scale),
-            EncoderType::Avif(encoder) => encoder.encode(svg_data, writer, scale),
-            EncoderType::Gif(encoder) => encoder.encode(svg_data, writer, scale),
-            EncoderType::Ico(encoder) => encoder.encode(svg_data, writer, scale),
+            EncoderType::Png(encoder) => encoder.encode_animation(animation, writer, options),
+            EncoderType::WebP(encoder) => encoder.encode_animation(animation, writer, options),
+            EncoderType::Jpeg(encoder) => encoder.encode_animation(animation, writer, options),
+            EncoderType::Svg(encoder) => encoder.encode_animation(animation, writer, options),
+            EncoderType::Avif(encoder) => encoder.encode_animation(animation, writer, options),
+            EncoderType::Gif(encoder) => encoder.encode_animation(animation, writer, options),
+            EncoderType::Ico(encoder) => encoder.encode_animation(animation, writer, options),
         }
     }
 }
@@ -27,6 +27,18 @@ pub enum GlimError {
     #[error("CLI error: {0}")]
     Cli(#[from] CliError),
 
+    /// Theme lookup and color errors
+    #[error("Theme error: {0}")]
+    Theme(#[from] ThemeError),
+
+    /// Configuration file errors
+    #[error("Configuration error: {0}")]
+    Config(#[from] ConfigError),
+
+    /// Card template errors
+    #[error("Template error: {0}")]
+    Template(#[from] TemplateError),
+
     /// General I/O errors
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
@@ -126,6 +138,22 @@ pub enum ImageError {
     /// Failed to write ICO
     #[error("Failed to write ICO: {0}")]
     IcoWrite(String),
+
+    /// Encoding task panicked or was cancelled
+    #[error("Encoding task failed: {0}")]
+    Task(String),
+
+    /// Quality settings not supported by the format
+    #[error("Invalid quality settings: {0}")]
+    InvalidQuality(String),
+
+    /// Compression level given for a format without one
+    #[error("Invalid compression setting: {0}")]
+    InvalidCompression(String),
+
+    /// Failed to encode an animation
+    #[error("Failed to encode animation: {0}")]
+    Animation(String),
 }
 
 /// Server/HTTP specific errors
@@ -150,6 +178,104 @@ pub enum CliError {
     // Currently no CLI-specific errors are used
 }
 
+/// Theme specific errors
+#[derive(Error, Debug, Clone)]
+pub enum ThemeError {
+    /// No theme registered under the requested name
+    #[error("Unknown theme: {0}")]
+    Unknown(String),
+
+    /// Color field name is not part of a theme
+    #[error("Unknown theme color field: {0}")]
+    UnknownField(String),
+
+    /// Color value is not a valid hex color
+    #[error("Invalid color for {field}: {value:?}")]
+    InvalidColor { field: String, value: String },
+}
+
+/// Configuration file specific errors
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    /// Failed to read the configuration file
+    #[error("Failed to read {path}: {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+
+    /// Configuration file is not valid YAML
+    #[error("Invalid YAML: {0}")]
+    Parse(String),
+
+    /// Configuration value has the wrong type or an invalid value
+    #[error("Invalid value for {key}: {message}")]
+    Invalid { key: String, message: String },
+
+    /// Theme defined in the configuration file is invalid
+    #[error("Invalid theme {name}: {source}")]
+    Theme { name: String, source: ThemeError },
+}
+
+/// Card template specific errors
+#[derive(Error, Debug)]
+pub enum TemplateError {
+    /// No template registered under the requested name
+    #[error("Unknown template: {0}")]
+    Unknown(String),
+
+    /// Template exists but cannot render the requested kind of card
+    #[error("Template {name} cannot render {kind} cards")]
+    Incompatible { name: String, kind: &'static str },
+
+    /// Failed to read a template file or directory
+    #[error("Failed to read {path}: {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+
+    /// Template source has a syntax error
+    #[error("Invalid template {name}: {message}")]
+    Syntax { name: String, message: String },
+
+    /// Template failed while rendering
+    #[error("Failed to render template {name}: {message}")]
+    Render { name: String, message: String },
+
+    /// Template rendered successfully but did not produce a valid SVG document
+    #[error("Template {name} does not produce valid SVG: {message}")]
+    InvalidSvg { name: String, message: String },
+}
+
+/// Avatar download and processing errors
+#[derive(Error, Debug)]
+pub enum AvatarError {
+    /// Avatar URL is malformed or not served by GitHub
+    #[error("Invalid avatar URL: {0}")]
+    InvalidUrl(String),
+
+    /// Request failed or timed out
+    #[error("Failed to download avatar: {0}")]
+    Network(#[from] reqwest::Error),
+
+    /// Server responded with an error status
+    #[error("Avatar request failed with status {0}")]
+    Status(u16),
+
+    /// Response is not one of the accepted image types
+    #[error("Unsupported avatar content type: {0}")]
+    UnsupportedType(String),
+
+    /// Response body exceeds the download limit
+    #[error("Avatar exceeds the {limit} byte download limit")]
+    TooLarge { limit: usize },
+
+    /// Image failed to decode or re-encode, or exceeds the decoding limits
+    #[error("Failed to process avatar: {0}")]
+    Image(#[from] image::ImageError),
+}
+
 /// Type alias for Result using the unified error type
 pub type Result<T> = std::result::Result<T, GlimError>;
 
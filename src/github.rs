@@ -2,6 +2,7 @@
 
 use crate::errors::{self, GitHubError, Result};
 use axum::http::header;
+use chrono::{DateTime, Utc};
 use failsafe::{
     backoff::{self},
     failure_policy::{self, ConsecutiveFailures, OrElse, SuccessRateOverTimeWindow},
@@ -10,13 +11,48 @@ use failsafe::{
 use moka::future::Cache;
 use once_cell::sync::Lazy;
 use reqwest::Client;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::env;
+use std::future::Future;
 use std::time::Duration;
 use tracing::{debug, info, instrument, warn};
 
 const DEFAULT_API_RETRIES: u8 = 3;
 
+/// Number of languages kept in a user's or organization's language summary.
+const TOP_LANGUAGES: usize = 5;
+
+/// Number of items requested per page from paginated endpoints (the API maximum).
+const PER_PAGE: usize = 100;
+
+/// Maximum number of pages fetched from a paginated endpoint.
+const MAX_PAGES: usize = 10;
+
+/// Media type that adds `starred_at` timestamps to stargazer listings.
+const STAR_MEDIA_TYPE: &str = "application/vnd.github.star+json";
+
+/// Number of stargazer pages sampled for repositories with more pages than this.
+const STAR_HISTORY_SAMPLES: usize = 15;
+
+/// Last stargazer page the API serves; later pages are rejected.
+const MAX_STARGAZER_PAGES: usize = 400;
+
+/// Maximum number of points kept in a star history.
+const MAX_STAR_POINTS: usize = 60;
+
+/// Number of contributors fetched per repository, enough for the largest contributor row.
+const CONTRIBUTORS_PER_REPOSITORY: usize = crate::card::MAX_CONTRIBUTORS;
+
+/// Delays between background requests for commit activity GitHub is still computing.
+const ACTIVITY_RETRY_DELAYS: [Duration; 4] = [
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+    Duration::from_secs(20),
+    Duration::from_secs(40),
+];
+
 /// Type alias for the circuit breaker implementation
 type DefaultCircuitBreaker = StateMachine<
     OrElse<
@@ -30,10 +66,13 @@ type DefaultCircuitBreaker = StateMachine<
 pub static GITHUB_CLIENT: Lazy<GitHubClient> = Lazy::new(GitHubClient::new);
 
 /// Repository information retrieved from the GitHub API.
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone, Debug, Default)]
 pub struct Repository {
     /// Repository name
     pub name: String,
+    /// Account that owns the repository
+    #[serde(default)]
+    pub owner: Owner,
     /// Repository description
     pub description: Option<String>,
     /// Primary programming language
@@ -43,14 +82,327 @@ pub struct Repository {
     /// Number of forks
     pub forks_count: u32,
     /// Whether the repository is private
+    #[serde(default)]
     pub private: bool,
+    /// Number of open issues, including pull requests
+    #[serde(default)]
+    pub open_issues_count: u32,
+    /// Number of accounts watching the repository
+    ///
+    /// GitHub's `watchers_count` mirrors the star count; `subscribers_count`
+    /// is the actual number of watchers.
+    #[serde(default)]
+    pub subscribers_count: u32,
+    /// License detected by GitHub
+    #[serde(default)]
+    pub license: Option<License>,
+    /// Repository topics
+    #[serde(default)]
+    pub topics: Vec<String>,
+    /// Name of the default branch
+    #[serde(default)]
+    pub default_branch: String,
+    /// Time of the last push to any branch
+    #[serde(default)]
+    pub pushed_at: Option<DateTime<Utc>>,
+    /// Whether the repository is archived (read-only)
+    #[serde(default)]
+    pub archived: bool,
+    /// Whether the repository is a fork
+    #[serde(default)]
+    pub fork: bool,
+    /// Repository this one was forked from, for forks
+    #[serde(default)]
+    pub parent: Option<Parent>,
+    /// Whether the repository is a template for new repositories
+    #[serde(default)]
+    pub is_template: bool,
+    /// URL of the repository this one mirrors, for mirrors
+    #[serde(default)]
+    pub mirror_url: Option<String>,
+}
+
+/// Repository a fork was created from, as embedded in repository responses.
+#[derive(Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Parent {
+    /// Full name of the parent, e.g. `owner/repo`
+    pub full_name: String,
+}
+
+/// License of a repository, as embedded in repository responses.
+#[derive(Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct License {
+    /// SPDX identifier, e.g. `MIT`; `NOASSERTION` when GitHub can't identify the license
+    pub spdx_id: Option<String>,
+}
+
+impl License {
+    /// Returns the SPDX identifier, or None if the license is unidentified.
+    pub fn spdx(&self) -> Option<&str> {
+        self.spdx_id
+            .as_deref()
+            .filter(|id| !id.is_empty() && *id != "NOASSERTION")
+    }
+}
+
+/// Account owning a repository, as embedded in repository responses.
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct Owner {
+    /// User or organization login
+    pub login: String,
+    /// URL of the account's avatar image
+    pub avatar_url: String,
 }
 
+/// User profile retrieved from the GitHub API.
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct User {
+    /// User login
+    pub login: String,
+    /// Display name, if set
+    pub name: Option<String>,
+    /// URL of the user's avatar image
+    #[serde(default)]
+    pub avatar_url: String,
+    /// Profile bio
+    pub bio: Option<String>,
+    /// Number of followers
+    pub followers: u32,
+    /// Number of public repositories
+    pub public_repos: u32,
+    /// Most used languages across the user's own repositories, most used first
+    #[serde(skip)]
+    pub top_languages: Vec<LanguageCount>,
+}
+
+/// Organization profile retrieved from the GitHub API.
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct Organization {
+    /// Organization login
+    pub login: String,
+    /// Display name, if set
+    pub name: Option<String>,
+    /// Organization description
+    pub description: Option<String>,
+    /// URL of the organization's logo
+    #[serde(default)]
+    pub avatar_url: String,
+    /// Number of public repositories
+    pub public_repos: u32,
+    /// Total stars across the organization's public repositories
+    #[serde(skip)]
+    pub stars: u64,
+    /// Whether the repository list was cut off at the page limit, so `stars`
+    /// only counts the most recently pushed repositories
+    #[serde(skip)]
+    pub truncated: bool,
+    /// Most used languages across the organization's public (non-fork) repositories, most used first
+    #[serde(skip)]
+    pub top_languages: Vec<LanguageCount>,
+}
+
+/// A published release, as returned by the latest release endpoint.
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct Release {
+    /// Name of the release's git tag, e.g. `v1.2.0`
+    pub tag_name: String,
+    /// Release title, if set
+    pub name: Option<String>,
+    /// Time the release was published
+    #[serde(default)]
+    pub published_at: Option<DateTime<Utc>>,
+    /// Files attached to the release
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+impl Release {
+    /// Returns the total number of downloads across all assets.
+    pub fn download_count(&self) -> u64 {
+        self.assets.iter().map(|asset| asset.download_count).sum()
+    }
+}
+
+/// A file attached to a release.
+#[derive(Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReleaseAsset {
+    /// File name
+    pub name: String,
+    /// Number of times the file was downloaded
+    #[serde(default)]
+    pub download_count: u64,
+}
+
+/// An issue or pull request, as returned by the issues endpoint.
+///
+/// The issues endpoint also serves pull requests, along with their draft and
+/// merged state, so one request covers both.
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct Issue {
+    /// Issue or pull request number within the repository
+    pub number: u64,
+    /// Title
+    pub title: String,
+    /// `open` or `closed`
+    pub state: String,
+    /// Why an issue was closed: `completed`, `not_planned` or `reopened`
+    #[serde(default)]
+    pub state_reason: Option<String>,
+    /// Account that opened the issue; None for deleted accounts
+    #[serde(default)]
+    pub user: Option<Owner>,
+    /// Labels, in the order GitHub lists them
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    /// Number of comments
+    #[serde(default)]
+    pub comments: u32,
+    /// Time the issue was opened
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    /// Time the issue was last changed, including comments and labels
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Whether a pull request is a draft
+    #[serde(default)]
+    pub draft: bool,
+    /// Pull request details; None for issues
+    #[serde(default)]
+    pub pull_request: Option<PullRequestRef>,
+}
+
+impl Issue {
+    /// Returns true if this is a pull request rather than an issue.
+    pub fn is_pull_request(&self) -> bool {
+        self.pull_request.is_some()
+    }
+
+    /// Returns true if this is a merged pull request.
+    pub fn is_merged(&self) -> bool {
+        self.pull_request
+            .as_ref()
+            .is_some_and(|pull_request| pull_request.merged_at.is_some())
+    }
+}
+
+/// Pull request details embedded in issue responses.
+#[derive(Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PullRequestRef {
+    /// Time the pull request was merged, if it was
+    #[serde(default)]
+    pub merged_at: Option<DateTime<Utc>>,
+}
+
+/// A label applied to an issue or pull request.
+#[derive(Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Label {
+    /// Label name
+    pub name: String,
+    /// Background color as hex without the leading `#`, e.g. `d73a4a`
+    #[serde(default)]
+    pub color: String,
+}
+
+/// Star count of a repository at a point in time, from its stargazer listing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StarPoint {
+    /// Time the star was given
+    pub time: DateTime<Utc>,
+    /// Number of stars the repository had after it
+    pub stars: u32,
+}
+
+/// A stargazer listing entry with the star media type.
+#[derive(Deserialize)]
+struct Stargazer {
+    starred_at: DateTime<Utc>,
+}
+
+/// A contributor to a repository, as returned by the contributors endpoint.
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct Contributor {
+    /// User login
+    pub login: String,
+    /// URL of the user's avatar image
+    #[serde(default)]
+    pub avatar_url: String,
+    /// Number of commits to the default branch
+    #[serde(default)]
+    pub contributions: u32,
+    /// Avatar as a data URI, filled in from [`crate::avatar::AVATARS`] before rendering
+    #[serde(skip)]
+    pub avatar: Option<String>,
+}
+
+/// A week of commits, from a repository's commit activity statistics.
+#[derive(Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommitWeek {
+    /// Start of the week (Sunday, midnight UTC) as a Unix timestamp
+    pub week: i64,
+    /// Number of commits made during the week
+    pub total: u32,
+}
+
+/// Number of repositories using a language as their primary language.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LanguageCount {
+    /// Language name
+    pub name: String,
+    /// Number of repositories
+    pub repositories: u32,
+}
+
+/// Amount of code in a language, from a repository's language breakdown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LanguageBytes {
+    /// Language name
+    pub name: String,
+    /// Bytes of code written in the language
+    pub bytes: u64,
+}
+
+/// The fields of a repository listing needed to summarize languages and stars.
+#[derive(Deserialize)]
+struct RepositorySummary {
+    language: Option<String>,
+    #[serde(default)]
+    fork: bool,
+    #[serde(default)]
+    stargazers_count: u32,
+}
+
+/// Cache entry for repository requests.
+pub type CacheEntry = ApiCacheEntry<Repository>;
+
+/// Cache entry for user profile requests.
+pub type UserCacheEntry = ApiCacheEntry<User>;
+
+/// Cache entry for organization profile requests.
+pub type OrgCacheEntry = ApiCacheEntry<Organization>;
+
+/// Cache entry for repository language breakdown requests.
+pub type LanguagesCacheEntry = ApiCacheEntry<Vec<LanguageBytes>>;
+
+/// Cache entry for latest release requests; `None` if the repository has no releases.
+pub type ReleaseCacheEntry = ApiCacheEntry<Option<Release>>;
+
+/// Cache entry for issue and pull request requests.
+pub type IssueCacheEntry = ApiCacheEntry<Issue>;
+
+/// Cache entry for star history requests.
+pub type StarHistoryCacheEntry = ApiCacheEntry<Vec<StarPoint>>;
+
+/// Cache entry for commit activity requests; `None` while GitHub is still computing it.
+pub type ActivityCacheEntry = ApiCacheEntry<Option<Vec<CommitWeek>>>;
+
+/// Cache entry for contributor list requests.
+pub type ContributorsCacheEntry = ApiCacheEntry<Vec<Contributor>>;
+
 /// Cache entry for tracking successful and failed requests.
 #[derive(Clone, Debug)]
-pub enum CacheEntry {
-    /// Successfully fetched repository data (cached for 30 minutes)
-    Valid { data: Repository },
+pub enum ApiCacheEntry<T> {
+    /// Successfully fetched data (cached for 30 minutes)
+    Valid { data: T },
     /// Failed request with retry counter (up to 3 attempts)
     Invalid {
         error: errors::GitHubError,
@@ -69,6 +421,24 @@ pub struct GitHubClient {
     circuit_breaker: DefaultCircuitBreaker,
     /// Cache for repository data
     pub cache: Cache<String, CacheEntry>,
+    /// Cache for user profiles
+    pub user_cache: Cache<String, UserCacheEntry>,
+    /// Cache for organization profiles
+    pub org_cache: Cache<String, OrgCacheEntry>,
+    /// Cache for repository language breakdowns
+    pub languages_cache: Cache<String, LanguagesCacheEntry>,
+    /// Cache for latest releases
+    pub release_cache: Cache<String, ReleaseCacheEntry>,
+    /// Cache for issues and pull requests, with a shorter TTL
+    pub issue_cache: Cache<String, IssueCacheEntry>,
+    /// Cache for star histories, with a longer TTL
+    pub star_history_cache: Cache<String, StarHistoryCacheEntry>,
+    /// Cache for commit activity
+    pub activity_cache: Cache<String, ActivityCacheEntry>,
+    /// Cache for top contributors
+    pub contributors_cache: Cache<String, ContributorsCacheEntry>,
+    /// Repositories with a background request for commit activity in progress
+    activity_retries: Cache<String, ()>,
 }
 
 impl GitHubClient {
@@ -116,15 +486,54 @@ impl GitHubClient {
             )
             .build();
 
-        // Create cache
+        // Create caches
         let cache = Cache::builder()
             .time_to_live(Duration::from_secs(30 * 60)) // 30 minutes TTL
             .build();
+        let user_cache = Cache::builder()
+            .time_to_live(Duration::from_secs(30 * 60)) // 30 minutes TTL
+            .build();
+        let org_cache = Cache::builder()
+            .time_to_live(Duration::from_secs(30 * 60)) // 30 minutes TTL
+            .build();
+        let languages_cache = Cache::builder()
+            .time_to_live(Duration::from_secs(30 * 60)) // 30 minutes TTL
+            .build();
+        let release_cache = Cache::builder()
+            .time_to_live(Duration::from_secs(30 * 60)) // 30 minutes TTL
+            .build();
+        // Issues change state, labels and comments far more often than repositories
+        let issue_cache = Cache::builder()
+            .time_to_live(Duration::from_secs(5 * 60)) // 5 minutes TTL
+            .build();
+        // Star histories take many requests and barely change; the card adds the current count
+        let star_history_cache = Cache::builder()
+            .time_to_live(Duration::from_secs(24 * 60 * 60)) // 24 hours TTL
+            .build();
+        let activity_cache = Cache::builder()
+            .time_to_live(Duration::from_secs(30 * 60)) // 30 minutes TTL
+            .build();
+        let contributors_cache = Cache::builder()
+            .time_to_live(Duration::from_secs(30 * 60)) // 30 minutes TTL
+            .build();
+        // Outlives the background requests, in case one is dropped without cleaning up
+        let activity_retries = Cache::builder()
+            .time_to_live(Duration::from_secs(2 * 60))
+            .build();
 
         Self {
             http_client,
             circuit_breaker,
             cache,
+            user_cache,
+            org_cache,
+            languages_cache,
+            release_cache,
+            issue_cache,
+            star_history_cache,
+            activity_cache,
+            contributors_cache,
+            activity_retries,
         }
     }
 
@@ -161,21 +570,271 @@ impl GitHubClient {
     /// - When circuit breaker is open, returns a 503 Service Unavailable error
     #[instrument(skip(self))]
     pub async fn get_repository_info(&self, repo_path: &str) -> Result<Repository> {
+        self.get_cached(
+            &self.cache,
+            repo_path,
+            self.fetch_repository_info(repo_path),
+        )
+        .await
+    }
+
+    /// Fetches a repository's language breakdown with circuit breaker and caching.
+    ///
+    /// # Arguments
+    /// * `repo_path` - Repository path in format "owner/repo"
+    ///
+    /// # Returns
+    /// Languages by bytes of code, largest first
+    ///
+    /// # Circuit Breaker Behavior
+    /// Same as [`GitHubClient::get_repository_info`], with separate cache entries.
+    #[instrument(skip(self))]
+    pub async fn get_repository_languages(&self, repo_path: &str) -> Result<Vec<LanguageBytes>> {
+        self.get_cached(
+            &self.languages_cache,
+            repo_path,
+            self.fetch_repository_languages(repo_path),
+        )
+        .await
+    }
+
+    /// Fetches a repository's latest release with circuit breaker and caching.
+    ///
+    /// # Arguments
+    /// * `repo_path` - Repository path in format "owner/repo"
+    ///
+    /// # Returns
+    /// The latest published release, or None if the repository has no releases
+    ///
+    /// # Circuit Breaker Behavior
+    /// Same as [`GitHubClient::get_repository_info`], with separate cache entries.
+    /// GitHub answers 404 both for repositories without releases and for missing
+    /// repositories, so callers should look up the repository first.
+    #[instrument(skip(self))]
+    pub async fn get_latest_release(&self, repo_path: &str) -> Result<Option<Release>> {
+        self.get_cached(
+            &self.release_cache,
+            repo_path,
+            self.fetch_latest_release(repo_path),
+        )
+        .await
+    }
+
+    /// Fetches an issue or pull request with circuit breaker and caching.
+    ///
+    /// # Arguments
+    /// * `repo_path` - Repository path in format "owner/repo"
+    /// * `number` - Issue or pull request number
+    ///
+    /// # Returns
+    /// The issue or pull request, or specific error type
+    ///
+    /// # Circuit Breaker Behavior
+    /// Same as [`GitHubClient::get_repository_info`], with separate cache entries
+    /// that expire after 5 minutes instead of 30.
+    #[instrument(skip(self))]
+    pub async fn get_issue(&self, repo_path: &str, number: u64) -> Result<Issue> {
+        self.get_cached(
+            &self.issue_cache,
+            &format!("{}#{}", repo_path, number),
+            self.fetch_issue(repo_path, number),
+        )
+        .await
+    }
+
+    /// Fetches a repository's star history with circuit breaker and caching.
+    ///
+    /// # Arguments
+    /// * `repo_path` - Repository path in format "owner/repo"
+    /// * `stars` - Current number of stars, used to pick the pages to sample
+    ///
+    /// # Returns
+    /// Star counts over time, oldest first, ending at the last sampled star
+    /// rather than the current count
+    ///
+    /// # Circuit Breaker Behavior
+    /// Same as [`GitHubClient::get_repository_info`], with separate cache entries
+    /// that expire after 24 hours. Every sampled page is fetched as part of a
+    /// single call, so one failed page fails (and counts) once.
+    #[instrument(skip(self))]
+    pub async fn get_star_history(&self, repo_path: &str, stars: u32) -> Result<Vec<StarPoint>> {
+        self.get_cached(
+            &self.star_history_cache,
+            repo_path,
+            self.fetch_star_history(repo_path, stars),
+        )
+        .await
+    }
+
+    /// Fetches a repository's weekly commit activity with circuit breaker and caching.
+    ///
+    /// GitHub computes commit statistics on the first request for them and
+    /// answers 202 Accepted until they are ready. That state isn't cached;
+    /// instead the statistics are requested again in the background and cached
+    /// once ready, so a later card can include them.
+    ///
+    /// # Arguments
+    /// * `repo_path` - Repository path in format "owner/repo"
+    ///
+    /// # Returns
+    /// Commits per week over the last year, oldest first, or None while GitHub
+    /// is still computing them
+    ///
+    /// # Circuit Breaker Behavior
+    /// Same as [`GitHubClient::get_repository_info`], with separate cache entries.
+    /// Background requests are skipped while the circuit breaker is open.
+    #[instrument(skip(self))]
+    pub async fn get_commit_activity(&self, repo_path: &str) -> Result<Option<Vec<CommitWeek>>> {
+        let activity = self
+            .get_cached(
+                &self.activity_cache,
+                repo_path,
+                self.fetch_commit_activity(repo_path),
+            )
+            .await?;
+
+        if activity.is_none() {
+            self.activity_cache.invalidate(repo_path).await;
+            self.retry_commit_activity(repo_path).await;
+        }
+
+        Ok(activity)
+    }
+
+    /// Requests commit activity GitHub is still computing in the background,
+    /// caching it once ready.
+    ///
+    /// At most one background task runs per repository; it gives up after a
+    /// few attempts or on the first error.
+    async fn retry_commit_activity(&self, repo_path: &str) {
+        // Claimed atomically, so concurrent requests don't each start a task
+        let claim = self
+            .activity_retries
+            .entry_by_ref(repo_path)
+            .or_insert(())
+            .await;
+        if !claim.is_fresh() {
+            return;
+        }
+
+        let client = self.clone();
+        let repo_path = repo_path.to_string();
+        tokio::spawn(async move {
+            for delay in ACTIVITY_RETRY_DELAYS {
+                tokio::time::sleep(delay).await;
+                if !client.circuit_breaker.is_call_permitted() {
+                    break;
+                }
+
+                match client.fetch_commit_activity(&repo_path).await {
+                    Ok(Some(weeks)) => {
+                        debug!("Commit activity for {} is ready", repo_path);
+                        client
+                            .activity_cache
+                            .insert(
+                                repo_path.clone(),
+                                ApiCacheEntry::Valid { data: Some(weeks) },
+                            )
+                            .await;
+                        break;
+                    }
+                    Ok(None) => debug!("Commit activity for {} is still computing", repo_path),
+                    Err(e) => {
+                        debug!("Failed to fetch commit activity for {}: {}", repo_path, e);
+                        break;
+                    }
+                }
+            }
+
+            client.activity_retries.invalidate(&repo_path).await;
+        });
+    }
+
+    /// Fetches a repository's top contributors with circuit breaker and caching.
+    ///
+    /// # Arguments
+    /// * `repo_path` - Repository path in format "owner/repo"
+    ///
+    /// # Returns
+    /// Up to [`MAX_CONTRIBUTORS`](crate::card::MAX_CONTRIBUTORS) contributors,
+    /// most commits first, without their avatars
+    ///
+    /// # Circuit Breaker Behavior
+    /// Same as [`GitHubClient::get_repository_info`], with separate cache entries.
+    #[instrument(skip(self))]
+    pub async fn get_contributors(&self, repo_path: &str) -> Result<Vec<Contributor>> {
+        self.get_cached(
+            &self.contributors_cache,
+            repo_path,
+            self.fetch_contributors(repo_path),
+        )
+        .await
+    }
+
+    /// Fetches a user profile and language summary with circuit breaker and caching.
+    ///
+    /// # Arguments
+    /// * `login` - User login
+    ///
+    /// # Returns
+    /// User profile or specific error type
+    ///
+    /// # Circuit Breaker Behavior
+    /// Same as [`GitHubClient::get_repository_info`]; users share the circuit breaker
+    /// but have their own cache entries.
+    #[instrument(skip(self))]
+    pub async fn get_user_info(&self, login: &str) -> Result<User> {
+        self.get_cached(&self.user_cache, login, self.fetch_user_info(login))
+            .await
+    }
+
+    /// Fetches an organization profile with aggregate stars and languages, with circuit
+    /// breaker and caching.
+    ///
+    /// # Arguments
+    /// * `org` - Organization login
+    ///
+    /// # Returns
+    /// Organization profile or specific error type
+    ///
+    /// # Circuit Breaker Behavior
+    /// Same as [`GitHubClient::get_repository_info`]. Every page of repositories is
+    /// fetched as part of a single call, so one failed page fails (and counts) once.
+    #[instrument(skip(self))]
+    pub async fn get_org_info(&self, org: &str) -> Result<Organization> {
+        self.get_cached(&self.org_cache, org, self.fetch_org_info(org))
+            .await
+    }
+
+    /// Looks up a cache entry, falling back to `fetch` on a miss or a retryable failure.
+    ///
+    /// Successes are cached, failures are cached with a retry counter, and the
+    /// circuit breaker is informed of the outcome.
+    async fn get_cached<T, F>(
+        &self,
+        cache: &Cache<String, ApiCacheEntry<T>>,
+        key: &str,
+        fetch: F,
+    ) -> Result<T>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: Future<Output = Result<T>>,
+    {
         // Check cache for existing entry
-        if let Some(entry) = self.cache.get(repo_path).await {
+        if let Some(entry) = cache.get(key).await {
             match entry {
                 // Valid entry: return the data
-                CacheEntry::Valid { data } => {
-                    debug!("Cache hit for {}", repo_path);
+                ApiCacheEntry::Valid { data } => {
+                    debug!("Cache hit for {}", key);
                     return Ok(data);
                 }
                 // Invalid exhausted entry: return the error
-                CacheEntry::InvalidExhausted { error } => {
-                    debug!("Cache hit for invalid exhausted repo {}", repo_path);
+                ApiCacheEntry::InvalidExhausted { error } => {
+                    debug!("Cache hit for invalid exhausted entry {}", key);
                     return Err(errors::GlimError::GitHub(error));
                 }
                 // Invalid entry with remaining retries: try to make the API call
-                CacheEntry::Invalid {
+                ApiCacheEntry::Invalid {
                     error: _,
                     remaining: _,
                 } => {}
@@ -184,28 +843,25 @@ impl GitHubClient {
 
         // Check if the circuit breaker is open
         if !self.circuit_breaker.is_call_permitted() {
-            info!("Request blocked by circuit breaker for {}", repo_path);
+            info!("Request blocked by circuit breaker for {}", key);
             return Err(errors::GlimError::GitHub(GitHubError::CircuitBreakerOpen));
         }
 
         // Invoke the API call
-        debug!("Cache miss for {}", repo_path);
-        let result = self.fetch_repository_info(repo_path).await;
+        debug!("Cache miss for {}", key);
+        let result = fetch.await;
 
         match result {
             // Success, cache the result
-            Ok(repo) => {
-                self.cache
-                    .insert(
-                        repo_path.to_string(),
-                        CacheEntry::Valid { data: repo.clone() },
-                    )
+            Ok(data) => {
+                cache
+                    .insert(key.to_string(), ApiCacheEntry::Valid { data: data.clone() })
                     .await;
 
                 // Inform the circuit breaker of the success
                 self.circuit_breaker.on_success();
 
-                Ok(repo)
+                Ok(data)
             }
             Err(glim_error) => {
                 // Extract GitHub error from GlimError
@@ -231,7 +887,7 @@ impl GitHubClient {
                 }
 
                 // Handle the error
-                self.handle_github_error(repo_path, &github_error).await
+                Self::handle_github_error(cache, key, &github_error).await
             }
         }
     }
@@ -239,9 +895,288 @@ impl GitHubClient {
     /// Makes the actual GitHub API request.
     #[instrument(skip(self))]
     pub async fn fetch_repository_info(&self, repo_path: &str) -> Result<Repository> {
-        // Build request
-        let url = format!("https://api.github.com/repos/{}", repo_path);
-        let request = self.http_client.get(&url);
+        let repo: Repository = self.get_json(&format!("repos/{}", repo_path)).await?;
+        debug!("Fetched repo info for {}", repo_path);
+
+        if repo.private {
+            warn!("A private repository was fetched: {}", repo_path);
+
+            // Return a 404 as if the repository was not found
+            return Err(errors::GlimError::GitHub(GitHubError::NotFound));
+        }
+
+        Ok(repo)
+    }
+
+    /// Makes the repository languages API request.
+    #[instrument(skip(self))]
+    pub async fn fetch_repository_languages(&self, repo_path: &str) -> Result<Vec<LanguageBytes>> {
+        let languages: HashMap<String, u64> = self
+            .get_json(&format!("repos/{}/languages", repo_path))
+            .await?;
+        debug!("Fetched {} languages for {}", languages.len(), repo_path);
+
+        Ok(sort_languages(languages))
+    }
+
+    /// Makes the latest release API request, treating a 404 as a repository without releases.
+    #[instrument(skip(self))]
+    pub async fn fetch_latest_release(&self, repo_path: &str) -> Result<Option<Release>> {
+        match self
+            .get_json::<Release>(&format!("repos/{}/releases/latest", repo_path))
+            .await
+        {
+            Ok(release) => {
+                debug!("Fetched release {} for {}", release.tag_name, repo_path);
+                Ok(Some(release))
+            }
+            Err(errors::GlimError::GitHub(GitHubError::NotFound)) => {
+                debug!("No releases found for {}", repo_path);
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Makes the issue API request, which also serves pull requests.
+    #[instrument(skip(self))]
+    pub async fn fetch_issue(&self, repo_path: &str, number: u64) -> Result<Issue> {
+        let issue: Issue = self
+            .get_json(&format!("repos/{}/issues/{}", repo_path, number))
+            .await?;
+        debug!("Fetched issue #{} for {}", number, repo_path);
+
+        Ok(issue)
+    }
+
+    /// Fetches the stargazer pages picked by [`star_history_pages`] concurrently
+    /// and turns them into star counts over time.
+    ///
+    /// Every star is kept when all pages are fetched; otherwise each sampled page
+    /// contributes its first star.
+    #[instrument(skip(self))]
+    pub async fn fetch_star_history(&self, repo_path: &str, stars: u32) -> Result<Vec<StarPoint>> {
+        let pages = star_history_pages(stars);
+        let sampled = pages.len() < pages.last().copied().unwrap_or_default();
+
+        let mut requests = tokio::task::JoinSet::new();
+        for page in pages {
+            let client = self.clone();
+            let path = format!(
+                "repos/{}/stargazers?per_page={}&page={}",
+                repo_path, PER_PAGE, page
+            );
+            requests.spawn(async move {
+                let stargazers: Vec<Stargazer> = client.get_json_as(&path, STAR_MEDIA_TYPE).await?;
+                Ok::<_, errors::GlimError>((page, stargazers))
+            });
+        }
+
+        let mut points = Vec::new();
+        while let Some(result) = requests.join_next().await {
+            let (page, stargazers) =
+                result.map_err(|_| errors::GlimError::GitHub(GitHubError::NetworkError))??;
+            let first = (page - 1) * PER_PAGE;
+            let count = if sampled { 1 } else { stargazers.len() };
+            points.extend(stargazers.into_iter().take(count).enumerate().map(
+                |(index, stargazer)| StarPoint {
+                    time: stargazer.starred_at,
+                    stars: (first + index + 1) as u32,
+                },
+            ));
+        }
+        points.sort_by_key(|point| point.stars);
+        debug!(
+            "Fetched {} star history points for {}",
+            points.len(),
+            repo_path
+        );
+
+        Ok(thin_points(points, MAX_STAR_POINTS))
+    }
+
+    /// Makes the commit activity API request.
+    ///
+    /// Treats 202 Accepted as statistics still being computed and 204 No
+    /// Content (an empty repository) as no activity.
+    #[instrument(skip(self))]
+    pub async fn fetch_commit_activity(&self, repo_path: &str) -> Result<Option<Vec<CommitWeek>>> {
+        match self
+            .get_json::<Vec<CommitWeek>>(&format!("repos/{}/stats/commit_activity", repo_path))
+            .await
+        {
+            Ok(weeks) => {
+                debug!(
+                    "Fetched {} weeks of commit activity for {}",
+                    weeks.len(),
+                    repo_path
+                );
+                Ok(Some(weeks))
+            }
+            Err(errors::GlimError::GitHub(GitHubError::ApiError(202))) => {
+                debug!("Commit activity for {} is being computed", repo_path);
+                Ok(None)
+            }
+            Err(errors::GlimError::GitHub(GitHubError::ApiError(204))) => Ok(Some(Vec::new())),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Makes the contributors API request, treating 204 No Content (an empty repository) as none.
+    #[instrument(skip(self))]
+    pub async fn fetch_contributors(&self, repo_path: &str) -> Result<Vec<Contributor>> {
+        match self
+            .get_json::<Vec<Contributor>>(&format!(
+                "repos/{}/contributors?per_page={}",
+                repo_path, CONTRIBUTORS_PER_REPOSITORY
+            ))
+            .await
+        {
+            Ok(contributors) => {
+                debug!(
+                    "Fetched {} contributors for {}",
+                    contributors.len(),
+                    repo_path
+                );
+                Ok(contributors)
+            }
+            Err(errors::GlimError::GitHub(GitHubError::ApiError(204))) => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Fetches a user profile and summarizes the languages of their repositories.
+    #[instrument(skip(self))]
+    pub async fn fetch_user_info(&self, login: &str) -> Result<User> {
+        if !is_valid_login(login) {
+            return Err(errors::GlimError::GitHub(GitHubError::NotFound));
+        }
+
+        let mut user: User = self.get_json(&format!("users/{}", login)).await?;
+        let repositories: Vec<RepositorySummary> = self
+            .get_json(&format!(
+                "users/{}/repos?type=owner&sort=pushed&per_page=100",
+                login
+            ))
+            .await?;
+        debug!(
+            "Fetched user info for {} with {} repositories",
+            login,
+            repositories.len()
+        );
+
+        user.top_languages = top_languages(
+            repositories
+                .iter()
+                .filter(|repo| !repo.fork)
+                .filter_map(|repo| repo.language.as_deref()),
+        );
+
+        Ok(user)
+    }
+
+    /// Fetches an organization profile and summarizes its public repositories.
+    #[instrument(skip(self))]
+    pub async fn fetch_org_info(&self, org: &str) -> Result<Organization> {
+        if !is_valid_login(org) {
+            return Err(errors::GlimError::GitHub(GitHubError::NotFound));
+        }
+
+        let mut organization: Organization = self.get_json(&format!("orgs/{}", org)).await?;
+        let (repositories, truncated): (Vec<RepositorySummary>, bool) = self
+            .get_paginated(&format!("orgs/{}/repos?type=public&sort=pushed", org))
+            .await?;
+        debug!(
+            "Fetched org info for {} with {} repositories",
+            org,
+            repositories.len()
+        );
+
+        organization.stars = repositories
+            .iter()
+            .map(|repo| u64::from(repo.stargazers_count))
+            .sum();
+        organization.truncated = truncated;
+        organization.top_languages = top_languages(
+            repositories
+                .iter()
+                .filter(|repo| !repo.fork)
+                .filter_map(|repo| repo.language.as_deref()),
+        );
+
+        Ok(organization)
+    }
+
+    /// Fetches every page of a paginated list endpoint, up to a fixed page limit.
+    ///
+    /// Pages are requested in order until one comes back short. Lists longer than
+    /// the limit are truncated with a warning rather than failing the request.
+    ///
+    /// # Arguments
+    /// * `path` - API path including a query string, without `per_page` or `page`
+    ///
+    /// # Returns
+    /// The items, and whether the list was truncated at the page limit
+    ///
+    /// # Errors
+    /// Returns the error of the first page that fails, see [`GitHubClient::get_json`].
+    async fn get_paginated<T: DeserializeOwned>(&self, path: &str) -> Result<(Vec<T>, bool)> {
+        let mut items = Vec::new();
+
+        for page in 1..=MAX_PAGES {
+            let batch: Vec<T> = self
+                .get_json(&format!("{}&per_page={}&page={}", path, PER_PAGE, page))
+                .await?;
+            let last_page = batch.len() < PER_PAGE;
+            items.extend(batch);
+
+            if last_page {
+                return Ok((items, false));
+            }
+        }
+
+        warn!(
+            "Stopped paginating {} after {} pages ({} items)",
+            path,
+            MAX_PAGES,
+            items.len()
+        );
+        Ok((items, true))
+    }
+
+    /// Sends a GET request to the GitHub API and deserializes the JSON response.
+    ///
+    /// # Arguments
+    /// * `path` - API path relative to `https://api.github.com/`
+    ///
+    /// # Errors
+    /// Returns `NotFound` for 404s, `RateLimited` for 403s, `ApiError` for other
+    /// error statuses and for successes without a body (202 and 204), and
+    /// `NetworkError` if the request or parsing fails.
+    async fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.request_json(path, None).await
+    }
+
+    /// Like [`GitHubClient::get_json`], but requesting a custom media type.
+    ///
+    /// # Arguments
+    /// * `path` - API path relative to `https://api.github.com/`
+    /// * `media_type` - Value of the `Accept` header, e.g. `application/vnd.github.star+json`
+    async fn get_json_as<T: DeserializeOwned>(&self, path: &str, media_type: &str) -> Result<T> {
+        self.request_json(path, Some(media_type)).await
+    }
+
+    /// Sends a GET request, overriding the default media type if one is given.
+    async fn request_json<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        media_type: Option<&str>,
+    ) -> Result<T> {
+        let url = format!("https://api.github.com/{}", path);
+        let mut request = self.http_client.get(&url);
+        if let Some(media_type) = media_type {
+            request = request.header(header::ACCEPT, media_type);
+        }
 
         debug!("GET {}", url);
 
@@ -263,21 +1198,12 @@ impl GitHubClient {
             "Response received"
         );
 
-        if status.is_success() {
-            let repo: Repository = response
+        // 202 Accepted (still processing) and 204 No Content have no body to parse
+        if status.is_success() && !matches!(status.as_u16(), 202 | 204) {
+            response
                 .json()
                 .await
-                .map_err(|_| errors::GlimError::GitHub(GitHubError::NetworkError))?;
-            debug!("Fetched repo info for {}", repo_path);
-
-            if repo.private {
-                warn!("A private repository was fetched: {}", repo_path);
-
-                // Return a 404 as if the repository was not found
-                return Err(errors::GlimError::GitHub(GitHubError::NotFound));
-            }
-
-            Ok(repo)
+                .map_err(|_| errors::GlimError::GitHub(GitHubError::NetworkError))
         } else {
             let error = match status.as_u16() {
                 404 => GitHubError::NotFound,
@@ -290,21 +1216,21 @@ impl GitHubClient {
     }
 
     /// Handles GitHub API errors with caching logic.
-    async fn handle_github_error(
-        &self,
-        repo_path: &str,
+    async fn handle_github_error<T>(
+        cache: &Cache<String, ApiCacheEntry<T>>,
+        key: &str,
         error: &GitHubError,
-    ) -> Result<Repository> {
-        // 404 errors are immediately exhausted (no retries for non-zexistent repos)
+    ) -> Result<T>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        // 404 errors are immediately exhausted (no retries for non-existent repos, users or orgs)
         if matches!(error, GitHubError::NotFound) {
-            info!(
-                "Repository not found: {} (immediately exhausted)",
-                repo_path
-            );
-            self.cache
+            info!("Not found: {} (immediately exhausted)", key);
+            cache
                 .insert(
-                    repo_path.to_string(),
-                    CacheEntry::InvalidExhausted {
+                    key.to_string(),
+                    ApiCacheEntry::InvalidExhausted {
                         error: error.clone(),
                     },
                 )
@@ -314,10 +1240,10 @@ impl GitHubClient {
         }
 
         // Decrement remaining retries for other errors
-        let new_count = if let Some(CacheEntry::Invalid {
+        let new_count = if let Some(ApiCacheEntry::Invalid {
             error: _,
             remaining: count,
-        }) = self.cache.get(repo_path).await
+        }) = cache.get(key).await
         {
             count.saturating_sub(1)
         } else {
@@ -325,23 +1251,23 @@ impl GitHubClient {
         };
 
         info!(
-            "Failed to fetch repo info for {}, attempt {}, error: {:?}",
-            repo_path, new_count, error
+            "Failed to fetch {}, attempt {}, error: {:?}",
+            key, new_count, error
         );
 
         // Exhaust after 3 attempts, otherwise decrement counter
         let cache_entry = if new_count == 0 {
-            CacheEntry::InvalidExhausted {
+            ApiCacheEntry::InvalidExhausted {
                 error: error.clone(),
             }
         } else {
-            CacheEntry::Invalid {
+            ApiCacheEntry::Invalid {
                 error: error.clone(),
                 remaining: new_count,
             }
         };
 
-        self.cache.insert(repo_path.to_string(), cache_entry).await;
+        cache.insert(key.to_string(), cache_entry).await;
         Err(errors::GlimError::GitHub(error.clone()))
     }
 
@@ -361,3 +1287,99 @@ impl Default for GitHubClient {
         Self::new()
     }
 }
+
+/// Returns whether a string is a valid GitHub login.
+///
+/// Logins are 1 to 39 ASCII alphanumeric characters or single hyphens, and
+/// cannot start or end with a hyphen.
+pub fn is_valid_login(login: &str) -> bool {
+    (1..=39).contains(&login.len())
+        && login.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        && !login.starts_with('-')
+        && !login.ends_with('-')
+        && !login.contains("--")
+}
+
+/// Counts repositories per language, most used first.
+///
+/// Ties are broken alphabetically, and only the top languages are kept.
+///
+/// # Arguments
+/// * `languages` - Primary language of each repository
+///
+/// # Returns
+/// Up to five languages with their repository counts
+pub fn top_languages<'a>(languages: impl IntoIterator<Item = &'a str>) -> Vec<LanguageCount> {
+    let mut counts: HashMap<&str, u32> = HashMap::new();
+    for language in languages {
+        *counts.entry(language).or_default() += 1;
+    }
+
+    let mut languages: Vec<LanguageCount> = counts
+        .into_iter()
+        .map(|(name, repositories)| LanguageCount {
+            name: name.to_string(),
+            repositories,
+        })
+        .collect();
+    languages.sort_by(|a, b| {
+        b.repositories
+            .cmp(&a.repositories)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    languages.truncate(TOP_LANGUAGES);
+    languages
+}
+
+/// Orders a language breakdown by bytes of code, largest first.
+///
+/// Ties are broken alphabetically and languages without code are dropped.
+///
+/// # Arguments
+/// * `languages` - Bytes of code per language, as returned by the languages endpoint
+pub fn sort_languages(languages: impl IntoIterator<Item = (String, u64)>) -> Vec<LanguageBytes> {
+    let mut languages: Vec<LanguageBytes> = languages
+        .into_iter()
+        .filter(|(_, bytes)| *bytes > 0)
+        .map(|(name, bytes)| LanguageBytes { name, bytes })
+        .collect();
+    languages.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+    languages
+}
+
+/// Picks the stargazer pages to fetch for a star history.
+///
+/// Repositories with few pages have every page fetched. Larger ones have a
+/// fixed number of pages sampled evenly from the first to the last, so a
+/// 100k-star repository costs as many requests as one with 2k stars. The API
+/// stops listing stargazers after 400 pages, so later stars are never sampled.
+///
+/// # Arguments
+/// * `stars` - Current number of stars
+///
+/// # Returns
+/// Page numbers in ascending order, empty for repositories without stars
+pub fn star_history_pages(stars: u32) -> Vec<usize> {
+    let pages = (stars as usize).div_ceil(PER_PAGE).min(MAX_STARGAZER_PAGES);
+    if pages <= STAR_HISTORY_SAMPLES {
+        return (1..=pages).collect();
+    }
+
+    let mut sampled: Vec<usize> = (0..STAR_HISTORY_SAMPLES)
+        .map(|index| 1 + index * (pages - 1) / (STAR_HISTORY_SAMPLES - 1))
+        .collect();
+    sampled.dedup();
+    sampled
+}
+
+/// Keeps at most `max` points spread evenly across the list, always keeping the first and last.
+fn thin_points(points: Vec<StarPoint>, max: usize) -> Vec<StarPoint> {
+    if points.len() <= max || max < 2 {
+        return points;
+    }
+
+    let last = points.len() - 1;
+    (0..max)
+        .map(|index| points[index * last / (max - 1)].clone())
+        .collect()
+}
@@ -3,72 +3,58 @@
 //! This module handles SVG template processing and multi-format encoding
 //! to create beautiful repository cards with dynamic content.
 
+use crate::encode::EncodeOptions;
 use crate::errors::{GlimError, ImageError, Result};
+use once_cell::sync::Lazy;
 use resvg::{tiny_skia, usvg};
+use std::sync::Arc;
 use tracing::instrument;
 
 // Re-export ImageFormat for public use
 pub use crate::encode::ImageFormat;
 
+/// Fonts available for rendering, loaded once and shared by all rasterizers.
+static FONT_DATABASE: Lazy<Arc<usvg::fontdb::Database>> = Lazy::new(|| Arc::new(load_fonts()));
+
 /// SVG to PNG rasterizer with font support.
 #[derive(Debug)]
 pub struct Rasterizer {
-    font_db: usvg::fontdb::Database,
+    font_db: Arc<usvg::fontdb::Database>,
 }
 
-/// Wraps text to fit within a specified width.
-///
-/// # Arguments
-/// * `text` - The text to wrap
-/// * `width` - Maximum line width in characters
-///
-/// # Returns
-/// SVG tspan elements with wrapped text
-pub fn wrap_text(text: &str, width: usize) -> String {
-    let mut lines = Vec::new();
-    let mut current_line = String::new();
-
-    for word in text.split_whitespace() {
-        if current_line.len() + word.len() + 1 > width {
-            lines.push(current_line);
-            current_line = String::new();
-        }
-        if !current_line.is_empty() {
-            current_line.push(' ');
+/// Returns the shared font database used for rendering and text measurement.
+pub fn font_database() -> Arc<usvg::fontdb::Database> {
+    FONT_DATABASE.clone()
+}
+
+/// Loads system fonts and the fonts bundled with glim.
+#[instrument]
+fn load_fonts() -> usvg::fontdb::Database {
+    let mut fontdb = usvg::fontdb::Database::new();
+    fontdb.load_system_fonts();
+
+    // Try multiple font paths for different environments
+    let font_paths = ["src/fonts", "fonts"];
+    for path in &font_paths {
+        if std::path::Path::new(path).exists() {
+            fontdb.load_fonts_dir(path);
+            break;
         }
-        current_line.push_str(word);
     }
-    lines.push(current_line);
-
-    lines
-        .iter()
-        .enumerate()
-        .map(|(i, line)| {
-            format!(
-                r#"<tspan x="16" dy="{}em">{}</tspan>"#,
-                (i as f32 * 1.9) - 0.5,
-                line
-            )
-        })
-        .collect::<String>()
+
+    fontdb
 }
 
 impl Rasterizer {
-    #[instrument]
     pub fn new() -> Self {
-        let mut fontdb = usvg::fontdb::Database::new();
-        fontdb.load_system_fonts();
-
-        // Try multiple font paths for different environments
-        let font_paths = ["src/fonts", "fonts"];
-        for path in &font_paths {
-            if std::path::Path::new(path).exists() {
-                fontdb.load_fonts_dir(path);
-                break;
-            }
+        Self {
+            font_db: font_database(),
         }
+    }
 
-        Self { font_db: fontdb }
+    /// Returns the font database used for rendering.
+    pub fn font_db(&self) -> &Arc<usvg::fontdb::Database> {
+        &self.font_db
     }
 
     #[instrument(skip(self, svg_data))]
@@ -82,13 +68,20 @@ impl Rasterizer {
         svg_data: &str,
         scale: Option<f64>,
     ) -> Result<tiny_skia::Pixmap> {
-        let options = usvg::Options {
-            fontdb: std::sync::Arc::new(self.font_db.clone()),
-            ..Default::default()
-        };
+        self.render_with_options(svg_data, &EncodeOptions::with_scale(scale))
+    }
 
-        let tree = usvg::Tree::from_str(svg_data, &options)
-            .map_err(|e| GlimError::Image(ImageError::SvgRendering(e.to_string())))?;
+    /// Renders an SVG to a pixmap.
+    ///
+    /// The output is the SVG size multiplied by the scale factor, plus a
+    /// transparent margin on each side if `options.padding` is set.
+    #[instrument(skip(self, svg_data))]
+    pub fn render_with_options(
+        &self,
+        svg_data: &str,
+        options: &EncodeOptions,
+    ) -> Result<tiny_skia::Pixmap> {
+        let tree = self.parse(svg_data)?;
 
         // Get the original SVG dimensions
         let original_size = tree.size().to_int_size();
@@ -96,11 +89,15 @@ impl Rasterizer {
         let original_height = original_size.height() as f32;
 
         // Apply scale factor (minimum 0.1 = 10%)
-        let scale_factor = scale.unwrap_or(1.0).max(0.1) as f32;
+        let scale_factor = options.scale.unwrap_or(1.0).max(0.1) as f32;
 
         // Calculate new dimensions with padding that scales with scale factor
         let base_padding = 20.0; // Base padding in pixels
-        let padding = (base_padding * scale_factor).min(20.0); // Scale padding but cap at 20px
+        let padding = if options.padding {
+            (base_padding * scale_factor).min(20.0) // Scale padding but cap at 20px
+        } else {
+            0.0
+        };
         let new_width = (original_width * scale_factor) + (2.0 * padding);
         let new_height = (original_height * scale_factor) + (2.0 * padding);
 
@@ -125,6 +122,55 @@ impl Rasterizer {
 
         Ok(pixmap)
     }
+
+    /// Renders an SVG once for each size, fitted within a square of that size
+    /// and centered, so small sizes are drawn natively rather than downsampled.
+    ///
+    /// # Arguments
+    /// * `svg_data` - The SVG data to render
+    /// * `sizes` - Side of each square output, in pixels
+    ///
+    /// # Returns
+    /// One pixmap per size, in the same order
+    #[instrument(skip(self, svg_data))]
+    pub fn render_squares(&self, svg_data: &str, sizes: &[u32]) -> Result<Vec<tiny_skia::Pixmap>> {
+        let tree = self.parse(svg_data)?;
+        let width = tree.size().width();
+        let height = tree.size().height();
+
+        sizes
+            .iter()
+            .map(|&size| {
+                let mut pixmap = tiny_skia::Pixmap::new(size, size).ok_or_else(|| {
+                    GlimError::Image(ImageError::PixmapCreation(format!(
+                        "Failed to create {0}x{0} pixmap",
+                        size
+                    )))
+                })?;
+
+                let scale = size as f32 / width.max(height);
+                let render_ts = tiny_skia::Transform::from_translate(
+                    (size as f32 - width * scale) / 2.0,
+                    (size as f32 - height * scale) / 2.0,
+                )
+                .pre_scale(scale, scale);
+                resvg::render(&tree, render_ts, &mut pixmap.as_mut());
+
+                Ok(pixmap)
+            })
+            .collect()
+    }
+
+    /// Parses an SVG document with the rasterizer's fonts.
+    fn parse(&self, svg_data: &str) -> Result<usvg::Tree> {
+        let usvg_options = usvg::Options {
+            fontdb: self.font_db.clone(),
+            ..Default::default()
+        };
+
+        usvg::Tree::from_str(svg_data, &usvg_options)
+            .map_err(|e| GlimError::Image(ImageError::SvgRendering(e.to_string())))
+    }
 }
 
 impl Default for Rasterizer {
@@ -153,26 +199,27 @@ pub fn parse_extension(extension: &str) -> Option<ImageFormat> {
     }
 }
 
-/// Formats a number string to show thousands with "k" suffix.
+/// Formats a number string to show thousands with a "k" suffix and millions with an "M" suffix.
 ///
 /// # Arguments
 /// * `count` - The count as a string
 ///
 /// # Returns
-/// Formatted string (e.g., "1200" -> "1.2k", "820" -> "820")
+/// Formatted string (e.g., "1200" -> "1.2k", "820" -> "820", "2500000" -> "2.5M")
 pub fn format_count(count: &str) -> String {
-    if let Ok(num) = count.parse::<u32>() {
-        if num >= 1000 {
-            let thousands = num as f64 / 1000.0;
-            if thousands >= 10.0 {
-                format!("{}k", (thousands as u32))
-            } else {
-                format!("{:.1}k", thousands)
-            }
-        } else {
-            count.to_string()
-        }
+    let Ok(num) = count.parse::<u64>() else {
+        return count.to_string();
+    };
+
+    let (value, suffix) = match num {
+        0..=999 => return count.to_string(),
+        1_000..=999_999 => (num as f64 / 1_000.0, "k"),
+        _ => (num as f64 / 1_000_000.0, "M"),
+    };
+
+    if value >= 10.0 {
+        format!("{}{}", value as u64, suffix)
     } else {
-        count.to_string()
+        format!("{:.1}{}", value, suffix)
     }
 }
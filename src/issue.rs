@@ -0,0 +1,497 @@
+//! Issue and pull request cards.
+//!
+//! An [`IssueCard`] shows an issue's or pull request's title, state, author,
+//! labels, comment count and age. The state is drawn as a pill in GitHub's
+//! colors: open, closed or closed as not planned for issues, and open, draft,
+//! merged or closed for pull requests. Issue cards render through the same
+//! template engine as repository cards, using templates that accept
+//! [`IssueCardContext`] (see [`TemplateKind`](crate::template::TemplateKind)).
+//!
+//! # Example
+//!
+//! ```rust
+//! use glim::card::{CardOptions, Render};
+//! use glim::github::{Issue, Repository};
+//! use glim::issue::IssueCard;
+//! use glim::template::ISSUE_TEMPLATE;
+//!
+//! let repository = Repository {
+//!     name: "glim".to_string(),
+//!     ..Default::default()
+//! };
+//! let issue = Issue {
+//!     number: 42,
+//!     title: "Cards render blank on Safari".to_string(),
+//!     state: "open".to_string(),
+//!     ..Default::default()
+//! };
+//!
+//! let options = CardOptions {
+//!     template: ISSUE_TEMPLATE.to_string(),
+//!     ..Default::default()
+//! };
+//!
+//! let svg = IssueCard::new(repository, issue, options).render().unwrap();
+//! assert!(svg.contains("Cards render blank on Safari"));
+//! ```
+
+use crate::avatar::SAMPLE_AVATAR;
+use crate::card::{
+    age_label, layout_row, round_coordinate, CardLayout, CardOptions, Layout, Render, ThemeColors,
+};
+use crate::github::{Issue, Label, Owner, PullRequestRef, Repository};
+use crate::template::{Sample, ISSUE_TEMPLATE};
+use crate::text::{strip_control_chars, FontStyle, TextMeasurer};
+use crate::theme::{contrast_ratio, parse_hex_color};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// Font of the title, matching the `.title` class in `issue.svg`.
+const TITLE_FONT: FontStyle = FontStyle {
+    family: "Inter",
+    size: 16.0,
+    weight: 700,
+};
+
+/// Font of the state pill and the author's login, matching the `.state` and `.author` classes in `issue.svg`.
+const STATE_FONT: FontStyle = FontStyle {
+    family: "Inter",
+    size: 12.0,
+    weight: 700,
+};
+
+/// Font of the repository reference and footer, matching `issue.svg`.
+const TEXT_FONT: FontStyle = FontStyle {
+    family: "Inter",
+    size: 12.0,
+    weight: 400,
+};
+
+/// Font of label chips, matching the `.label` class in `issue.svg`.
+const LABEL_FONT: FontStyle = FontStyle {
+    family: "Inter",
+    size: 11.0,
+    weight: 600,
+};
+
+/// Card width in pixels.
+const CARD_WIDTH: u32 = 400;
+
+/// Horizontal padding inside the card.
+const PADDING: f32 = 16.0;
+
+/// Space available for the title, labels and footer.
+const TEXT_MAX_WIDTH: f32 = 368.0;
+
+/// Maximum number of title lines before clamping with an ellipsis.
+const TITLE_MAX_LINES: usize = 3;
+
+/// Baseline of the first title line.
+const TITLE_BASELINE: f64 = 68.0;
+
+/// Distance between title baselines (1.4em at 16px).
+const TITLE_LINE_HEIGHT: f64 = 22.4;
+
+/// Width of the state icon and the padding around the pill's contents.
+const STATE_ICON_WIDTH: f32 = 20.0;
+
+/// Horizontal padding inside the state pill.
+const STATE_PADDING: f32 = 10.0;
+
+/// Space between the state pill and the repository reference.
+const STATE_GAP: f32 = 8.0;
+
+/// Distance from the last title baseline to the top of the label row.
+const LABELS_OFFSET: f64 = 14.0;
+
+/// Height of a label chip.
+const LABEL_HEIGHT: f64 = 20.0;
+
+/// Horizontal padding inside a label chip.
+const LABEL_PADDING: f32 = 8.0;
+
+/// Space between label chips.
+const LABEL_GAP: f32 = 6.0;
+
+/// Distance from the last title baseline to the footer baseline, without labels.
+const FOOTER_GAP: f64 = 30.0;
+
+/// Distance from the bottom of the label row to the footer baseline.
+const LABELS_FOOTER_GAP: f64 = 24.0;
+
+/// Width of the author avatar and the space before the author's login.
+const AUTHOR_AVATAR_WIDTH: f32 = 22.0;
+
+/// Space between the author line and the comment count.
+const COMMENTS_GAP: f32 = 16.0;
+
+/// Distance from the footer baseline to the bottom edge of the card.
+const BOTTOM_PADDING: f64 = 25.0;
+
+/// Label text colors, whichever contrasts more with the label's background.
+const LABEL_TEXT_COLORS: [&str; 2] = ["#1f2328", "#ffffff"];
+
+/// Background of labels without a valid color.
+const FALLBACK_LABEL_COLOR: &str = "#ededed";
+
+/// State of an issue or pull request, as shown in its state pill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueState {
+    Open,
+    /// A draft pull request
+    Draft,
+    /// A merged pull request
+    Merged,
+    Closed,
+    /// An issue closed as not planned
+    NotPlanned,
+}
+
+impl IssueState {
+    /// Determines the state of an issue or pull request.
+    pub fn of(issue: &Issue) -> Self {
+        let open = issue.state == "open";
+        match (issue.is_pull_request(), open) {
+            (true, true) if issue.draft => IssueState::Draft,
+            (_, true) => IssueState::Open,
+            (true, false) if issue.is_merged() => IssueState::Merged,
+            (false, false) if issue.state_reason.as_deref() == Some("not_planned") => {
+                IssueState::NotPlanned
+            }
+            (_, false) => IssueState::Closed,
+        }
+    }
+
+    /// Returns the label shown in the state pill.
+    pub fn label(&self) -> &'static str {
+        match self {
+            IssueState::Open => "Open",
+            IssueState::Draft => "Draft",
+            IssueState::Merged => "Merged",
+            IssueState::Closed | IssueState::NotPlanned => "Closed",
+        }
+    }
+
+    /// Returns GitHub's color for the state; closed issues and pull requests differ.
+    pub fn color(&self, pull_request: bool) -> &'static str {
+        match self {
+            IssueState::Open => "#1f883d",
+            IssueState::Draft | IssueState::NotPlanned => "#59636e",
+            IssueState::Merged => "#8250df",
+            IssueState::Closed if pull_request => "#cf222e",
+            IssueState::Closed => "#8250df",
+        }
+    }
+
+    /// Returns the name of the state's icon in `issue.svg`.
+    pub fn icon(&self, pull_request: bool) -> &'static str {
+        match (self, pull_request) {
+            (IssueState::Open, false) => "issue_open",
+            (IssueState::Open, true) => "pull_open",
+            (IssueState::Draft, _) => "pull_draft",
+            (IssueState::Merged, _) => "pull_merged",
+            (IssueState::Closed, false) => "issue_closed",
+            (IssueState::Closed, true) => "pull_closed",
+            (IssueState::NotPlanned, _) => "issue_not_planned",
+        }
+    }
+}
+
+/// An issue or pull request card ready to be rendered.
+#[derive(Debug, Clone)]
+pub struct IssueCard {
+    /// Repository the issue belongs to
+    pub repository: Repository,
+    /// Issue or pull request shown on the card
+    pub issue: Issue,
+    /// Presentation options
+    pub options: CardOptions,
+    /// Author avatar as a data URI, see [`crate::avatar`]
+    pub avatar: Option<String>,
+    /// Time the card is rendered at, used for the age
+    pub now: DateTime<Utc>,
+}
+
+/// The state pill at the top of the card.
+#[derive(Debug, Serialize)]
+pub struct StatePill {
+    pub state: IssueState,
+    /// Text shown in the pill, e.g. `Merged`
+    pub label: &'static str,
+    /// Name of the icon in `issue.svg`, e.g. `pull_merged`
+    pub icon: &'static str,
+    /// Background color
+    pub color: &'static str,
+    /// Width including padding
+    pub width: u32,
+}
+
+/// A label chip in the label row.
+#[derive(Debug, Serialize)]
+pub struct LabelChip {
+    pub name: String,
+    /// Background color as `#rrggbb`
+    pub color: String,
+    /// Text color contrasting with the background
+    pub text_color: &'static str,
+    /// Offset from the left edge of the row
+    pub x: u32,
+    /// Width including padding
+    pub width: u32,
+}
+
+/// Values exposed to issue card templates.
+#[derive(Debug, Serialize)]
+pub struct IssueCardContext<'a> {
+    /// Login of the repository owner
+    pub owner: Cow<'a, str>,
+    /// Repository name
+    pub name: Cow<'a, str>,
+    pub number: u64,
+    /// `owner/name #number`, truncated with an ellipsis to fit beside the state pill
+    pub reference: String,
+    /// Whether this is a pull request rather than an issue
+    pub is_pull_request: bool,
+    pub title: Cow<'a, str>,
+    /// Title wrapped to the card width, ending in an ellipsis if clamped
+    pub title_lines: Vec<String>,
+    pub state: StatePill,
+    /// Login of the author, `ghost` for deleted accounts
+    pub author: Cow<'a, str>,
+    /// Author avatar as a data URI, if available
+    pub avatar: Option<&'a str>,
+    /// Time since the issue was opened, e.g. `opened 3 days ago`, empty if unknown
+    pub opened: String,
+    pub comments: u32,
+    /// Horizontal offset of the comment count in the footer
+    pub comments_x: f32,
+    /// Labels that fit on one row
+    pub labels: Vec<LabelChip>,
+    /// Top edge of the label row
+    pub labels_y: f64,
+    /// Card dimensions, with the first title baseline at `description_y` and the footer at `stats_y`
+    pub layout: CardLayout,
+    /// Theme colors, exposed as top-level variables
+    #[serde(flatten)]
+    pub colors: ThemeColors<'a>,
+}
+
+impl IssueCard {
+    /// Creates a card from a repository, one of its issues and presentation options.
+    pub fn new(repository: Repository, issue: Issue, options: CardOptions) -> Self {
+        Self {
+            repository,
+            issue,
+            options,
+            avatar: None,
+            now: Utc::now(),
+        }
+    }
+
+    /// Sets the author avatar, as returned by [`AvatarCache::get`](crate::avatar::AvatarCache::get).
+    pub fn with_avatar(mut self, avatar: Option<String>) -> Self {
+        self.avatar = avatar;
+        self
+    }
+
+    /// Sets the time the card is rendered at, instead of the time it was created.
+    pub fn at(mut self, now: DateTime<Utc>) -> Self {
+        self.now = now;
+        self
+    }
+
+    /// Returns the time since the issue was opened, e.g. `opened 3 days ago`.
+    pub fn opened(&self) -> String {
+        self.issue
+            .created_at
+            .map(|created_at| age_label("opened", created_at, self.now))
+            .unwrap_or_default()
+    }
+}
+
+impl Render for IssueCard {
+    type Context<'a> = IssueCardContext<'a>;
+
+    fn options(&self) -> &CardOptions {
+        &self.options
+    }
+
+    fn context(&self) -> IssueCardContext<'_> {
+        let issue = &self.issue;
+        let theme = &self.options.theme;
+        let measurer = TextMeasurer::shared();
+
+        let owner = strip_control_chars(&self.repository.owner.login);
+        let name = strip_control_chars(&self.repository.name);
+        let pull_request = issue.is_pull_request();
+
+        let state = IssueState::of(issue);
+        let state = StatePill {
+            state,
+            label: state.label(),
+            icon: state.icon(pull_request),
+            color: state.color(pull_request),
+            width: (2.0 * STATE_PADDING
+                + STATE_ICON_WIDTH
+                + measurer.width(state.label(), &STATE_FONT))
+            .ceil() as u32,
+        };
+        let reference_x = PADDING + state.width as f32 + STATE_GAP;
+        let reference = if owner.is_empty() {
+            format!("{} #{}", name, issue.number)
+        } else {
+            format!("{}/{} #{}", owner, name, issue.number)
+        };
+
+        let title = strip_control_chars(issue.title.trim());
+        let title_lines = measurer.wrap(&title, &TITLE_FONT, TEXT_MAX_WIDTH, TITLE_MAX_LINES);
+        let labels = layout_labels(measurer, &issue.labels);
+        let (layout, labels_y) = issue_layout(title_lines.len(), !labels.is_empty());
+
+        let author = issue
+            .user
+            .as_ref()
+            .map(|user| strip_control_chars(&user.login))
+            .filter(|login| !login.is_empty())
+            .unwrap_or(Cow::Borrowed("ghost"));
+        let opened = self.opened();
+        let comments_x = AUTHOR_AVATAR_WIDTH
+            + measurer.width(&author, &STATE_FONT)
+            + measurer.width(&format!(" {}", opened), &TEXT_FONT)
+            + COMMENTS_GAP;
+
+        IssueCardContext {
+            reference: measurer.truncate(
+                &reference,
+                &TEXT_FONT,
+                PADDING + TEXT_MAX_WIDTH - reference_x,
+            ),
+            owner,
+            name,
+            number: issue.number,
+            is_pull_request: pull_request,
+            title_lines,
+            title,
+            state,
+            author,
+            avatar: self.avatar.as_deref(),
+            opened,
+            comments: issue.comments,
+            comments_x,
+            labels,
+            labels_y,
+            layout,
+            colors: ThemeColors::from(theme),
+        }
+    }
+}
+
+impl Sample for IssueCard {
+    fn sample() -> Self {
+        let repository = Repository {
+            name: "glim".to_string(),
+            owner: Owner {
+                login: "Xevion".to_string(),
+                avatar_url: String::new(),
+            },
+            ..Default::default()
+        };
+        let sample_time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let issue = Issue {
+            number: 42,
+            title: "Render <svg> cards & cache them".to_string(),
+            state: "closed".to_string(),
+            user: Some(Owner {
+                login: "octocat".to_string(),
+                avatar_url: String::new(),
+            }),
+            labels: vec![
+                Label {
+                    name: "enhancement".to_string(),
+                    color: "a2eeef".to_string(),
+                },
+                Label {
+                    name: "<needs> review".to_string(),
+                    color: "d73a4a".to_string(),
+                },
+            ],
+            comments: 12,
+            created_at: Some(sample_time),
+            updated_at: Some(sample_time),
+            pull_request: Some(PullRequestRef {
+                merged_at: Some(sample_time),
+            }),
+            ..Default::default()
+        };
+        let options = CardOptions {
+            template: ISSUE_TEMPLATE.to_string(),
+            ..Default::default()
+        };
+
+        Self::new(repository, issue, options)
+            .with_avatar(Some(SAMPLE_AVATAR.to_string()))
+            .at(sample_time + chrono::Duration::days(3))
+    }
+}
+
+/// Computes the card size and the top of the label row.
+///
+/// # Arguments
+/// * `title_lines` - Number of wrapped title lines
+/// * `has_labels` - Whether the label row is shown
+///
+/// # Returns
+/// The card layout and the top edge of the label row
+fn issue_layout(title_lines: usize, has_labels: bool) -> (CardLayout, f64) {
+    let last_baseline = TITLE_BASELINE + title_lines.saturating_sub(1) as f64 * TITLE_LINE_HEIGHT;
+    let labels_y = round_coordinate(last_baseline + LABELS_OFFSET);
+    let stats_y = if has_labels {
+        labels_y + LABEL_HEIGHT + LABELS_FOOTER_GAP
+    } else {
+        round_coordinate(last_baseline + FOOTER_GAP)
+    };
+
+    let layout = CardLayout {
+        kind: Layout::Card,
+        width: CARD_WIDTH,
+        height: (stats_y + BOTTOM_PADDING).ceil() as u32,
+        description_y: TITLE_BASELINE,
+        stats_y,
+    };
+    (layout, labels_y)
+}
+
+/// Builds the label chips that fit on one row, see [`layout_row`].
+fn layout_labels(measurer: &TextMeasurer, labels: &[Label]) -> Vec<LabelChip> {
+    layout_row(
+        labels
+            .iter()
+            .map(|label| (strip_control_chars(&label.name), label)),
+        TEXT_MAX_WIDTH,
+        LABEL_GAP,
+        |(name, _)| (measurer.width(name, &LABEL_FONT) + 2.0 * LABEL_PADDING).ceil(),
+    )
+    .into_iter()
+    .map(|((name, label), x, width)| {
+        let color =
+            parse_hex_color(&label.color).unwrap_or_else(|| FALLBACK_LABEL_COLOR.to_string());
+        let text_color = LABEL_TEXT_COLORS
+            .into_iter()
+            .max_by(|a, b| {
+                let contrast = |text| contrast_ratio(text, &color).unwrap_or_default();
+                contrast(*a).total_cmp(&contrast(*b))
+            })
+            .unwrap_or(LABEL_TEXT_COLORS[0]);
+
+        LabelChip {
+            name: name.into_owned(),
+            color,
+            text_color,
+            x: x as u32,
+            width: width as u32,
+        }
+    })
+    .collect()
+}
@@ -1,4 +1,6 @@
+pub mod avatar;
 pub mod cache;
+pub mod card;
 pub mod cli;
 pub mod colors;
 pub mod config;
@@ -6,5 +8,11 @@ pub mod encode;
 pub mod errors;
 pub mod github;
 pub mod image;
+pub mod issue;
+pub mod profile;
 pub mod ratelimit;
+pub mod release;
 pub mod server;
+pub mod template;
+pub mod text;
+pub mod theme;
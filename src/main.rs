@@ -3,7 +3,9 @@
 //! A command-line tool and HTTP server for creating dynamic repository cards
 //! that display GitHub repository information in a clean, visual format.
 
+pub mod avatar;
 pub mod cache;
+pub mod card;
 pub mod cli;
 pub mod colors;
 pub mod config;
@@ -11,8 +13,14 @@ pub mod encode;
 pub mod errors;
 pub mod github;
 pub mod image;
+pub mod issue;
+pub mod profile;
 pub mod ratelimit;
+pub mod release;
 pub mod server;
+pub mod template;
+pub mod text;
+pub mod theme;
 
 use crate::errors::Result;
 use std::net::SocketAddr;
@@ -68,11 +76,13 @@ async fn main() -> Result<()> {
 
     tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
 
-    if let Some(addr_argument) = cli.server.as_ref() {
-        // Load configuration with CLI overrides
-        let cli_overrides = config::CliOverrides::from_cli_args(cli.token, cli.port);
-        let config = config::Config::load(Some(cli_overrides));
+    // Load configuration with CLI overrides
+    let cli_overrides = config::CliOverrides::from_cli_args(cli.token.clone(), cli.port)
+        .with_config_file(cli.config.clone())
+        .with_templates_dir(cli.templates.clone());
+    let config = config::Config::try_load(Some(cli_overrides))?;
 
+    if let Some(addr_argument) = cli.server.as_ref() {
         let addrs = addr_argument.as_ref().map_or(
             Ok(vec![SocketAddr::new(
                 config.default_host(),
@@ -86,10 +96,10 @@ async fn main() -> Result<()> {
             tracing::error!("Server error: {}", e);
             return Err(crate::errors::GlimError::General(e));
         }
-    } else if cli.repository.is_some() {
-        cli::run(cli).await?;
+    } else if cli.repository.is_some() || cli.command.is_some() {
+        cli::run(cli, config).await?;
     } else {
-        tracing::error!("Please provide a repository or start the server with --server.");
+        tracing::error!("Please provide a repository, a user (glim user <login>), an organization (glim org <org>) or start the server with --server.");
     }
 
     Ok(())
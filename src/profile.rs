@@ -0,0 +1,387 @@
+//! Profile cards for GitHub users and organizations.
+//!
+//! A [`UserCard`] shows a user's name, bio, follower and repository counts and
+//! their most used languages. An [`OrgCard`] shows an organization's name,
+//! description, repository count, and the total stars and most used languages
+//! across its public repositories. Both render through the same template engine
+//! as repository cards, using templates that accept [`UserCardContext`] or
+//! [`OrgCardContext`] (see [`TemplateKind`](crate::template::TemplateKind)).
+//!
+//! # Example
+//!
+//! ```rust
+//! use glim::card::{CardOptions, Render};
+//! use glim::github::User;
+//! use glim::profile::UserCard;
+//! use glim::template::USER_TEMPLATE;
+//!
+//! let user = User {
+//!     login: "octocat".to_string(),
+//!     name: Some("The Octocat".to_string()),
+//!     followers: 4200,
+//!     public_repos: 8,
+//!     ..Default::default()
+//! };
+//!
+//! let options = CardOptions {
+//!     template: USER_TEMPLATE.to_string(),
+//!     ..Default::default()
+//! };
+//!
+//! let svg = UserCard::new(user, options).render().unwrap();
+//! assert!(svg.contains("The Octocat"));
+//! ```
+
+use crate::avatar::SAMPLE_AVATAR;
+use crate::card::{
+    layout_row, round_coordinate, CardLayout, CardOptions, Layout, Render, ThemeColors,
+    FALLBACK_LANGUAGE_COLOR,
+};
+use crate::github::{LanguageCount, Organization, User};
+use crate::template::{Sample, ORG_TEMPLATE, USER_TEMPLATE};
+use crate::text::{strip_control_chars, FontStyle, TextMeasurer};
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// Font of the display name, matching the `.name` class in `user.svg`.
+const NAME_FONT: FontStyle = FontStyle {
+    family: "Inter",
+    size: 16.0,
+    weight: 700,
+};
+
+/// Font of the bio, description and language labels, matching `user.svg` and `org.svg`.
+const TEXT_FONT: FontStyle = FontStyle {
+    family: "Inter",
+    size: 12.0,
+    weight: 400,
+};
+
+/// Card width in pixels.
+const CARD_WIDTH: u32 = 400;
+
+/// Space available for the display name, between the avatar and the right padding.
+const NAME_MAX_WIDTH: f32 = 308.0;
+
+/// Space available for each bio or description line and the language row.
+const TEXT_MAX_WIDTH: f32 = 368.0;
+
+/// Maximum number of bio or description lines before clamping with an ellipsis.
+const TEXT_MAX_LINES: usize = 3;
+
+/// Baseline of the login beneath the display name.
+const LOGIN_BASELINE: f64 = 56.0;
+
+/// Baseline of the first bio or description line.
+const TEXT_BASELINE: f64 = 88.0;
+
+/// Distance between bio or description baselines (1.4em at 12px).
+const TEXT_LINE_HEIGHT: f64 = 16.8;
+
+/// Distance from the last line of text to the stats row baseline.
+const STATS_GAP: f64 = 33.2;
+
+/// Distance between the stats row and the language row baselines.
+const ROW_GAP: f64 = 24.0;
+
+/// Distance from the last row baseline to the bottom edge of the card.
+const BOTTOM_PADDING: f64 = 25.0;
+
+/// Width of a language dot and the space before its label.
+const LANGUAGE_DOT_WIDTH: f32 = 18.0;
+
+/// Space between language labels.
+const LANGUAGE_GAP: f32 = 16.0;
+
+/// A user profile card ready to be rendered.
+#[derive(Debug, Clone)]
+pub struct UserCard {
+    /// User profile shown on the card
+    pub user: User,
+    /// Presentation options
+    pub options: CardOptions,
+    /// User avatar as a data URI, see [`crate::avatar`]
+    pub avatar: Option<String>,
+}
+
+/// A language in the language row, positioned to fit the card.
+#[derive(Debug, Serialize)]
+pub struct LanguageLabel {
+    pub name: String,
+    pub color: String,
+    /// Number of repositories using the language
+    pub repositories: u32,
+    /// Horizontal offset of the dot within the row
+    pub x: f32,
+}
+
+/// Values exposed to user card templates.
+#[derive(Debug, Serialize)]
+pub struct UserCardContext<'a> {
+    pub login: Cow<'a, str>,
+    /// Avatar as a data URI, if available
+    pub avatar: Option<&'a str>,
+    /// Display name, falling back to the login
+    pub name: Cow<'a, str>,
+    /// Name truncated with an ellipsis to fit beside the avatar
+    pub display_name: String,
+    pub bio: Cow<'a, str>,
+    /// Bio wrapped to the card width, ending in an ellipsis if clamped
+    pub bio_lines: Vec<String>,
+    /// Card dimensions and the stats row position for the wrapped bio
+    pub layout: CardLayout,
+    /// Baseline of the language row
+    pub languages_y: f64,
+    /// Most used languages that fit on one row
+    pub languages: Vec<LanguageLabel>,
+    pub followers: u32,
+    pub public_repos: u32,
+    /// Theme colors, exposed as top-level variables
+    #[serde(flatten)]
+    pub colors: ThemeColors<'a>,
+}
+
+/// An organization profile card ready to be rendered.
+#[derive(Debug, Clone)]
+pub struct OrgCard {
+    /// Organization profile shown on the card
+    pub organization: Organization,
+    /// Presentation options
+    pub options: CardOptions,
+    /// Organization logo as a data URI, see [`crate::avatar`]
+    pub avatar: Option<String>,
+}
+
+/// Values exposed to organization card templates.
+#[derive(Debug, Serialize)]
+pub struct OrgCardContext<'a> {
+    pub login: Cow<'a, str>,
+    /// Logo as a data URI, if available
+    pub avatar: Option<&'a str>,
+    /// Display name, falling back to the login
+    pub name: Cow<'a, str>,
+    /// Name truncated with an ellipsis to fit beside the logo
+    pub display_name: String,
+    pub description: Cow<'a, str>,
+    /// Description wrapped to the card width, ending in an ellipsis if clamped
+    pub description_lines: Vec<String>,
+    /// Card dimensions and the stats row position for the wrapped description
+    pub layout: CardLayout,
+    /// Baseline of the language row
+    pub languages_y: f64,
+    /// Most used languages that fit on one row
+    pub languages: Vec<LanguageLabel>,
+    pub public_repos: u32,
+    /// Total stars across the organization's public repositories
+    pub stars: u64,
+    /// Whether `stars` misses repositories past the page limit, shown as `N+`
+    pub stars_truncated: bool,
+    /// Theme colors, exposed as top-level variables
+    #[serde(flatten)]
+    pub colors: ThemeColors<'a>,
+}
+
+impl UserCard {
+    /// Creates a card from a user profile and presentation options.
+    pub fn new(user: User, options: CardOptions) -> Self {
+        Self {
+            user,
+            options,
+            avatar: None,
+        }
+    }
+
+    /// Sets the avatar, as returned by [`AvatarCache::get`](crate::avatar::AvatarCache::get).
+    pub fn with_avatar(mut self, avatar: Option<String>) -> Self {
+        self.avatar = avatar;
+        self
+    }
+}
+
+impl Render for UserCard {
+    type Context<'a> = UserCardContext<'a>;
+
+    fn options(&self) -> &CardOptions {
+        &self.options
+    }
+
+    fn context(&self) -> UserCardContext<'_> {
+        let user = &self.user;
+        let theme = &self.options.theme;
+        let measurer = TextMeasurer::shared();
+
+        let login = strip_control_chars(&user.login);
+        let name = display_name_or_login(user.name.as_deref(), &login);
+        let bio = strip_control_chars(user.bio.as_deref().unwrap_or_default());
+        let bio_lines = measurer.wrap(&bio, &TEXT_FONT, TEXT_MAX_WIDTH, TEXT_MAX_LINES);
+        let languages = layout_languages(measurer, &user.top_languages);
+        let (layout, languages_y) = profile_layout(bio_lines.len(), !languages.is_empty());
+
+        UserCardContext {
+            avatar: self.avatar.as_deref(),
+            display_name: measurer.truncate(&name, &NAME_FONT, NAME_MAX_WIDTH),
+            login,
+            name,
+            bio,
+            bio_lines,
+            layout,
+            languages_y,
+            languages,
+            followers: user.followers,
+            public_repos: user.public_repos,
+            colors: ThemeColors::from(theme),
+        }
+    }
+}
+
+impl Sample for UserCard {
+    fn sample() -> Self {
+        let user = User {
+            login: "octocat".to_string(),
+            name: Some("The Octocat".to_string()),
+            bio: Some("Building <fast> things & writing about them".to_string()),
+            followers: 1234,
+            public_repos: 56,
+            top_languages: crate::github::top_languages(["Rust", "Rust", "Python", "TypeScript"]),
+            ..Default::default()
+        };
+        let options = CardOptions {
+            template: USER_TEMPLATE.to_string(),
+            ..Default::default()
+        };
+
+        Self::new(user, options).with_avatar(Some(SAMPLE_AVATAR.to_string()))
+    }
+}
+
+impl OrgCard {
+    /// Creates a card from an organization profile and presentation options.
+    pub fn new(organization: Organization, options: CardOptions) -> Self {
+        Self {
+            organization,
+            options,
+            avatar: None,
+        }
+    }
+
+    /// Sets the logo, as returned by [`AvatarCache::get`](crate::avatar::AvatarCache::get).
+    pub fn with_avatar(mut self, avatar: Option<String>) -> Self {
+        self.avatar = avatar;
+        self
+    }
+}
+
+impl Render for OrgCard {
+    type Context<'a> = OrgCardContext<'a>;
+
+    fn options(&self) -> &CardOptions {
+        &self.options
+    }
+
+    fn context(&self) -> OrgCardContext<'_> {
+        let organization = &self.organization;
+        let theme = &self.options.theme;
+        let measurer = TextMeasurer::shared();
+
+        let login = strip_control_chars(&organization.login);
+        let name = display_name_or_login(organization.name.as_deref(), &login);
+        let description =
+            strip_control_chars(organization.description.as_deref().unwrap_or_default());
+        let description_lines =
+            measurer.wrap(&description, &TEXT_FONT, TEXT_MAX_WIDTH, TEXT_MAX_LINES);
+        let languages = layout_languages(measurer, &organization.top_languages);
+        let (layout, languages_y) = profile_layout(description_lines.len(), !languages.is_empty());
+
+        OrgCardContext {
+            avatar: self.avatar.as_deref(),
+            display_name: measurer.truncate(&name, &NAME_FONT, NAME_MAX_WIDTH),
+            login,
+            name,
+            description,
+            description_lines,
+            layout,
+            languages_y,
+            languages,
+            public_repos: organization.public_repos,
+            stars: organization.stars,
+            stars_truncated: organization.truncated,
+            colors: ThemeColors::from(theme),
+        }
+    }
+}
+
+impl Sample for OrgCard {
+    fn sample() -> Self {
+        let organization = Organization {
+            login: "github".to_string(),
+            name: Some("GitHub".to_string()),
+            description: Some("How people build <software> & ship it".to_string()),
+            public_repos: 512,
+            stars: 123_456,
+            top_languages: crate::github::top_languages(["Go", "Ruby", "Ruby", "TypeScript"]),
+            ..Default::default()
+        };
+        let options = CardOptions {
+            template: ORG_TEMPLATE.to_string(),
+            ..Default::default()
+        };
+
+        Self::new(organization, options).with_avatar(Some(SAMPLE_AVATAR.to_string()))
+    }
+}
+
+/// Returns the trimmed display name, or the login if the name is missing or blank.
+fn display_name_or_login<'a>(name: Option<&'a str>, login: &Cow<'a, str>) -> Cow<'a, str> {
+    match name.map(str::trim) {
+        Some(name) if !name.is_empty() => strip_control_chars(name),
+        _ => login.clone(),
+    }
+}
+
+/// Computes the card size and the language row baseline for a profile card.
+///
+/// # Arguments
+/// * `text_lines` - Number of wrapped bio or description lines
+/// * `has_languages` - Whether the language row is shown
+///
+/// # Returns
+/// The card layout and the baseline of the language row
+fn profile_layout(text_lines: usize, has_languages: bool) -> (CardLayout, f64) {
+    let last_baseline = match text_lines {
+        0 => LOGIN_BASELINE,
+        n => TEXT_BASELINE + (n - 1) as f64 * TEXT_LINE_HEIGHT,
+    };
+    let stats_y = round_coordinate(last_baseline + STATS_GAP);
+    let languages_y = stats_y + ROW_GAP;
+    let last_row = if has_languages { languages_y } else { stats_y };
+
+    let layout = CardLayout {
+        kind: Layout::Card,
+        width: CARD_WIDTH,
+        height: (last_row + BOTTOM_PADDING).ceil() as u32,
+        description_y: TEXT_BASELINE,
+        stats_y,
+    };
+    (layout, languages_y)
+}
+
+/// Builds the language labels that fit on one row, see [`layout_row`].
+fn layout_languages(measurer: &TextMeasurer, languages: &[LanguageCount]) -> Vec<LanguageLabel> {
+    layout_row(
+        languages
+            .iter()
+            .map(|language| (strip_control_chars(&language.name), language.repositories)),
+        TEXT_MAX_WIDTH,
+        LANGUAGE_GAP,
+        |(name, _)| LANGUAGE_DOT_WIDTH + measurer.width(name, &TEXT_FONT),
+    )
+    .into_iter()
+    .map(|((name, repositories), x, _)| LanguageLabel {
+        color: crate::colors::get_color(&name)
+            .unwrap_or_else(|| FALLBACK_LANGUAGE_COLOR.to_string()),
+        name: name.into_owned(),
+        repositories,
+        x,
+    })
+    .collect()
+}
@@ -0,0 +1,351 @@
+//! Latest release cards.
+//!
+//! A [`ReleaseCard`] advertises the current version of a repository: the tag
+//! name, release title, publish date, number of assets and total downloads of
+//! its latest release. Repositories without releases get a card saying so,
+//! which the server returns with a 404 status. Release cards render through the
+//! same template engine as repository cards, using templates that accept
+//! [`ReleaseCardContext`] (see [`TemplateKind`](crate::template::TemplateKind)).
+//!
+//! # Example
+//!
+//! ```rust
+//! use glim::card::{CardOptions, Render};
+//! use glim::github::{Release, Repository};
+//! use glim::release::ReleaseCard;
+//! use glim::template::RELEASE_TEMPLATE;
+//!
+//! let repository = Repository {
+//!     name: "glim".to_string(),
+//!     ..Default::default()
+//! };
+//! let release = Release {
+//!     tag_name: "v1.2.0".to_string(),
+//!     name: Some("Faster rendering".to_string()),
+//!     ..Default::default()
+//! };
+//!
+//! let options = CardOptions {
+//!     template: RELEASE_TEMPLATE.to_string(),
+//!     ..Default::default()
+//! };
+//!
+//! let svg = ReleaseCard::new(repository, Some(release), options)
+//!     .render()
+//!     .unwrap();
+//! assert!(svg.contains("v1.2.0"));
+//! ```
+
+use crate::avatar::SAMPLE_AVATAR;
+use crate::card::{layout_row, CardLayout, CardOptions, Layout, Render, ThemeColors};
+use crate::github::{Owner, Release, ReleaseAsset, Repository};
+use crate::template::{Sample, RELEASE_TEMPLATE};
+use crate::text::{strip_control_chars, FontStyle, TextMeasurer};
+use chrono::{TimeZone, Utc};
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// Font of the repository name, matching the `.name` class in `release.svg`.
+const NAME_FONT: FontStyle = FontStyle {
+    family: "Inter",
+    size: 16.0,
+    weight: 700,
+};
+
+/// Font of the tag name, matching the `.tag` class in `release.svg`.
+const TAG_FONT: FontStyle = FontStyle {
+    family: "Inter",
+    size: 20.0,
+    weight: 700,
+};
+
+/// Font of the release title and stats, matching `release.svg`.
+const TEXT_FONT: FontStyle = FontStyle {
+    family: "Inter",
+    size: 12.0,
+    weight: 400,
+};
+
+/// Card width in pixels.
+const CARD_WIDTH: u32 = 400;
+
+/// Height of the card for repositories without releases, fitting the avatar.
+const EMPTY_CARD_HEIGHT: u32 = 80;
+
+/// Space available for the repository name, between the avatar and the right padding.
+const NAME_MAX_WIDTH: f32 = 308.0;
+
+/// Space available for the tag name, after the tag icon.
+const TAG_MAX_WIDTH: f32 = 344.0;
+
+/// Space available for the release title and the stats row.
+const TEXT_MAX_WIDTH: f32 = 368.0;
+
+/// Baseline of the release title beneath the tag name.
+const TITLE_BASELINE: f64 = 122.0;
+
+/// Distance from the tag name or title baseline to the stats row baseline.
+const STATS_GAP: f64 = 28.0;
+
+/// Baseline of the tag name.
+const TAG_BASELINE: f64 = 100.0;
+
+/// Distance from the stats row baseline to the bottom edge of the card.
+const BOTTOM_PADDING: f64 = 25.0;
+
+/// Width of a stat icon and the space before its label.
+const STAT_ICON_WIDTH: f32 = 20.0;
+
+/// Space between stats.
+const STAT_GAP: f32 = 16.0;
+
+/// A latest release card ready to be rendered.
+#[derive(Debug, Clone)]
+pub struct ReleaseCard {
+    /// Repository the release belongs to
+    pub repository: Repository,
+    /// Latest release, or None if the repository has no releases
+    pub release: Option<Release>,
+    /// Presentation options
+    pub options: CardOptions,
+    /// Owner avatar as a data URI, see [`crate::avatar`]
+    pub avatar: Option<String>,
+}
+
+/// A stat in the stats row, positioned to fit the card.
+#[derive(Debug, Serialize)]
+pub struct ReleaseStat {
+    /// Name of the stat (`published`, `assets` or `downloads`), used to pick its icon
+    pub kind: &'static str,
+    /// Text shown beside the icon, e.g. `3 assets`
+    pub label: String,
+    /// Horizontal offset of the icon within the row
+    pub x: f32,
+}
+
+/// Values exposed to release card templates.
+#[derive(Debug, Serialize)]
+pub struct ReleaseCardContext<'a> {
+    /// Login of the repository owner
+    pub owner: Cow<'a, str>,
+    /// Repository name
+    pub name: Cow<'a, str>,
+    /// `owner/name`, truncated with an ellipsis to fit beside the avatar
+    pub display_name: String,
+    /// Avatar as a data URI, if available
+    pub avatar: Option<&'a str>,
+    /// Whether the repository has a release; the other release fields are empty if not
+    pub has_release: bool,
+    pub tag: Cow<'a, str>,
+    /// Tag name truncated with an ellipsis to fit the card
+    pub display_tag: String,
+    /// Release title, empty if unset or the same as the tag name
+    pub title: Cow<'a, str>,
+    /// Title truncated with an ellipsis to fit the card
+    pub display_title: String,
+    /// Publish date, e.g. `Jan 5, 2025`
+    pub published: String,
+    /// Number of assets attached to the release
+    pub assets: usize,
+    /// Total downloads across all assets
+    pub downloads: u64,
+    /// Publish date, asset count and downloads, positioned left to right
+    pub stats: Vec<ReleaseStat>,
+    /// Card dimensions, with the title at `description_y` and the stats row at `stats_y`
+    pub layout: CardLayout,
+    /// Theme colors, exposed as top-level variables
+    #[serde(flatten)]
+    pub colors: ThemeColors<'a>,
+}
+
+impl ReleaseCard {
+    /// Creates a card from a repository, its latest release and presentation options.
+    pub fn new(repository: Repository, release: Option<Release>, options: CardOptions) -> Self {
+        Self {
+            repository,
+            release,
+            options,
+            avatar: None,
+        }
+    }
+
+    /// Sets the owner avatar, as returned by [`AvatarCache::get`](crate::avatar::AvatarCache::get).
+    pub fn with_avatar(mut self, avatar: Option<String>) -> Self {
+        self.avatar = avatar;
+        self
+    }
+}
+
+impl Render for ReleaseCard {
+    type Context<'a> = ReleaseCardContext<'a>;
+
+    fn options(&self) -> &CardOptions {
+        &self.options
+    }
+
+    fn context(&self) -> ReleaseCardContext<'_> {
+        let repository = &self.repository;
+        let theme = &self.options.theme;
+        let measurer = TextMeasurer::shared();
+
+        let owner = strip_control_chars(&repository.owner.login);
+        let name = strip_control_chars(&repository.name);
+        let full_name = if owner.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}/{}", owner, name)
+        };
+
+        let tag = self
+            .release
+            .as_ref()
+            .map(|release| strip_control_chars(&release.tag_name))
+            .unwrap_or_default();
+        let title = match self
+            .release
+            .as_ref()
+            .and_then(|release| release.name.as_deref())
+        {
+            Some(title) if !title.trim().is_empty() && title.trim() != tag => {
+                strip_control_chars(title.trim())
+            }
+            _ => Cow::Borrowed(""),
+        };
+        let published = self
+            .release
+            .as_ref()
+            .and_then(|release| release.published_at)
+            .map(|published_at| published_at.format("%b %-d, %Y").to_string())
+            .unwrap_or_default();
+        let assets = self
+            .release
+            .as_ref()
+            .map_or(0, |release| release.assets.len());
+        let downloads = self.release.as_ref().map_or(0, Release::download_count);
+
+        let stats = match self.release {
+            Some(_) => layout_stats(measurer, &published, assets, downloads),
+            None => Vec::new(),
+        };
+        let layout = release_layout(self.release.is_some(), !title.is_empty());
+
+        ReleaseCardContext {
+            display_name: measurer.truncate(&full_name, &NAME_FONT, NAME_MAX_WIDTH),
+            owner,
+            name,
+            avatar: self.avatar.as_deref(),
+            has_release: self.release.is_some(),
+            display_tag: measurer.truncate(&tag, &TAG_FONT, TAG_MAX_WIDTH),
+            tag,
+            display_title: measurer.truncate(&title, &TEXT_FONT, TEXT_MAX_WIDTH),
+            title,
+            published,
+            assets,
+            downloads,
+            stats,
+            layout,
+            colors: ThemeColors::from(theme),
+        }
+    }
+}
+
+impl Sample for ReleaseCard {
+    fn sample() -> Self {
+        let repository = Repository {
+            name: "glim".to_string(),
+            owner: Owner {
+                login: "Xevion".to_string(),
+                avatar_url: String::new(),
+            },
+            ..Default::default()
+        };
+        let release = Release {
+            tag_name: "v1.2.0".to_string(),
+            name: Some("Faster <rendering> & caching".to_string()),
+            published_at: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).single(),
+            assets: vec![
+                ReleaseAsset {
+                    name: "glim-x86_64-linux.tar.gz".to_string(),
+                    download_count: 1234,
+                },
+                ReleaseAsset {
+                    name: "glim-aarch64-darwin.tar.gz".to_string(),
+                    download_count: 567,
+                },
+            ],
+        };
+        let options = CardOptions {
+            template: RELEASE_TEMPLATE.to_string(),
+            ..Default::default()
+        };
+
+        Self::new(repository, Some(release), options).with_avatar(Some(SAMPLE_AVATAR.to_string()))
+    }
+}
+
+/// Computes the card size and the title and stats row baselines.
+///
+/// # Arguments
+/// * `has_release` - Whether the repository has a release to show
+/// * `has_title` - Whether the release title is shown beneath the tag name
+///
+/// # Returns
+/// The card layout; repositories without releases get a card that only fits the header
+fn release_layout(has_release: bool, has_title: bool) -> CardLayout {
+    if !has_release {
+        return CardLayout {
+            kind: Layout::Card,
+            width: CARD_WIDTH,
+            height: EMPTY_CARD_HEIGHT,
+            description_y: TITLE_BASELINE,
+            stats_y: TITLE_BASELINE,
+        };
+    }
+
+    let last_baseline = if has_title {
+        TITLE_BASELINE
+    } else {
+        TAG_BASELINE
+    };
+    let stats_y = last_baseline + STATS_GAP;
+
+    CardLayout {
+        kind: Layout::Card,
+        width: CARD_WIDTH,
+        height: (stats_y + BOTTOM_PADDING).ceil() as u32,
+        description_y: TITLE_BASELINE,
+        stats_y,
+    }
+}
+
+/// Builds the publish date, asset count and downloads that fit on one row, see [`layout_row`].
+fn layout_stats(
+    measurer: &TextMeasurer,
+    published: &str,
+    assets: usize,
+    downloads: u64,
+) -> Vec<ReleaseStat> {
+    let mut candidates = Vec::new();
+    if !published.is_empty() {
+        candidates.push(("published", published.to_string()));
+    }
+    candidates.push(("assets", plural(assets as u64, "asset", "assets")));
+    candidates.push(("downloads", plural(downloads, "download", "downloads")));
+
+    layout_row(candidates, TEXT_MAX_WIDTH, STAT_GAP, |(_, label)| {
+        STAT_ICON_WIDTH + measurer.width(label, &TEXT_FONT)
+    })
+    .into_iter()
+    .map(|((kind, label), x, _)| ReleaseStat { kind, label, x })
+    .collect()
+}
+
+/// Formats an abbreviated count with the singular or plural noun, e.g. `1.2k downloads`.
+fn plural(count: u64, one: &str, many: &str) -> String {
+    let noun = if count == 1 { one } else { many };
+    format!(
+        "{} {}",
+        crate::image::format_count(&count.to_string()),
+        noun
+    )
+}
@@ -15,9 +15,11 @@ use socket2::{Domain, Socket, Type};
 use std::{
     collections::HashSet,
     env,
+    hash::{DefaultHasher, Hash, Hasher},
     io::Cursor,
     net::{IpAddr, Ipv4Addr},
     num::ParseIntError,
+    sync::Arc,
 };
 use std::{
     net::AddrParseError,
@@ -34,12 +36,30 @@ use tokio::time::timeout;
 use tracing::{info, instrument};
 
 use crate::{
-    encode::Encoder,
+    avatar, cache,
+    card::{
+        Card, CardOptions, Layout, Render, Section, ANIMATION_DURATION, ANIMATION_FRAMES,
+        DEFAULT_CONTRIBUTORS, MAX_CONTRIBUTORS, MAX_LEGEND,
+    },
+    encode::{Animation, Compression, EncodeOptions, Encoder, EncodingTiming},
+    errors::{ImageError, TemplateError, ThemeError},
     github,
     image::{self, ImageFormat},
+    issue::IssueCard,
+    profile::{OrgCard, UserCard},
     ratelimit::{RateLimitConfig, RateLimitResult, RateLimiter},
+    release::ReleaseCard,
+    template::{TemplateEngine, TemplateKind},
+    theme::{ColorOverrides, Theme, ThemeRegistry},
 };
 
+/// Content Security Policy for SVG cards.
+///
+/// SVG responses are documents when opened directly, so block scripts and
+/// external resources; cards only need inline styles and embedded images.
+const SVG_CONTENT_SECURITY_POLICY: &str =
+    "default-src 'none'; style-src 'unsafe-inline'; img-src data:";
+
 /// Error response structure for JSON error responses
 #[derive(Debug, Serialize)]
 struct ErrorResponse {
@@ -84,49 +104,75 @@ struct GitHubApiHealth {
     last_error: Option<String>,
 }
 
-/// SVG input data for repository cards
-#[derive(Debug, Clone)]
-struct SvgInputData {
-    name: String,
-    description: String,
-    language: String,
-    stars: String,
-    forks: String,
-}
-
-impl SvgInputData {
-    fn new(
-        name: String,
-        description: String,
-        language: String,
-        stars: String,
-        forks: String,
-    ) -> Self {
-        Self {
-            name,
-            description,
-            language,
-            stars,
-            forks,
-        }
-    }
-}
-
 /// Query parameters for image generation
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct ImageQuery {
     #[serde(rename = "scale")]
     pub scale: Option<String>,
     #[serde(rename = "s")]
     pub s: Option<String>,
+    /// Encoder quality, for formats with a quality setting
+    pub quality: Option<String>,
+    /// Whether to encode losslessly, for formats that support both
+    pub lossless: Option<String>,
+    /// Deflate effort for PNG output (fast, default or best)
+    pub compression: Option<String>,
+    /// Whether to pick the smallest PNG color type, on unless set to false
+    pub optimize: Option<String>,
+}
+
+/// Query parameters controlling card presentation
+#[derive(Debug, Default, Deserialize)]
+pub struct CardQuery {
+    /// Name of the color theme
+    pub theme: Option<String>,
+    /// Repository name color override (hex)
+    pub title_color: Option<String>,
+    /// Description and stats text color override (hex)
+    pub text_color: Option<String>,
+    /// Octicon fill color override (hex)
+    pub icon_color: Option<String>,
+    /// Card background color override (hex)
+    pub bg_color: Option<String>,
+    /// Card border color override (hex)
+    pub border_color: Option<String>,
+    /// Name of the card template
+    pub template: Option<String>,
+    /// Card layout (card, social, badge or icon)
+    pub layout: Option<String>,
+    /// Comma-separated sections to add to the defaults
+    pub show: Option<String>,
+    /// Comma-separated sections to remove, taking precedence over `show`
+    pub hide: Option<String>,
+    /// Number of languages listed beneath the language bar
+    pub legend: Option<String>,
+    /// Number of avatars in the contributors row
+    pub max: Option<String>,
+    /// Whether to animate the card (PNG, GIF and WebP only)
+    pub animate: Option<String>,
 }
 
-/// Application state containing the rate limiter, startup time, and configuration
+impl CardQuery {
+    /// Extracts the per-request color overrides.
+    pub fn color_overrides(&self) -> ColorOverrides {
+        ColorOverrides {
+            title_color: self.title_color.clone(),
+            text_color: self.text_color.clone(),
+            icon_color: self.icon_color.clone(),
+            bg_color: self.bg_color.clone(),
+            border_color: self.border_color.clone(),
+        }
+    }
+}
+
+/// Application state containing the rate limiter, startup time, configuration, themes and templates
 #[derive(Clone, Debug)]
 struct AppState {
     rate_limiter: RateLimiter,
     startup_time: Instant,
     config: crate::config::Config,
+    themes: Arc<ThemeRegistry>,
+    templates: Arc<TemplateEngine>,
 }
 
 /// Middleware to add Server header to all responses
@@ -211,16 +257,46 @@ pub async fn start_server(
         }
     }
 
+    let themes = match ThemeRegistry::from_config(config.card_config()) {
+        Ok(themes) => Arc::new(themes),
+        Err(e) => return Some(Err(anyhow::Error::new(e))),
+    };
+
+    // Load and validate templates up front so a broken template fails at startup
+    let templates = match TemplateEngine::from_config(config.card_config()) {
+        Ok(templates) => Arc::new(templates),
+        Err(e) => return Some(Err(anyhow::Error::new(e))),
+    };
+    info!(
+        "Loaded card templates: {}",
+        templates.names().collect::<Vec<_>>().join(", ")
+    );
+
+    if let Some(cache_config) = config.cache.clone() {
+        let disk_path = cache_config.disk_path.clone();
+        if let Err(e) = cache::init(cache_config).await {
+            return Some(Err(anyhow::Error::new(e)));
+        }
+        info!("Image cache enabled at {}", disk_path);
+    }
+
     let rate_limiter = RateLimiter::new(RateLimitConfig::default());
     let app_state = AppState {
         rate_limiter,
         startup_time: Instant::now(),
         config,
+        themes,
+        templates,
     };
 
     let app = Router::new()
         .route("/", get(index_handler))
+        .route("/users/{login}", get(user_handler))
+        .route("/orgs/{org}", get(org_handler))
         .route("/{owner}/{repo}", get(handler))
+        .route("/{owner}/{repo}/releases/{release}", get(release_handler))
+        .route("/{owner}/{repo}/issues/{number}", get(issue_handler))
+        .route("/{owner}/{repo}/pull/{number}", get(issue_handler))
         .route("/status", get(status_handler))
         .route("/health", get(health_handler))
         .layer(middleware::from_fn(add_server_header))
@@ -546,6 +622,21 @@ async fn health_handler(
     (status_code, Json(health_response)).into_response()
 }
 
+/// Error returned by card handlers: a status code with a JSON body.
+type ApiError = (StatusCode, Json<ErrorResponse>);
+
+/// Builds an API error response.
+fn api_error(status: StatusCode, error: &str, message: String) -> ApiError {
+    (
+        status,
+        Json(ErrorResponse {
+            error: error.to_string(),
+            message,
+            status: status.as_u16(),
+        }),
+    )
+}
+
 /// Handles HTTP requests for repository cards with rate limiting.
 ///
 /// Endpoint: GET /:owner/:repo or GET /:owner/:repo.:extension
@@ -553,37 +644,11 @@ async fn health_handler(
 async fn handler(
     Path((owner, repo_name)): Path<(String, String)>,
     Query(query): Query<ImageQuery>,
+    Query(card_query): Query<CardQuery>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     State(state): State<AppState>,
-) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
-    let client_ip = addr.ip();
-
-    // Check rate limit
-    match state.rate_limiter.check_rate_limit(client_ip).await {
-        RateLimitResult::Allowed => {
-            // Continue with request processing
-        }
-        RateLimitResult::GlobalLimitExceeded => {
-            return Err((
-                StatusCode::TOO_MANY_REQUESTS,
-                Json(ErrorResponse {
-                    error: "rate_limit_exceeded".to_string(),
-                    message: "Global rate limit exceeded".to_string(),
-                    status: 429,
-                }),
-            ));
-        }
-        RateLimitResult::IpLimitExceeded => {
-            return Err((
-                StatusCode::TOO_MANY_REQUESTS,
-                Json(ErrorResponse {
-                    error: "rate_limit_exceeded".to_string(),
-                    message: "IP rate limit exceeded".to_string(),
-                    status: 429,
-                }),
-            ));
-        }
-    }
+) -> Result<Response, ApiError> {
+    check_rate_limit(&state, addr.ip()).await?;
 
     // Parse format from repo_name (e.g., "repo.png" -> format PNG, "repo" -> format PNG)
     let (actual_repo_name, format) = {
@@ -593,26 +658,97 @@ async fn handler(
 
     let repo_path = format!("{}/{}", owner, actual_repo_name);
 
+    // Resolve the layout, theme and template before fetching so invalid styling doesn't cost an API call
+    let layout = match card_query.layout.as_deref() {
+        None => Layout::for_format(format),
+        Some(name) => Layout::parse(name).ok_or_else(|| {
+            api_error(
+                StatusCode::BAD_REQUEST,
+                "invalid_layout",
+                format!(
+                    "Unknown layout: {} (available: {})",
+                    name,
+                    Layout::ALL.map(|layout| layout.name()).join(", ")
+                ),
+            )
+        })?,
+    };
+    let theme = resolve_theme(&state, &card_query)?;
+    let template = resolve_template(
+        &state,
+        TemplateKind::Repository,
+        card_query.template.as_deref().or(layout.template()),
+    )?;
+    let options = CardOptions {
+        theme,
+        template,
+        layout,
+        ..resolve_content(&card_query)?
+    };
+    let animate = parse_flag(card_query.animate.as_deref(), "invalid_animate", "animate")?;
+    if animate && !format.supports_animation() {
+        return Err(api_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_animate",
+            format!(
+                "Animated cards are available as PNG, GIF or WebP, not {}",
+                format.extension()
+            ),
+        ));
+    }
+    let encode_options = resolve_encode_options(&query, layout, format)?;
+
     // Start GitHub API timing
     let github_start = Instant::now();
     let repo = github::GITHUB_CLIENT
         .get_repository_info(&repo_path)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get repository info: {}", e);
-            let status_code = match &e {
-                crate::errors::GlimError::GitHub(github_error) => github_error.clone().into(),
-                _ => StatusCode::INTERNAL_SERVER_ERROR,
-            };
-            (
-                status_code,
-                Json(ErrorResponse {
-                    error: "repository_error".to_string(),
-                    message: format!("Failed to get repository info: {}", e),
-                    status: status_code.as_u16(),
-                }),
-            )
-        })?;
+        .map_err(|e| github_error("repository_error", "Failed to get repository info", e))?;
+    // Everything else only depends on the repository, so it is fetched concurrently
+    let languages = async {
+        if !options.needs_languages() {
+            return Ok(Vec::new());
+        }
+        github::GITHUB_CLIENT
+            .get_repository_languages(&repo_path)
+            .await
+            .map_err(|e| github_error("repository_error", "Failed to get repository languages", e))
+    };
+    let star_history = async {
+        if !options.needs_star_history() {
+            return Ok(Vec::new());
+        }
+        github::GITHUB_CLIENT
+            .get_star_history(&repo_path, repo.stargazers_count)
+            .await
+            .map_err(|e| github_error("repository_error", "Failed to get star history", e))
+    };
+    // None while GitHub computes the statistics; the card is rendered without the graph
+    let activity = async {
+        if !options.needs_activity() {
+            return Ok(Some(Vec::new()));
+        }
+        github::GITHUB_CLIENT
+            .get_commit_activity(&repo_path)
+            .await
+            .map_err(|e| github_error("repository_error", "Failed to get commit activity", e))
+    };
+    let contributors = async {
+        if !options.needs_contributors() {
+            return Ok(Vec::new());
+        }
+        let mut contributors = github::GITHUB_CLIENT
+            .get_contributors(&repo_path)
+            .await
+            .map_err(|e| github_error("repository_error", "Failed to get contributors", e))?;
+        contributors.truncate(options.contributors);
+        avatar::AVATARS.fill_contributors(&mut contributors).await;
+        Ok(contributors)
+    };
+    let avatar = async { Ok(avatar::AVATARS.get(&repo.owner.avatar_url).await) };
+    let (languages, star_history, activity, contributors, avatar) =
+        tokio::try_join!(languages, star_history, activity, contributors, avatar)?;
+    let activity_pending = activity.is_none();
     let github_api_duration = github_start.elapsed();
 
     tracing::debug!(
@@ -625,18 +761,25 @@ async fn handler(
     // Start overall image generation timing
     let total_start = Instant::now();
 
-    // Create SVG input data
-    let svg_data = SvgInputData::new(
-        repo.name,
-        repo.description.unwrap_or_default(),
-        repo.language.unwrap_or_default(),
-        repo.stargazers_count.to_string(),
-        repo.forks_count.to_string(),
-    );
-
-    // Format the SVG template with timing
+    // Render the card SVG with timing
     let svg_start = Instant::now();
-    let formatted_svg = format_svg_template(&svg_data);
+    let card = Card::new(repo, options)
+        .with_avatar(avatar)
+        .with_languages(languages)
+        .with_star_history(star_history)
+        .with_activity(activity.unwrap_or_default())
+        .with_contributors(contributors);
+    let formatted_svg = card.render_with(&state.templates).map_err(template_error)?;
+    let animation = if animate {
+        Some(Animation {
+            frames: card
+                .render_frames_with(&state.templates, ANIMATION_FRAMES)
+                .map_err(template_error)?,
+            duration: ANIMATION_DURATION,
+        })
+    } else {
+        None
+    };
     let svg_template_duration = svg_start.elapsed();
 
     tracing::debug!(
@@ -646,32 +789,31 @@ async fn handler(
         "SVG template rendered"
     );
 
-    // Parse scale parameter
-    let scale = parse_scale_parameter(&query);
-
-    // Encode the image with timing
-    let mut buffer = Cursor::new(Vec::new());
-    let encoder = crate::encode::create_encoder(format);
-
-    let encoding_timing = encoder
-        .encode(&formatted_svg, &mut buffer, scale)
-        .map_err(|e| {
-            tracing::error!("Failed to generate image: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "image_generation_error".to_string(),
-                    message: format!("Failed to generate image: {}", e),
-                    status: 500,
-                }),
-            )
-        })?;
+    let meaning = cache::RepositoryCard {
+        owner: owner.clone(),
+        repo: actual_repo_name.clone(),
+        theme: card.options.theme.key(),
+        variant: format!(
+            "{}/{}:{}@{}{}{}{}{}",
+            layout.name(),
+            card.options.template,
+            format.extension(),
+            encode_options.scale.unwrap_or(1.0),
+            encode_options.quality_key(format),
+            card.options.content_key(),
+            if animate { "~animated" } else { "" },
+            // Keep cards missing the activity graph apart from complete ones
+            if activity_pending { "~pending" } else { "" }
+        ),
+    };
+    let (image_data, encoding_timing) =
+        encode_card_cached(meaning, formatted_svg, animation, format, encode_options).await?;
 
     tracing::debug!(
         owner = &owner,
         repo = &actual_repo_name,
         format = ?format,
-        scale = ?scale,
+        scale = ?encode_options.scale,
         rasterization_duration = ?encoding_timing.rasterization,
         encoding_duration = ?encoding_timing.encoding,
         "Image encoding completed"
@@ -687,13 +829,772 @@ async fn handler(
     timing.total = total_duration;
 
     // Log detailed timing breakdown
-    timing.log_timing_breakdown(&owner, &actual_repo_name, &format, scale);
+    timing.log_timing_breakdown(&owner, &actual_repo_name, &format, encode_options.scale);
+
+    let mut response = card_response(format, image_data);
+    if activity_pending {
+        // The graph is on its way; don't let proxies hold on to the card without it
+        response.headers_mut().insert(
+            axum::http::header::CACHE_CONTROL,
+            axum::http::HeaderValue::from_static("no-cache"),
+        );
+    }
+    Ok(response)
+}
+
+/// Handles HTTP requests for user profile cards with rate limiting.
+///
+/// Endpoint: GET /users/:login or GET /users/:login.:extension
+/// Returns: Image in the requested format (PNG by default)
+async fn user_handler(
+    Path(login): Path<String>,
+    Query(query): Query<ImageQuery>,
+    Query(card_query): Query<CardQuery>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    State(state): State<AppState>,
+) -> Result<Response, ApiError> {
+    check_rate_limit(&state, addr.ip()).await?;
+
+    let (login, format) = {
+        let (login, format) = parse_repo_name_and_format(&login);
+        (login, format.unwrap_or(ImageFormat::Png))
+    };
+
+    // Resolve the theme and template before fetching so invalid styling doesn't cost an API call
+    let theme = resolve_theme(&state, &card_query)?;
+    let template = resolve_template(&state, TemplateKind::User, card_query.template.as_deref())?;
+    let options = resolve_encode_options(&query, Layout::Card, format)?;
+
+    let github_start = Instant::now();
+    let user = github::GITHUB_CLIENT
+        .get_user_info(&login)
+        .await
+        .map_err(|e| github_error("user_error", "Failed to get user info", e))?;
+    let avatar = avatar::AVATARS.get(&user.avatar_url).await;
+    let github_api_duration = github_start.elapsed();
+
+    let total_start = Instant::now();
+    let svg_start = Instant::now();
+    let card = UserCard::new(
+        user,
+        CardOptions {
+            theme,
+            template,
+            layout: Layout::Card,
+            ..Default::default()
+        },
+    )
+    .with_avatar(avatar);
+    let formatted_svg = card.render_with(&state.templates).map_err(template_error)?;
+    let svg_template_duration = svg_start.elapsed();
+
+    // Users share the image cache with repositories; no repository has an empty name
+    let meaning = cache::RepositoryCard {
+        owner: login.clone(),
+        repo: String::new(),
+        theme: card.options.theme.key(),
+        variant: format!(
+            "user/{}:{}@{}{}",
+            card.options.template,
+            format.extension(),
+            options.scale.unwrap_or(1.0),
+            options.quality_key(format)
+        ),
+    };
+    let (image_data, encoding_timing) =
+        encode_card_cached(meaning, formatted_svg, None, format, options).await?;
+
+    let mut timing = ImageGenerationTiming::new();
+    timing.github_api = github_api_duration;
+    timing.svg_template = svg_template_duration;
+    timing.rasterization = encoding_timing.rasterization;
+    timing.encoding = encoding_timing.encoding;
+    timing.total = total_start.elapsed();
+    timing.log_timing_breakdown(&login, "", &format, options.scale);
 
-    Ok((
+    Ok(card_response(format, image_data))
+}
+
+/// Handles HTTP requests for organization profile cards with rate limiting.
+///
+/// Endpoint: GET /orgs/:org or GET /orgs/:org.:extension
+/// Returns: Image in the requested format (PNG by default)
+async fn org_handler(
+    Path(org): Path<String>,
+    Query(query): Query<ImageQuery>,
+    Query(card_query): Query<CardQuery>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    State(state): State<AppState>,
+) -> Result<Response, ApiError> {
+    check_rate_limit(&state, addr.ip()).await?;
+
+    let (org, format) = {
+        let (org, format) = parse_repo_name_and_format(&org);
+        (org, format.unwrap_or(ImageFormat::Png))
+    };
+
+    // Resolve the theme and template before fetching so invalid styling doesn't cost an API call
+    let theme = resolve_theme(&state, &card_query)?;
+    let template = resolve_template(&state, TemplateKind::Org, card_query.template.as_deref())?;
+    let options = resolve_encode_options(&query, Layout::Card, format)?;
+
+    let github_start = Instant::now();
+    let organization = github::GITHUB_CLIENT
+        .get_org_info(&org)
+        .await
+        .map_err(|e| github_error("org_error", "Failed to get organization info", e))?;
+    let avatar = avatar::AVATARS.get(&organization.avatar_url).await;
+    let github_api_duration = github_start.elapsed();
+
+    let total_start = Instant::now();
+    let svg_start = Instant::now();
+    let card = OrgCard::new(
+        organization,
+        CardOptions {
+            theme,
+            template,
+            layout: Layout::Card,
+            ..Default::default()
+        },
+    )
+    .with_avatar(avatar);
+    let formatted_svg = card.render_with(&state.templates).map_err(template_error)?;
+    let svg_template_duration = svg_start.elapsed();
+
+    // Organizations share the image cache with repositories; no repository has an empty name
+    let meaning = cache::RepositoryCard {
+        owner: org.clone(),
+        repo: String::new(),
+        theme: card.options.theme.key(),
+        variant: format!(
+            "org/{}:{}@{}{}",
+            card.options.template,
+            format.extension(),
+            options.scale.unwrap_or(1.0),
+            options.quality_key(format)
+        ),
+    };
+    let (image_data, encoding_timing) =
+        encode_card_cached(meaning, formatted_svg, None, format, options).await?;
+
+    let mut timing = ImageGenerationTiming::new();
+    timing.github_api = github_api_duration;
+    timing.svg_template = svg_template_duration;
+    timing.rasterization = encoding_timing.rasterization;
+    timing.encoding = encoding_timing.encoding;
+    timing.total = total_start.elapsed();
+    timing.log_timing_breakdown(&org, "", &format, options.scale);
+
+    Ok(card_response(format, image_data))
+}
+
+/// Handles HTTP requests for latest release cards with rate limiting.
+///
+/// Endpoint: GET /:owner/:repo/releases/latest or GET /:owner/:repo/releases/latest.:extension
+/// Returns: Image in the requested format (PNG by default); repositories without
+/// releases get a "No releases yet" card with a 404 status
+async fn release_handler(
+    Path((owner, repo_name, release)): Path<(String, String, String)>,
+    Query(query): Query<ImageQuery>,
+    Query(card_query): Query<CardQuery>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    State(state): State<AppState>,
+) -> Result<Response, ApiError> {
+    check_rate_limit(&state, addr.ip()).await?;
+
+    let (release, format) = {
+        let (release, format) = parse_repo_name_and_format(&release);
+        (release, format.unwrap_or(ImageFormat::Png))
+    };
+    if release != "latest" {
+        return Err(api_error(
+            StatusCode::NOT_FOUND,
+            "release_error",
+            format!(
+                "Unknown release: {} (only the latest release is supported)",
+                release
+            ),
+        ));
+    }
+
+    let repo_path = format!("{}/{}", owner, repo_name);
+
+    // Resolve the theme and template before fetching so invalid styling doesn't cost an API call
+    let theme = resolve_theme(&state, &card_query)?;
+    let template = resolve_template(
+        &state,
+        TemplateKind::Release,
+        card_query.template.as_deref(),
+    )?;
+    let options = resolve_encode_options(&query, Layout::Card, format)?;
+
+    // The releases endpoint 404s for missing repositories too, so look up the repository first
+    let github_start = Instant::now();
+    let repo = github::GITHUB_CLIENT
+        .get_repository_info(&repo_path)
+        .await
+        .map_err(|e| github_error("repository_error", "Failed to get repository info", e))?;
+    let (latest, avatar) = tokio::join!(
+        github::GITHUB_CLIENT.get_latest_release(&repo_path),
+        avatar::AVATARS.get(&repo.owner.avatar_url)
+    );
+    let latest =
+        latest.map_err(|e| github_error("release_error", "Failed to get latest release", e))?;
+    let github_api_duration = github_start.elapsed();
+
+    let total_start = Instant::now();
+    let svg_start = Instant::now();
+    let card = ReleaseCard::new(
+        repo,
+        latest,
+        CardOptions {
+            theme,
+            template,
+            layout: Layout::Card,
+            ..Default::default()
+        },
+    )
+    .with_avatar(avatar);
+    let formatted_svg = card.render_with(&state.templates).map_err(template_error)?;
+    let svg_template_duration = svg_start.elapsed();
+
+    // Keyed by tag so a new release replaces the cached image
+    let meaning = cache::RepositoryCard {
+        owner: owner.clone(),
+        repo: repo_name.clone(),
+        theme: card.options.theme.key(),
+        variant: format!(
+            "release/{}:{}@{}{}#{}",
+            card.options.template,
+            format.extension(),
+            options.scale.unwrap_or(1.0),
+            options.quality_key(format),
+            card.release
+                .as_ref()
+                .map_or("none", |release| release.tag_name.as_str())
+        ),
+    };
+    let (image_data, encoding_timing) =
+        encode_card_cached(meaning, formatted_svg, None, format, options).await?;
+
+    let mut timing = ImageGenerationTiming::new();
+    timing.github_api = github_api_duration;
+    timing.svg_template = svg_template_duration;
+    timing.rasterization = encoding_timing.rasterization;
+    timing.encoding = encoding_timing.encoding;
+    timing.total = total_start.elapsed();
+    timing.log_timing_breakdown(&owner, &repo_name, &format, options.scale);
+
+    let mut response = card_response(format, image_data);
+    if card.release.is_none() {
+        *response.status_mut() = StatusCode::NOT_FOUND;
+    }
+    Ok(response)
+}
+
+/// Handles HTTP requests for issue and pull request cards with rate limiting.
+///
+/// Endpoint: GET /:owner/:repo/issues/:number or GET /:owner/:repo/pull/:number,
+/// optionally followed by .:extension. GitHub numbers issues and pull requests
+/// together, so either route renders whichever the number refers to.
+/// Returns: Image in the requested format (PNG by default)
+async fn issue_handler(
+    Path((owner, repo_name, number)): Path<(String, String, String)>,
+    Query(query): Query<ImageQuery>,
+    Query(card_query): Query<CardQuery>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    State(state): State<AppState>,
+) -> Result<Response, ApiError> {
+    check_rate_limit(&state, addr.ip()).await?;
+
+    let (number, format) = {
+        let (number, format) = parse_repo_name_and_format(&number);
+        (number, format.unwrap_or(ImageFormat::Png))
+    };
+    let number: u64 = number.parse().map_err(|_| {
+        api_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_number",
+            format!("Invalid issue or pull request number: {}", number),
+        )
+    })?;
+
+    let repo_path = format!("{}/{}", owner, repo_name);
+
+    // Resolve the theme and template before fetching so invalid styling doesn't cost an API call
+    let theme = resolve_theme(&state, &card_query)?;
+    let template = resolve_template(&state, TemplateKind::Issue, card_query.template.as_deref())?;
+    let options = resolve_encode_options(&query, Layout::Card, format)?;
+
+    // Look up the repository first so issues of private repositories are never shown
+    let github_start = Instant::now();
+    let repo = github::GITHUB_CLIENT
+        .get_repository_info(&repo_path)
+        .await
+        .map_err(|e| github_error("repository_error", "Failed to get repository info", e))?;
+    let issue = github::GITHUB_CLIENT
+        .get_issue(&repo_path, number)
+        .await
+        .map_err(|e| github_error("issue_error", "Failed to get issue", e))?;
+    let avatar = match &issue.user {
+        Some(user) => avatar::AVATARS.get(&user.avatar_url).await,
+        None => None,
+    };
+    let github_api_duration = github_start.elapsed();
+
+    let total_start = Instant::now();
+    let svg_start = Instant::now();
+    let card = IssueCard::new(
+        repo,
+        issue,
+        CardOptions {
+            theme,
+            template,
+            layout: Layout::Card,
+            ..Default::default()
+        },
+    )
+    .with_avatar(avatar);
+    let formatted_svg = card.render_with(&state.templates).map_err(template_error)?;
+    let svg_template_duration = svg_start.elapsed();
+
+    // Cached images have no TTL, so key them by the last change and the shown age;
+    // any edit, comment or label change (or a new day) renders a new image
+    let meaning = cache::RepositoryCard {
+        owner: owner.clone(),
+        repo: repo_name.clone(),
+        theme: card.options.theme.key(),
+        variant: format!(
+            "issue/{}:{}@{}{}#{}/{}/{}",
+            card.options.template,
+            format.extension(),
+            options.scale.unwrap_or(1.0),
+            options.quality_key(format),
+            number,
+            card.issue
+                .updated_at
+                .map(|updated_at| updated_at.timestamp())
+                .unwrap_or_default(),
+            card.opened()
+        ),
+    };
+    let (image_data, encoding_timing) =
+        encode_card_cached(meaning, formatted_svg, None, format, options).await?;
+
+    let mut timing = ImageGenerationTiming::new();
+    timing.github_api = github_api_duration;
+    timing.svg_template = svg_template_duration;
+    timing.rasterization = encoding_timing.rasterization;
+    timing.encoding = encoding_timing.encoding;
+    timing.total = total_start.elapsed();
+    timing.log_timing_breakdown(&owner, &repo_name, &format, options.scale);
+
+    Ok(card_response(format, image_data))
+}
+
+/// Checks the global and per-IP rate limits for a request.
+///
+/// # Errors
+/// Returns a 429 error if either limit is exceeded.
+async fn check_rate_limit(state: &AppState, client_ip: IpAddr) -> Result<(), ApiError> {
+    let message = match state.rate_limiter.check_rate_limit(client_ip).await {
+        RateLimitResult::Allowed => return Ok(()),
+        RateLimitResult::GlobalLimitExceeded => "Global rate limit exceeded",
+        RateLimitResult::IpLimitExceeded => "IP rate limit exceeded",
+    };
+
+    Err(api_error(
+        StatusCode::TOO_MANY_REQUESTS,
+        "rate_limit_exceeded",
+        message.to_string(),
+    ))
+}
+
+/// Builds the requested theme with any per-request color overrides.
+///
+/// # Errors
+/// Returns a 400 error if the theme is unknown or a color is invalid.
+fn resolve_theme(state: &AppState, card_query: &CardQuery) -> Result<Theme, ApiError> {
+    state
+        .themes
+        .build(card_query.theme.as_deref(), &card_query.color_overrides())
+        .map_err(|e| {
+            let (error, message) = match &e {
+                ThemeError::Unknown(_) => (
+                    "invalid_theme",
+                    format!(
+                        "{} (available: {})",
+                        e,
+                        state.themes.names().collect::<Vec<_>>().join(", ")
+                    ),
+                ),
+                _ => ("invalid_color", e.to_string()),
+            };
+            api_error(StatusCode::BAD_REQUEST, error, message)
+        })
+}
+
+/// Resolves the shown and hidden sections, legend size and number of
+/// contributors of a card request.
+///
+/// # Returns
+/// Card options with the requested content and the default theme, template and layout
+///
+/// # Errors
+/// Returns a 400 error if a section is unknown or a count is out of range.
+fn resolve_content(card_query: &CardQuery) -> Result<CardOptions, ApiError> {
+    let show = parse_sections(card_query.show.as_deref(), "invalid_show")?;
+    let hide = parse_sections(card_query.hide.as_deref(), "invalid_hide")?;
+    let legend = parse_count(
+        card_query.legend.as_deref(),
+        0,
+        MAX_LEGEND,
+        "invalid_legend",
+        "Legend size",
+    )?;
+    let contributors = parse_count(
+        card_query.max.as_deref(),
+        DEFAULT_CONTRIBUTORS,
+        MAX_CONTRIBUTORS,
+        "invalid_max",
+        "Number of contributors",
+    )?;
+
+    Ok(CardOptions {
+        show,
+        hide,
+        legend,
+        contributors,
+        ..Default::default()
+    })
+}
+
+/// Resolves the rasterization, quality and compression options for a card.
+///
+/// # Arguments
+/// * `query` - Image query parameters
+/// * `layout` - Layout of the card, which decides its scaling and margin
+/// * `format` - Requested output format
+///
+/// # Errors
+/// Returns a 400 error if the quality, lossless, compression or optimize
+/// parameter is malformed or not supported by the format.
+fn resolve_encode_options(
+    query: &ImageQuery,
+    layout: Layout,
+    format: ImageFormat,
+) -> Result<EncodeOptions, ApiError> {
+    let invalid =
+        |code: &'static str, message: String| api_error(StatusCode::BAD_REQUEST, code, message);
+
+    let quality = query
+        .quality
+        .as_deref()
+        .map(|quality| {
+            quality
+                .parse::<u8>()
+                .map_err(|_| invalid("invalid_quality", format!("Invalid quality: {}", quality)))
+        })
+        .transpose()?;
+    let lossless = query
+        .lossless
+        .as_deref()
+        .map(|lossless| parse_flag(Some(lossless), "invalid_quality", "lossless"))
+        .transpose()?;
+    let compression = query
+        .compression
+        .as_deref()
+        .map(|name| {
+            Compression::parse(name).ok_or_else(|| {
+                invalid(
+                    "invalid_compression",
+                    format!(
+                        "Unknown compression: {} (available: {})",
+                        name,
+                        Compression::ALL
+                            .map(|compression| compression.name())
+                            .join(", ")
+                    ),
+                )
+            })
+        })
+        .transpose()?;
+    let optimize = query
+        .optimize
+        .as_deref()
+        .map(|optimize| parse_flag(Some(optimize), "invalid_optimize", "optimize"))
+        .transpose()?
+        .unwrap_or(true);
+
+    let options = EncodeOptions {
+        quality,
+        lossless,
+        compression,
+        optimize,
+        ..layout.encode_options(parse_scale_parameter(query))
+    };
+    options.check(format).map_err(|e| match e {
+        crate::errors::GlimError::Image(e @ ImageError::InvalidCompression(_)) => {
+            invalid("invalid_compression", e.to_string())
+        }
+        e => invalid("invalid_quality", e.to_string()),
+    })?;
+
+    Ok(options)
+}
+
+/// Parses an optional count from a query parameter.
+///
+/// # Arguments
+/// * `value` - Raw parameter value, if given
+/// * `default` - Count used when the parameter is missing
+/// * `max` - Largest accepted count
+/// * `error` - Error code for invalid values
+/// * `name` - Name of the count in error messages
+///
+/// # Errors
+/// Returns a 400 error with the given code if the value isn't a number between 0 and `max`.
+fn parse_count(
+    value: Option<&str>,
+    default: usize,
+    max: usize,
+    error: &'static str,
+    name: &str,
+) -> Result<usize, ApiError> {
+    let Some(value) = value else {
+        return Ok(default);
+    };
+
+    value
+        .parse::<usize>()
+        .ok()
+        .filter(|count| *count <= max)
+        .ok_or_else(|| {
+            api_error(
+                StatusCode::BAD_REQUEST,
+                error,
+                format!("{} must be between 0 and {}", name, max),
+            )
+        })
+}
+
+/// Parses an optional on/off flag from a query parameter.
+///
+/// A flag given without a value, e.g. `?animate`, is on.
+///
+/// # Errors
+/// Returns a 400 error with the given code if the value isn't `true`, `false`, `1` or `0`.
+fn parse_flag(value: Option<&str>, error: &'static str, name: &str) -> Result<bool, ApiError> {
+    match value {
+        None | Some("false" | "0") => Ok(false),
+        Some("" | "true" | "1") => Ok(true),
+        Some(_) => Err(api_error(
+            StatusCode::BAD_REQUEST,
+            error,
+            format!("{} must be true or false", name),
+        )),
+    }
+}
+
+/// Parses an optional comma-separated list of sections from a query parameter.
+///
+/// # Errors
+/// Returns a 400 error with the given code if a section is unknown.
+fn parse_sections(list: Option<&str>, error: &'static str) -> Result<Vec<Section>, ApiError> {
+    let Some(list) = list else {
+        return Ok(Vec::new());
+    };
+
+    Section::parse_list(list).map_err(|name| {
+        api_error(
+            StatusCode::BAD_REQUEST,
+            error,
+            format!(
+                "Unknown section: {} (available: {})",
+                name,
+                Section::ALL.map(|section| section.name()).join(", ")
+            ),
+        )
+    })
+}
+
+/// Resolves the requested template for a kind of card, falling back to its default.
+///
+/// # Errors
+/// Returns a 400 error listing the available templates if the template is
+/// unknown or cannot render `kind`.
+fn resolve_template(
+    state: &AppState,
+    kind: TemplateKind,
+    name: Option<&str>,
+) -> Result<String, ApiError> {
+    state
+        .templates
+        .resolve_kind(kind, name)
+        .map(str::to_string)
+        .map_err(|e| {
+            api_error(
+                StatusCode::BAD_REQUEST,
+                "invalid_template",
+                format!(
+                    "{} (available: {})",
+                    e,
+                    state
+                        .templates
+                        .names_for(kind)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            )
+        })
+}
+
+/// Converts a failed GitHub lookup into an API error with the matching status code.
+fn github_error(error: &str, context: &str, e: crate::errors::GlimError) -> ApiError {
+    tracing::error!("{}: {}", context, e);
+    let status_code = match &e {
+        crate::errors::GlimError::GitHub(github_error) => github_error.clone().into(),
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    api_error(status_code, error, format!("{}: {}", context, e))
+}
+
+/// Converts a template rendering failure into a 500 API error.
+fn template_error(e: TemplateError) -> ApiError {
+    tracing::error!("Failed to render card template: {}", e);
+    api_error(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "template_error",
+        format!("Failed to render card: {}", e),
+    )
+}
+
+/// Encodes a rendered card, going through the image cache when it is initialized.
+///
+/// The animation's frames are encoded instead of the still card when given.
+/// Renders are keyed by a hash of the SVG they encode, so a card is only
+/// served from the cache while its repository data is unchanged.
+///
+/// # Returns
+/// Encoded image bytes and the encoding timing breakdown (zero for cached renders)
+async fn encode_card_cached(
+    mut meaning: cache::RepositoryCard,
+    svg: String,
+    animation: Option<Animation>,
+    format: ImageFormat,
+    options: EncodeOptions,
+) -> Result<(Vec<u8>, EncodingTiming), ApiError> {
+    let image_error = |e: String| {
+        tracing::error!("Failed to generate image: {}", e);
+        api_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "image_generation_error",
+            format!("Failed to generate image: {}", e),
+        )
+    };
+
+    match cache::try_cache() {
+        Some(image_cache) => {
+            meaning.variant = format!(
+                "{}#{:016x}",
+                meaning.variant,
+                content_hash(&svg, animation.as_ref())
+            );
+            let value = image_cache
+                .get_or_create(meaning, move || async move {
+                    encode_card_blocking(svg, animation, format, options)
+                        .await
+                        .map(|(data, _)| data)
+                        .map_err(|e| cache::CacheError::Create(anyhow::Error::new(e)))
+                })
+                .await
+                .map_err(|e| image_error(e.to_string()))?;
+
+            // Timing is unknown for cached renders
+            Ok((value.image_data, EncodingTiming::default()))
+        }
+        None => encode_card_blocking(svg, animation, format, options)
+            .await
+            .map_err(|e| image_error(e.to_string())),
+    }
+}
+
+/// Hashes the SVG documents a card is encoded from.
+fn content_hash(svg: &str, animation: Option<&Animation>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    svg.hash(&mut hasher);
+    if let Some(animation) = animation {
+        animation.frames.hash(&mut hasher);
+        animation.duration.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Builds the HTTP response for an encoded card.
+///
+/// SVG responses get a restrictive Content Security Policy, since they are
+/// documents when opened directly.
+fn card_response(format: ImageFormat, image_data: Vec<u8>) -> Response {
+    let mut response = (
         [(axum::http::header::CONTENT_TYPE, format.mime_type())],
-        buffer.into_inner(),
+        image_data,
     )
-        .into_response())
+        .into_response();
+
+    if format == ImageFormat::Svg {
+        let headers = response.headers_mut();
+        headers.insert(
+            axum::http::header::CONTENT_SECURITY_POLICY,
+            axum::http::HeaderValue::from_static(SVG_CONTENT_SECURITY_POLICY),
+        );
+        headers.insert(
+            axum::http::header::X_CONTENT_TYPE_OPTIONS,
+            axum::http::HeaderValue::from_static("nosniff"),
+        );
+    }
+
+    response
+}
+
+/// Encodes a card on the blocking thread pool.
+///
+/// Rasterizing and encoding take long enough, up to seconds for animations,
+/// that running them on an async worker would stall other requests.
+async fn encode_card_blocking(
+    svg: String,
+    animation: Option<Animation>,
+    format: ImageFormat,
+    options: EncodeOptions,
+) -> crate::errors::Result<(Vec<u8>, EncodingTiming)> {
+    tokio::task::spawn_blocking(move || encode_card(&svg, animation.as_ref(), format, &options))
+        .await
+        .map_err(|e| ImageError::Task(e.to_string()))?
+}
+
+/// Encodes a rendered SVG card into the requested format.
+///
+/// # Arguments
+/// * `svg` - The formatted SVG card
+/// * `animation` - Frames to encode instead of the still card, if animated
+/// * `format` - Output image format
+/// * `options` - Rasterization options for the card's layout
+///
+/// # Returns
+/// Encoded image bytes and the encoding timing breakdown
+fn encode_card(
+    svg: &str,
+    animation: Option<&Animation>,
+    format: ImageFormat,
+    options: &EncodeOptions,
+) -> crate::errors::Result<(Vec<u8>, EncodingTiming)> {
+    let mut buffer = Cursor::new(Vec::new());
+    let encoder = crate::encode::create_encoder(format);
+    let timing = match animation {
+        Some(animation) => encoder.encode_animation(animation, &mut buffer, options)?,
+        None => encoder.encode(svg, &mut buffer, options)?,
+    };
+
+    Ok((buffer.into_inner(), timing))
 }
 
 /// Parses the repository name and format from the path.
@@ -751,50 +1652,6 @@ pub fn parse_scale_parameter(query: &ImageQuery) -> Option<f64> {
     }))
 }
 
-/// Formats the SVG template with repository data.
-///
-/// # Arguments
-/// * `data` - The SVG input data containing repository information
-///
-/// # Returns
-/// Formatted SVG string
-fn format_svg_template(data: &SvgInputData) -> String {
-    let svg_template = {
-        #[cfg(debug_assertions)]
-        {
-            tracing::debug!("Loading card.svg from current directory");
-
-            // Load at runtime to allow for hot reloading
-            std::fs::read_to_string("card.svg").unwrap_or_else(|_| {
-                tracing::warn!(
-                    "Failed to load card.svg from current directory, using embedded template"
-                );
-                include_str!("../card.svg").to_string()
-            })
-        }
-
-        #[cfg(not(debug_assertions))]
-        {
-            // Load at compile time as it generally won't be changing
-            include_str!("../card.svg")
-        }
-    };
-    let wrapped_description = crate::image::wrap_text(&data.description, 65);
-    let language_color =
-        crate::colors::get_color(&data.language).unwrap_or_else(|| "#f1e05a".to_string());
-
-    let formatted_stars = crate::image::format_count(&data.stars);
-    let formatted_forks = crate::image::format_count(&data.forks);
-
-    svg_template
-        .replace("{{name}}", &data.name)
-        .replace("{{description}}", &wrapped_description)
-        .replace("{{language}}", &data.language)
-        .replace("{{language_color}}", &language_color)
-        .replace("{{stars}}", &formatted_stars)
-        .replace("{{forks}}", &formatted_forks)
-}
-
 /// Detailed timing breakdown for image generation phases
 #[derive(Debug)]
 struct ImageGenerationTiming {
@@ -0,0 +1,568 @@
+//! SVG card templates.
+//!
+//! Cards are rendered through [MiniJinja](https://docs.rs/minijinja) templates,
+//! which support conditionals, loops and filters. Every printed value is
+//! XML-escaped automatically (see [`crate::text::escape_xml`]); values that are
+//! already markup can opt out with the `safe` filter. Besides the built-in
+//! filters (`upper`, `default`, ...), card templates can use:
+//!
+//! - `format_count`: abbreviates large numbers (`1234` → `1.2k`)
+//! - `truncate(length, end="…")`: shortens text to at most `length` characters
+//! - `escape` / `e`: XML-escapes a value, e.g. inside `{% autoescape false %}`
+//!
+//! The built-in templates are compiled from the SVG files at the crate root:
+//!
+//! - `default`: repository card, `card.svg`
+//! - `social`: social preview, `social.svg`
+//! - `badge`: single-line badge, `badge.svg`
+//! - `icon`: square icon, `icon.svg`
+//! - `user`: user profile card, `user.svg`
+//! - `org`: organization card, `org.svg`
+//! - `release`: release card, `release.svg`
+//! - `issue`: issue and pull request card, `issue.svg`
+//!
+//! Additional templates are loaded from a directory, one `<name>.svg` file per
+//! template, and may replace the built-in ones. Every template is rendered
+//! against sample data and parsed as SVG when loaded, so broken templates are
+//! reported at startup rather than on the first request.
+//!
+//! Repository, user, organization, release and issue cards expose different
+//! variables, so each template records which [`TemplateKind`]s it renders valid
+//! SVG for and is only used for those.
+
+use crate::card::{Card, Render};
+use crate::config::CardConfig;
+use crate::errors::TemplateError;
+use crate::issue::IssueCard;
+use crate::profile::{OrgCard, UserCard};
+use crate::release::ReleaseCard;
+use minijinja::{
+    AutoEscape, Environment, Error, ErrorKind, Output, State, UndefinedBehavior, Value,
+};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Name of the built-in card template.
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// Name of the built-in social preview template.
+pub const SOCIAL_TEMPLATE: &str = "social";
+
+/// Name of the built-in compact badge template.
+pub const BADGE_TEMPLATE: &str = "badge";
+
+/// Name of the built-in square icon template.
+pub const ICON_TEMPLATE: &str = "icon";
+
+/// Name of the built-in user profile template.
+pub const USER_TEMPLATE: &str = "user";
+
+/// Name of the built-in organization profile template.
+pub const ORG_TEMPLATE: &str = "org";
+
+/// Name of the built-in latest release template.
+pub const RELEASE_TEMPLATE: &str = "release";
+
+/// Name of the built-in issue and pull request template.
+pub const ISSUE_TEMPLATE: &str = "issue";
+
+/// Built-in templates as (name, kind, source file, source).
+const BUILTIN_TEMPLATES: [(&str, TemplateKind, &str, &str); 8] = [
+    (
+        DEFAULT_TEMPLATE,
+        TemplateKind::Repository,
+        "card.svg",
+        include_str!("../card.svg"),
+    ),
+    (
+        SOCIAL_TEMPLATE,
+        TemplateKind::Repository,
+        "social.svg",
+        include_str!("../social.svg"),
+    ),
+    (
+        BADGE_TEMPLATE,
+        TemplateKind::Repository,
+        "badge.svg",
+        include_str!("../badge.svg"),
+    ),
+    (
+        ICON_TEMPLATE,
+        TemplateKind::Repository,
+        "icon.svg",
+        include_str!("../icon.svg"),
+    ),
+    (
+        USER_TEMPLATE,
+        TemplateKind::User,
+        "user.svg",
+        include_str!("../user.svg"),
+    ),
+    (
+        ORG_TEMPLATE,
+        TemplateKind::Org,
+        "org.svg",
+        include_str!("../org.svg"),
+    ),
+    (
+        RELEASE_TEMPLATE,
+        TemplateKind::Release,
+        "release.svg",
+        include_str!("../release.svg"),
+    ),
+    (
+        ISSUE_TEMPLATE,
+        TemplateKind::Issue,
+        "issue.svg",
+        include_str!("../issue.svg"),
+    ),
+];
+
+/// Auto escape mode applied to every card template.
+const XML_ESCAPE: AutoEscape = AutoEscape::Custom("xml");
+
+/// Built-in templates, shared by callers that don't load a template directory.
+static BUILTIN: Lazy<TemplateEngine> = Lazy::new(TemplateEngine::new);
+
+/// Kind of data a template renders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TemplateKind {
+    /// Repository cards, rendered from [`CardContext`](crate::card::CardContext)
+    Repository,
+    /// User profile cards, rendered from [`UserCardContext`](crate::profile::UserCardContext)
+    User,
+    /// Organization profile cards, rendered from [`OrgCardContext`](crate::profile::OrgCardContext)
+    Org,
+    /// Latest release cards, rendered from [`ReleaseCardContext`](crate::release::ReleaseCardContext)
+    Release,
+    /// Issue and pull request cards, rendered from [`IssueCardContext`](crate::issue::IssueCardContext)
+    Issue,
+}
+
+impl TemplateKind {
+    /// All template kinds, in the order templates are validated against them.
+    pub const ALL: [TemplateKind; 5] = [
+        TemplateKind::Repository,
+        TemplateKind::User,
+        TemplateKind::Org,
+        TemplateKind::Release,
+        TemplateKind::Issue,
+    ];
+
+    /// Returns the name used in error messages.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TemplateKind::Repository => "repository",
+            TemplateKind::User => "user",
+            TemplateKind::Org => "organization",
+            TemplateKind::Release => "release",
+            TemplateKind::Issue => "issue",
+        }
+    }
+}
+
+/// A registered template.
+#[derive(Debug, Clone)]
+struct TemplateEntry {
+    /// File backing the template, re-read on every render in debug builds
+    path: Option<PathBuf>,
+    /// Kinds of card the template renders
+    kinds: Vec<TemplateKind>,
+}
+
+/// Registry of named card templates.
+#[derive(Debug, Clone)]
+pub struct TemplateEngine {
+    env: Environment<'static>,
+    templates: BTreeMap<String, TemplateEntry>,
+    /// Template used for repository cards when none is requested
+    default: String,
+}
+
+impl TemplateEngine {
+    /// Creates an engine containing only the built-in templates.
+    pub fn new() -> Self {
+        let mut env = create_environment();
+        let mut templates = BTreeMap::new();
+
+        for (name, kind, file, source) in BUILTIN_TEMPLATES {
+            env.add_template(name, source)
+                .expect("built-in card templates are valid");
+            templates.insert(
+                name.to_string(),
+                TemplateEntry {
+                    // Hot reload built-in templates from the working directory during development
+                    path: cfg!(debug_assertions).then(|| PathBuf::from(file)),
+                    kinds: vec![kind],
+                },
+            );
+        }
+
+        Self {
+            env,
+            templates,
+            default: DEFAULT_TEMPLATE.to_string(),
+        }
+    }
+
+    /// Returns the shared engine containing only the built-in templates.
+    pub fn builtin() -> &'static Self {
+        &BUILTIN
+    }
+
+    /// Creates an engine from the card configuration.
+    ///
+    /// Loads the configured template directory, if any, and selects the
+    /// configured default template.
+    ///
+    /// # Errors
+    /// Returns an error if a template fails to load or validate, or the
+    /// default template does not exist.
+    pub fn from_config(config: &CardConfig) -> Result<Self, TemplateError> {
+        let mut engine = Self::new();
+
+        if let Some(dir) = &config.templates_dir {
+            engine.load_dir(dir)?;
+        }
+        if let Some(default) = &config.default_template {
+            engine.set_default(default)?;
+        }
+
+        Ok(engine)
+    }
+
+    /// Loads every `*.svg` file in a directory as a template named after the file stem.
+    ///
+    /// # Errors
+    /// Returns an error if the directory cannot be read or any template is invalid.
+    pub fn load_dir(&mut self, dir: &Path) -> Result<(), TemplateError> {
+        let read_error = |source| TemplateError::Read {
+            path: dir.display().to_string(),
+            source,
+        };
+
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(dir).map_err(read_error)? {
+            let path = entry.map_err(read_error)?.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == "svg") {
+                files.push(path);
+            }
+        }
+        files.sort();
+
+        for path in files {
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                tracing::warn!("Skipping template with non UTF-8 name: {}", path.display());
+                continue;
+            };
+            let source = std::fs::read_to_string(&path).map_err(|source| TemplateError::Read {
+                path: path.display().to_string(),
+                source,
+            })?;
+
+            self.add(name, source)?;
+            if let Some(entry) = self.templates.get_mut(name) {
+                entry.path = Some(path.clone());
+            }
+            tracing::debug!("Loaded template {} from {}", name, path.display());
+        }
+
+        Ok(())
+    }
+
+    /// Adds a template, replacing any existing template with the same name.
+    ///
+    /// The template is rendered against sample data for every [`TemplateKind`]
+    /// and may be used for each kind it renders valid SVG for.
+    ///
+    /// # Errors
+    /// Returns an error if the template has a syntax error, or fails to render
+    /// valid SVG for every kind of sample data.
+    pub fn add(&mut self, name: &str, source: String) -> Result<(), TemplateError> {
+        let mut env = self.env.clone();
+        env.add_template_owned(name.to_string(), source)
+            .map_err(|e| TemplateError::Syntax {
+                name: name.to_string(),
+                message: e.to_string(),
+            })?;
+        let kinds = validate(&env, name)?;
+
+        self.env = env;
+        self.templates
+            .insert(name.to_string(), TemplateEntry { path: None, kinds });
+        Ok(())
+    }
+
+    /// Sets the template used when a repository card request does not name one.
+    ///
+    /// # Errors
+    /// Returns an error if no repository template is registered under `name`.
+    pub fn set_default(&mut self, name: &str) -> Result<(), TemplateError> {
+        self.check_kind(name, TemplateKind::Repository)?;
+        self.default = name.to_string();
+        Ok(())
+    }
+
+    /// Returns whether a template is registered under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.templates.contains_key(name)
+    }
+
+    /// Returns the kinds of card a template renders, or None if it is not registered.
+    pub fn kinds(&self, name: &str) -> Option<&[TemplateKind]> {
+        self.templates.get(name).map(|entry| entry.kinds.as_slice())
+    }
+
+    /// Resolves an optional repository template name, falling back to the default.
+    ///
+    /// # Errors
+    /// Returns an error if a name is given but no repository template is registered under it.
+    pub fn resolve<'a>(&'a self, name: Option<&'a str>) -> Result<&'a str, TemplateError> {
+        self.resolve_kind(TemplateKind::Repository, name)
+    }
+
+    /// Resolves an optional template name for a kind of card, falling back to its default.
+    ///
+    /// # Errors
+    /// Returns an error if a name is given but is not registered, or cannot render `kind`.
+    pub fn resolve_kind<'a>(
+        &'a self,
+        kind: TemplateKind,
+        name: Option<&'a str>,
+    ) -> Result<&'a str, TemplateError> {
+        let name = name.unwrap_or(match kind {
+            TemplateKind::Repository => &self.default,
+            TemplateKind::User => USER_TEMPLATE,
+            TemplateKind::Org => ORG_TEMPLATE,
+            TemplateKind::Release => RELEASE_TEMPLATE,
+            TemplateKind::Issue => ISSUE_TEMPLATE,
+        });
+        self.check_kind(name, kind)?;
+        Ok(name)
+    }
+
+    /// Returns the names of all registered templates in sorted order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.templates.keys().map(String::as_str)
+    }
+
+    /// Returns the names of the templates that render `kind`, in sorted order.
+    pub fn names_for(&self, kind: TemplateKind) -> impl Iterator<Item = &str> {
+        self.templates
+            .iter()
+            .filter(move |(_, entry)| entry.kinds.contains(&kind))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Checks that a template is registered and renders `kind`.
+    fn check_kind(&self, name: &str, kind: TemplateKind) -> Result<(), TemplateError> {
+        match self.kinds(name) {
+            None => Err(TemplateError::Unknown(name.to_string())),
+            Some(kinds) if !kinds.contains(&kind) => Err(TemplateError::Incompatible {
+                name: name.to_string(),
+                kind: kind.name(),
+            }),
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Renders a template with the given context.
+    ///
+    /// # Errors
+    /// Returns an error if the template does not exist or fails to render.
+    pub fn render<S: Serialize>(&self, name: &str, context: S) -> Result<String, TemplateError> {
+        let path = &self
+            .templates
+            .get(name)
+            .ok_or_else(|| TemplateError::Unknown(name.to_string()))?
+            .path;
+
+        #[cfg(debug_assertions)]
+        if let Some(path) = path {
+            match std::fs::read_to_string(path) {
+                Ok(source) => {
+                    // Render from a scratch environment so the validated copy stays intact
+                    let mut env = self.env.clone();
+                    env.add_template_owned(name.to_string(), source)
+                        .map_err(|e| TemplateError::Syntax {
+                            name: name.to_string(),
+                            message: e.to_string(),
+                        })?;
+                    return render_in(&env, name, context);
+                }
+                Err(_) => tracing::warn!(
+                    "Failed to reload {} from {}, using loaded template",
+                    name,
+                    path.display()
+                ),
+            }
+        }
+        #[cfg(not(debug_assertions))]
+        let _ = path;
+
+        render_in(&self.env, name, context)
+    }
+}
+
+impl Default for TemplateEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Creates a template environment with the card filters registered.
+fn create_environment() -> Environment<'static> {
+    let mut env = Environment::new();
+    // Typos in variable names should fail validation rather than render blank
+    env.set_undefined_behavior(UndefinedBehavior::Strict);
+    // Repository data is user-controlled, so escape everything printed into the SVG
+    env.set_auto_escape_callback(|_| XML_ESCAPE);
+    env.set_formatter(xml_formatter);
+    env.add_filter("escape", escape_filter);
+    env.add_filter("e", escape_filter);
+    env.add_filter("format_count", format_count_filter);
+    env.add_filter("truncate", truncate_filter);
+    env
+}
+
+/// Renders a template from an environment.
+fn render_in<S: Serialize>(
+    env: &Environment<'_>,
+    name: &str,
+    context: S,
+) -> Result<String, TemplateError> {
+    let render_error = |e: minijinja::Error| TemplateError::Render {
+        name: name.to_string(),
+        message: e.to_string(),
+    };
+
+    env.get_template(name)
+        .map_err(render_error)?
+        .render(context)
+        .map_err(render_error)
+}
+
+/// Renders a template against sample data of every kind.
+///
+/// # Returns
+/// The kinds the template renders valid SVG for
+///
+/// # Errors
+/// Returns the error for the first kind if the template renders none of them.
+fn validate(env: &Environment<'_>, name: &str) -> Result<Vec<TemplateKind>, TemplateError> {
+    let mut kinds = Vec::new();
+    let mut first_error = None;
+
+    for kind in TemplateKind::ALL {
+        match validate_kind(env, name, kind) {
+            Ok(()) => kinds.push(kind),
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    match first_error {
+        Some(e) if kinds.is_empty() => Err(e),
+        _ => Ok(kinds),
+    }
+}
+
+/// A card with representative data, used to validate templates.
+pub(crate) trait Sample {
+    /// Creates the sample card.
+    ///
+    /// Its text contains markup characters, so templates that print values
+    /// unescaped fail validation.
+    fn sample() -> Self;
+}
+
+/// Renders a template against sample data of one kind and checks the output parses as SVG.
+fn validate_kind(
+    env: &Environment<'_>,
+    name: &str,
+    kind: TemplateKind,
+) -> Result<(), TemplateError> {
+    let svg = match kind {
+        TemplateKind::Repository => render_in(env, name, Card::sample().context())?,
+        TemplateKind::User => render_in(env, name, UserCard::sample().context())?,
+        TemplateKind::Org => render_in(env, name, OrgCard::sample().context())?,
+        TemplateKind::Release => render_in(env, name, ReleaseCard::sample().context())?,
+        TemplateKind::Issue => render_in(env, name, IssueCard::sample().context())?,
+    };
+
+    usvg::Tree::from_str(&svg, &usvg::Options::default()).map_err(|e| {
+        TemplateError::InvalidSvg {
+            name: name.to_string(),
+            message: e.to_string(),
+        }
+    })?;
+
+    Ok(())
+}
+
+/// Formatter writing printed values, XML-escaping them unless marked safe.
+fn xml_formatter(out: &mut Output, state: &State, value: &Value) -> Result<(), Error> {
+    if state.auto_escape() == XML_ESCAPE && !value.is_safe() {
+        out.write_str(&crate::text::escape_xml(&value.to_string()))?;
+        Ok(())
+    } else {
+        minijinja::escape_formatter(out, state, value)
+    }
+}
+
+/// Filter XML-escaping a value and marking the result safe.
+fn escape_filter(value: Value) -> Result<Value, Error> {
+    reject_undefined("escape", &value)?;
+    if value.is_safe() {
+        return Ok(value);
+    }
+    Ok(Value::from_safe_string(
+        crate::text::escape_xml(&value.to_string()).into_owned(),
+    ))
+}
+
+/// Filter abbreviating large numbers, e.g. `{{ stars | format_count }}`.
+fn format_count_filter(value: Value) -> Result<String, Error> {
+    reject_undefined("format_count", &value)?;
+    Ok(crate::image::format_count(&value.to_string()))
+}
+
+/// Fails on undefined filter input, so misspelled variables don't render blank.
+fn reject_undefined(filter: &str, value: &Value) -> Result<(), Error> {
+    if value.is_undefined() {
+        return Err(Error::new(
+            ErrorKind::UndefinedError,
+            format!("{} applied to an undefined value", filter),
+        ));
+    }
+    Ok(())
+}
+
+/// Filter shortening text to at most `length` characters, including the `end` marker.
+fn truncate_filter(value: String, length: usize, end: Option<String>) -> String {
+    truncate(&value, length, end.as_deref().unwrap_or("…"))
+}
+
+/// Shortens text to at most `length` characters, replacing the tail with `end`.
+///
+/// # Arguments
+/// * `text` - The text to shorten
+/// * `length` - Maximum length in characters, including `end`
+/// * `end` - Marker appended to shortened text
+///
+/// # Returns
+/// The original text if it fits, otherwise the shortened text
+pub fn truncate(text: &str, length: usize, end: &str) -> String {
+    if text.chars().count() <= length {
+        return text.to_string();
+    }
+
+    let keep = length.saturating_sub(end.chars().count());
+    let mut truncated: String = text.chars().take(keep).collect();
+    truncated.truncate(truncated.trim_end().len());
+    truncated.push_str(end);
+    truncated
+}
@@ -0,0 +1,296 @@
+//! Text processing for card content.
+//!
+//! Repository names, descriptions and other fields come straight from the
+//! GitHub API and are user-controlled. Before they reach an SVG document they
+//! pass through [`strip_control_chars`], which normalizes whitespace and drops
+//! characters XML cannot represent, and [`escape_xml`], which escapes markup.
+//! Card templates apply [`escape_xml`] automatically to every printed value.
+//!
+//! [`TextMeasurer`] lays text out using glyph advances from the same font
+//! database the [`Rasterizer`](crate::image::Rasterizer) renders with, so
+//! wrapping and truncation match the pixel widths of the rendered card.
+
+use once_cell::sync::Lazy;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use usvg::fontdb;
+
+/// Marker appended to text that was shortened to fit.
+pub const ELLIPSIS: &str = "…";
+
+/// Text measurer shared by card rendering.
+static SHARED_MEASURER: Lazy<TextMeasurer> =
+    Lazy::new(|| TextMeasurer::new(crate::image::font_database()));
+
+/// Returns whether a character can appear in an XML 1.0 document.
+fn is_xml_char(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\r') || !(c.is_control() || matches!(c, '\u{fffe}' | '\u{ffff}'))
+}
+
+/// Removes control characters from text.
+///
+/// Tabs, newlines and carriage returns become spaces; other control characters
+/// and XML noncharacters are dropped.
+///
+/// # Arguments
+/// * `text` - The text to clean
+///
+/// # Returns
+/// The cleaned text, borrowed if nothing needed to change
+pub fn strip_control_chars(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(|c| c.is_control() || !is_xml_char(c)) {
+        return Cow::Borrowed(text);
+    }
+
+    Cow::Owned(
+        text.chars()
+            .filter_map(|c| match c {
+                '\t' | '\n' | '\r' => Some(' '),
+                c if is_xml_char(c) => Some(c),
+                _ => None,
+            })
+            .collect(),
+    )
+}
+
+/// Escapes text for use in SVG character data and attribute values.
+///
+/// Escapes `&`, `<`, `>`, `"` and `'`, and drops characters XML cannot represent.
+///
+/// # Arguments
+/// * `text` - The text to escape
+///
+/// # Returns
+/// The escaped text, borrowed if nothing needed to change
+pub fn escape_xml(text: &str) -> Cow<'_, str> {
+    if !text.contains(['&', '<', '>', '"', '\'']) && is_xml_text(text) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut escaped = String::with_capacity(text.len() + 16);
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c if is_xml_char(c) => escaped.push(c),
+            _ => {}
+        }
+    }
+
+    Cow::Owned(escaped)
+}
+
+/// Returns whether every character in the text can appear in an XML document.
+fn is_xml_text(text: &str) -> bool {
+    text.chars().all(is_xml_char)
+}
+
+/// Font used to draw a piece of text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontStyle {
+    /// Preferred font family, falling back to the generic sans-serif family
+    pub family: &'static str,
+    /// Font size in pixels
+    pub size: f32,
+    /// Font weight (400 is normal, 700 is bold)
+    pub weight: u16,
+}
+
+/// Measures and lays out text using real glyph advances.
+///
+/// Advances are looked up per character and cached. Characters the requested
+/// font lacks are measured with the first font that has them, mirroring the
+/// renderer's font fallback; characters no font covers get an estimated width.
+#[derive(Debug)]
+pub struct TextMeasurer {
+    font_db: Arc<fontdb::Database>,
+    /// Advance widths in ems, keyed by family, weight and character
+    advances: RwLock<HashMap<(&'static str, u16, char), f32>>,
+}
+
+impl TextMeasurer {
+    /// Creates a measurer backed by a font database.
+    pub fn new(font_db: Arc<fontdb::Database>) -> Self {
+        Self {
+            font_db,
+            advances: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the measurer backed by the rasterizer's font database.
+    pub fn shared() -> &'static Self {
+        &SHARED_MEASURER
+    }
+
+    /// Measures the width of a single line of text in pixels.
+    pub fn width(&self, text: &str, font: &FontStyle) -> f32 {
+        text.chars().map(|c| self.advance(c, font)).sum::<f32>() * font.size
+    }
+
+    /// Wraps text into lines no wider than `max_width` pixels.
+    ///
+    /// Lines break at whitespace; words wider than a whole line (or scripts
+    /// written without spaces) break between characters. If the text needs more
+    /// than `max_lines` lines, the last line ends with an ellipsis.
+    ///
+    /// # Arguments
+    /// * `text` - The text to wrap
+    /// * `font` - Font the text is drawn with
+    /// * `max_width` - Maximum line width in pixels
+    /// * `max_lines` - Maximum number of lines
+    ///
+    /// # Returns
+    /// The wrapped lines, or no lines if the text is blank
+    pub fn wrap(
+        &self,
+        text: &str,
+        font: &FontStyle,
+        max_width: f32,
+        max_lines: usize,
+    ) -> Vec<String> {
+        if max_lines == 0 {
+            return Vec::new();
+        }
+
+        let mut lines: Vec<String> = Vec::new();
+        let mut current = String::new();
+
+        for word in text.split_whitespace() {
+            let separator = if current.is_empty() { "" } else { " " };
+            if self.width(&format!("{current}{separator}{word}"), font) <= max_width {
+                current.push_str(separator);
+                current.push_str(word);
+                continue;
+            }
+
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+
+            // Break words that don't fit on a line of their own between characters
+            for c in word.chars() {
+                current.push(c);
+                if current.chars().count() > 1 && self.width(&current, font) > max_width {
+                    current.pop();
+                    lines.push(std::mem::replace(&mut current, c.to_string()));
+                }
+            }
+        }
+        if !current.is_empty() {
+            lines.push(current);
+        }
+
+        if lines.len() > max_lines {
+            lines.truncate(max_lines);
+            if let Some(last) = lines.last_mut() {
+                *last = self.ellipsize(last, font, max_width);
+            }
+        }
+
+        lines
+    }
+
+    /// Shortens text with an ellipsis so it fits within `max_width` pixels.
+    ///
+    /// # Returns
+    /// The original text if it fits, otherwise the shortened text
+    pub fn truncate(&self, text: &str, font: &FontStyle, max_width: f32) -> String {
+        if self.width(text, font) <= max_width {
+            return text.to_string();
+        }
+        self.ellipsize(text, font, max_width)
+    }
+
+    /// Appends an ellipsis, removing characters until the text fits.
+    fn ellipsize(&self, text: &str, font: &FontStyle, max_width: f32) -> String {
+        let budget = max_width - self.width(ELLIPSIS, font);
+        let mut width = 0.0;
+        let mut end = 0;
+
+        for (index, c) in text.char_indices() {
+            width += self.advance(c, font) * font.size;
+            if width > budget {
+                break;
+            }
+            end = index + c.len_utf8();
+        }
+
+        format!("{}{}", text[..end].trim_end(), ELLIPSIS)
+    }
+
+    /// Returns the advance width of a character in ems.
+    fn advance(&self, c: char, font: &FontStyle) -> f32 {
+        let key = (font.family, font.weight, c);
+        if let Some(advance) = self
+            .advances
+            .read()
+            .ok()
+            .and_then(|cache| cache.get(&key).copied())
+        {
+            return advance;
+        }
+
+        let advance = self
+            .lookup_advance(c, font)
+            .unwrap_or_else(|| estimate_advance(c));
+        if let Ok(mut cache) = self.advances.write() {
+            cache.insert(key, advance);
+        }
+
+        advance
+    }
+
+    /// Looks up a character's advance in the requested font, then in any font that has it.
+    fn lookup_advance(&self, c: char, font: &FontStyle) -> Option<f32> {
+        let query = fontdb::Query {
+            families: &[fontdb::Family::Name(font.family), fontdb::Family::SansSerif],
+            weight: fontdb::Weight(font.weight),
+            stretch: fontdb::Stretch::Normal,
+            style: fontdb::Style::Normal,
+        };
+
+        if let Some(advance) = self
+            .font_db
+            .query(&query)
+            .and_then(|id| self.face_advance(id, c, font.weight))
+        {
+            return Some(advance);
+        }
+
+        self.font_db
+            .faces()
+            .find_map(|face| self.face_advance(face.id, c, font.weight))
+    }
+
+    /// Reads a character's advance from a font face, in ems.
+    fn face_advance(&self, id: fontdb::ID, c: char, weight: u16) -> Option<f32> {
+        self.font_db
+            .with_face_data(id, |data, index| {
+                let mut face = ttf_parser::Face::parse(data, index).ok()?;
+                if face.is_variable() {
+                    face.set_variation(ttf_parser::Tag::from_bytes(b"wght"), weight as f32);
+                }
+
+                let glyph = face.glyph_index(c)?;
+                let advance = face.glyph_hor_advance(glyph)?;
+                Some(advance as f32 / face.units_per_em() as f32)
+            })
+            .flatten()
+    }
+}
+
+/// Estimates the advance of a character no installed font covers, in ems.
+///
+/// CJK ideographs, emoji and other characters from U+2E80 onward are usually
+/// full width; everything else is treated as an average Latin glyph.
+fn estimate_advance(c: char) -> f32 {
+    if c as u32 >= 0x2e80 {
+        1.0
+    } else {
+        0.6
+    }
+}
@@ -0,0 +1,395 @@
+//! Color themes for repository cards.
+//!
+//! Provides the built-in palettes (light, dark, high-contrast), a registry
+//! that merges them with custom themes defined in the configuration file,
+//! and per-request color overrides with WCAG contrast enforcement.
+
+use crate::errors::ThemeError;
+use std::collections::BTreeMap;
+use tracing::warn;
+
+/// Name of the theme used when none is requested.
+pub const DEFAULT_THEME: &str = "light";
+
+/// Minimum text/background contrast ratio for WCAG AA normal text.
+pub const WCAG_AA_CONTRAST: f64 = 4.5;
+
+/// A named color palette applied to a card.
+///
+/// All colors are normalized `#rrggbb` hex strings, so they can be inserted
+/// into the SVG template without further escaping.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    /// Theme name, as used in `?theme=` and `--theme`
+    pub name: String,
+    /// Repository name color
+    pub title_color: String,
+    /// Description and stats text color
+    pub text_color: String,
+    /// Octicon fill color
+    pub icon_color: String,
+    /// Card background color
+    pub bg_color: String,
+    /// Card border color
+    pub border_color: String,
+}
+
+impl Theme {
+    /// GitHub's light palette, matching the original card colors.
+    pub fn light() -> Self {
+        Self::from_palette(
+            "light",
+            ["#0366d6", "#586069", "#586069", "#ffffff", "#d1d5da"],
+        )
+    }
+
+    /// GitHub's dark palette, suited to dark-mode READMEs.
+    pub fn dark() -> Self {
+        Self::from_palette(
+            "dark",
+            ["#58a6ff", "#8b949e", "#8b949e", "#0d1117", "#30363d"],
+        )
+    }
+
+    /// GitHub's dark high-contrast palette.
+    pub fn high_contrast() -> Self {
+        Self::from_palette(
+            "high-contrast",
+            ["#71b7ff", "#f0f3f6", "#f0f3f6", "#0a0c10", "#7a828e"],
+        )
+    }
+
+    /// Builds a theme from `[title, text, icon, bg, border]` colors.
+    fn from_palette(name: &str, colors: [&str; 5]) -> Self {
+        let [title, text, icon, bg, border] = colors.map(str::to_string);
+        Self {
+            name: name.to_string(),
+            title_color: title,
+            text_color: text,
+            icon_color: icon,
+            bg_color: bg,
+            border_color: border,
+        }
+    }
+
+    /// Sets one color by its field name, validating the value.
+    ///
+    /// # Arguments
+    /// * `field` - One of `title_color`, `text_color`, `icon_color`, `bg_color`, `border_color`
+    /// * `value` - Hex color, with or without the leading `#`
+    ///
+    /// # Errors
+    /// Returns an error if the field is unknown or the value is not a valid hex color.
+    pub fn set_color(&mut self, field: &str, value: &str) -> Result<(), ThemeError> {
+        let color = parse_hex_color(value).ok_or_else(|| ThemeError::InvalidColor {
+            field: field.to_string(),
+            value: value.to_string(),
+        })?;
+
+        let slot = match field {
+            "title_color" => &mut self.title_color,
+            "text_color" => &mut self.text_color,
+            "icon_color" => &mut self.icon_color,
+            "bg_color" => &mut self.bg_color,
+            "border_color" => &mut self.border_color,
+            _ => return Err(ThemeError::UnknownField(field.to_string())),
+        };
+        *slot = color;
+
+        Ok(())
+    }
+
+    /// Returns a key identifying this theme's name and resolved colors.
+    ///
+    /// Two themes with the same key render identically, so this is suitable
+    /// for distinguishing cached renders.
+    pub fn key(&self) -> String {
+        format!(
+            "{}/{}/{}/{}/{}/{}",
+            self.name,
+            self.title_color,
+            self.text_color,
+            self.icon_color,
+            self.bg_color,
+            self.border_color
+        )
+    }
+
+    /// Adjusts the title and text colors until they meet WCAG AA contrast against the background.
+    ///
+    /// Each failing color is blended toward black or white, whichever contrasts
+    /// more with the background, just far enough to reach the required ratio.
+    ///
+    /// # Returns
+    /// Names of the fields that were adjusted
+    pub fn ensure_contrast(&mut self) -> Vec<&'static str> {
+        let Some(bg) = parse_rgb(&self.bg_color) else {
+            return Vec::new();
+        };
+
+        let mut adjusted = Vec::new();
+        for (field, color) in [
+            ("title_color", &mut self.title_color),
+            ("text_color", &mut self.text_color),
+        ] {
+            if let Some(fixed) = adjust_contrast(color, bg) {
+                *color = fixed;
+                adjusted.push(field);
+            }
+        }
+
+        adjusted
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+/// Per-request color overrides applied on top of a theme.
+///
+/// Values are unvalidated user input until [`ColorOverrides::apply`] is called.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColorOverrides {
+    /// Repository name color override
+    pub title_color: Option<String>,
+    /// Description and stats text color override
+    pub text_color: Option<String>,
+    /// Octicon fill color override
+    pub icon_color: Option<String>,
+    /// Card background color override
+    pub bg_color: Option<String>,
+    /// Card border color override
+    pub border_color: Option<String>,
+}
+
+impl ColorOverrides {
+    /// Returns true if no color is overridden.
+    pub fn is_empty(&self) -> bool {
+        self.fields().all(|(_, value)| value.is_none())
+    }
+
+    /// Applies the overrides to a copy of the theme, validating every value.
+    ///
+    /// # Errors
+    /// Returns an error if any override is not a valid hex color.
+    pub fn apply(&self, theme: &Theme) -> Result<Theme, ThemeError> {
+        let mut theme = theme.clone();
+        for (field, value) in self.fields() {
+            if let Some(value) = value {
+                theme.set_color(field, value)?;
+            }
+        }
+        Ok(theme)
+    }
+
+    fn fields(&self) -> impl Iterator<Item = (&'static str, Option<&str>)> {
+        [
+            ("title_color", self.title_color.as_deref()),
+            ("text_color", self.text_color.as_deref()),
+            ("icon_color", self.icon_color.as_deref()),
+            ("bg_color", self.bg_color.as_deref()),
+            ("border_color", self.border_color.as_deref()),
+        ]
+        .into_iter()
+    }
+}
+
+/// Parses a hex color into its normalized `#rrggbb` form.
+///
+/// Accepts 3- or 6-digit hex with an optional leading `#`, so values can be
+/// passed in query strings without URL-encoding the `#`.
+///
+/// # Returns
+/// The lowercase `#rrggbb` color, or None if the value is not a valid hex color
+pub fn parse_hex_color(value: &str) -> Option<String> {
+    let hex = value.strip_prefix('#').unwrap_or(value);
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    match hex.len() {
+        3 => Some(
+            hex.chars()
+                .flat_map(|c| [c, c])
+                .fold(String::from("#"), |mut acc, c| {
+                    acc.push(c.to_ascii_lowercase());
+                    acc
+                }),
+        ),
+        6 => Some(format!("#{}", hex.to_ascii_lowercase())),
+        _ => None,
+    }
+}
+
+/// Parses a `#rrggbb` color into its RGB components.
+fn parse_rgb(color: &str) -> Option<[u8; 3]> {
+    let hex = parse_hex_color(color)?;
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some([channel(1)?, channel(3)?, channel(5)?])
+}
+
+/// Computes the WCAG relative luminance of an RGB color.
+fn relative_luminance(rgb: [u8; 3]) -> f64 {
+    let [r, g, b] = rgb.map(|c| {
+        let c = c as f64 / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    });
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// Computes the WCAG contrast ratio between two RGB colors (1.0 to 21.0).
+fn rgb_contrast(a: [u8; 3], b: [u8; 3]) -> f64 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Computes the WCAG contrast ratio between two hex colors.
+///
+/// # Returns
+/// Contrast ratio from 1.0 to 21.0, or None if either color is invalid
+pub fn contrast_ratio(foreground: &str, background: &str) -> Option<f64> {
+    Some(rgb_contrast(parse_rgb(foreground)?, parse_rgb(background)?))
+}
+
+/// Blends a color toward black or white until it meets WCAG AA contrast.
+///
+/// # Returns
+/// The adjusted color, or None if the color already has sufficient contrast
+fn adjust_contrast(color: &str, bg: [u8; 3]) -> Option<String> {
+    let rgb = parse_rgb(color)?;
+    if rgb_contrast(rgb, bg) >= WCAG_AA_CONTRAST {
+        return None;
+    }
+
+    // One of black or white always reaches at least ~4.58:1 against any background
+    let target = if rgb_contrast([0, 0, 0], bg) >= rgb_contrast([255, 255, 255], bg) {
+        [0, 0, 0]
+    } else {
+        [255, 255, 255]
+    };
+
+    (1..=20)
+        .map(|step| {
+            let t = step as f64 / 20.0;
+            [0, 1, 2]
+                .map(|i| (rgb[i] as f64 + (target[i] as f64 - rgb[i] as f64) * t).round() as u8)
+        })
+        .find(|candidate| rgb_contrast(*candidate, bg) >= WCAG_AA_CONTRAST)
+        .map(|[r, g, b]| format!("#{:02x}{:02x}{:02x}", r, g, b))
+}
+
+/// Registry of named themes available for rendering.
+#[derive(Debug, Clone)]
+pub struct ThemeRegistry {
+    themes: BTreeMap<String, Theme>,
+    default: String,
+}
+
+impl ThemeRegistry {
+    /// Creates a registry containing only the built-in themes.
+    pub fn new() -> Self {
+        let mut registry = Self {
+            themes: BTreeMap::new(),
+            default: DEFAULT_THEME.to_string(),
+        };
+
+        for theme in [Theme::light(), Theme::dark(), Theme::high_contrast()] {
+            registry.register(theme);
+        }
+
+        registry
+    }
+
+    /// Creates a registry from the built-in themes plus the configured custom themes.
+    ///
+    /// # Errors
+    /// Returns an error if the configured default theme does not exist.
+    pub fn from_config(config: &crate::config::CardConfig) -> Result<Self, ThemeError> {
+        let mut registry = Self::new();
+        for theme in &config.themes {
+            registry.register(theme.clone());
+        }
+
+        if let Some(default) = &config.default_theme {
+            registry.set_default(default)?;
+        }
+
+        Ok(registry)
+    }
+
+    /// Adds a theme, replacing any existing theme with the same name.
+    pub fn register(&mut self, theme: Theme) {
+        self.themes.insert(theme.name.to_lowercase(), theme);
+    }
+
+    /// Changes the theme used when no theme is requested.
+    ///
+    /// # Errors
+    /// Returns an error if no theme with that name is registered.
+    pub fn set_default(&mut self, name: &str) -> Result<(), ThemeError> {
+        let theme = self.get(name)?;
+        self.default = theme.name.to_lowercase();
+        Ok(())
+    }
+
+    /// Looks up a theme by name (case-insensitive).
+    ///
+    /// # Errors
+    /// Returns an error if no theme with that name is registered.
+    pub fn get(&self, name: &str) -> Result<&Theme, ThemeError> {
+        self.themes
+            .get(&name.to_lowercase())
+            .ok_or_else(|| ThemeError::Unknown(name.to_string()))
+    }
+
+    /// Resolves an optional requested theme, falling back to the default theme.
+    ///
+    /// # Errors
+    /// Returns an error if a theme was requested but is not registered.
+    pub fn resolve(&self, name: Option<&str>) -> Result<&Theme, ThemeError> {
+        self.get(name.unwrap_or(&self.default))
+    }
+
+    /// Builds the theme for a render: resolves the named theme, applies the
+    /// color overrides and enforces WCAG AA text contrast.
+    ///
+    /// # Errors
+    /// Returns an error if the theme is unknown or an override is invalid.
+    pub fn build(
+        &self,
+        name: Option<&str>,
+        overrides: &ColorOverrides,
+    ) -> Result<Theme, ThemeError> {
+        let mut theme = overrides.apply(self.resolve(name)?)?;
+
+        let adjusted = theme.ensure_contrast();
+        if !adjusted.is_empty() {
+            warn!(
+                theme = %theme.name,
+                fields = ?adjusted,
+                "Adjusted colors below WCAG AA contrast ({}:1)",
+                WCAG_AA_CONTRAST
+            );
+        }
+
+        Ok(theme)
+    }
+
+    /// Returns the names of all registered themes, sorted alphabetically.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.themes.values().map(|theme| theme.name.as_str())
+    }
+}
+
+impl Default for ThemeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -17,6 +17,7 @@ async fn test_cache_basic_functionality() -> glim::cache::Result<()> {
         owner: "test_owner".to_string(),
         repo: "test_repo".to_string(),
         theme: "dark".to_string(),
+        variant: "png@1".to_string(),
     };
 
     // Test cache miss and creation
@@ -0,0 +1,1059 @@
+use chrono::{TimeZone, Utc};
+use glim::card::{
+    age_label, updated_label, AnimationFrame, Card, CardLayout, CardOptions, Layout, Render,
+    Section,
+};
+use glim::encode::ImageFormat;
+use glim::errors::TemplateError;
+use glim::github::{
+    sort_languages, CommitWeek, Contributor, LanguageBytes, License, Parent, Repository, StarPoint,
+};
+use glim::template::DEFAULT_TEMPLATE;
+use glim::theme::Theme;
+
+// Test fixtures
+fn create_test_repository() -> Repository {
+    Repository {
+        name: "test-repo".to_string(),
+        description: Some("A test repository".to_string()),
+        language: Some("Rust".to_string()),
+        stargazers_count: 1234,
+        forks_count: 56,
+        ..Default::default()
+    }
+}
+
+fn create_detailed_repository() -> Repository {
+    Repository {
+        open_issues_count: 17,
+        subscribers_count: 9,
+        license: Some(License {
+            spdx_id: Some("MIT".to_string()),
+        }),
+        topics: vec!["github".to_string(), "svg".to_string()],
+        default_branch: "main".to_string(),
+        pushed_at: Some(Utc.with_ymd_and_hms(2025, 3, 1, 12, 0, 0).unwrap()),
+        ..create_test_repository()
+    }
+}
+
+fn create_test_languages() -> Vec<LanguageBytes> {
+    sort_languages([
+        ("Rust".to_string(), 7_500),
+        ("Python".to_string(), 2_000),
+        ("Shell".to_string(), 500),
+    ])
+}
+
+fn create_language_card(layout: Layout, legend: usize) -> Card {
+    let options = CardOptions {
+        template: layout.template().unwrap_or(DEFAULT_TEMPLATE).to_string(),
+        layout,
+        show: vec![Section::Languages],
+        legend,
+        ..Default::default()
+    };
+    Card::new(create_test_repository(), options).with_languages(create_test_languages())
+}
+
+#[test]
+fn test_render_repository_fields() {
+    let svg = Card::new(create_test_repository(), CardOptions::default())
+        .render()
+        .unwrap();
+
+    assert!(svg.contains("test-repo"));
+    assert!(svg.contains("A test repository"));
+    assert!(svg.contains(">Rust<"));
+    assert!(svg.contains("1.2k"));
+    assert!(svg.contains(">56<"));
+    assert!(!svg.contains("{{"), "All placeholders should be replaced");
+    assert!(!svg.contains("{%"), "All template tags should be evaluated");
+}
+
+#[test]
+fn test_render_description_lines() {
+    let mut repository = create_test_repository();
+    repository.description = Some("word ".repeat(200));
+
+    let svg = Card::new(repository, CardOptions::default())
+        .render()
+        .unwrap();
+
+    // The first line sits on the description baseline and each following
+    // line is offset from the previous one by the same amount
+    assert_eq!(svg.matches("<tspan").count(), 4);
+    assert!(svg.contains(r#"y="50.0" class="description""#));
+    assert_eq!(svg.matches(r#"dy="1.4em""#).count(), 3);
+}
+
+#[test]
+fn test_render_applies_theme_colors() {
+    let options = CardOptions {
+        theme: Theme::dark(),
+        ..Default::default()
+    };
+    let svg = Card::new(create_test_repository(), options)
+        .render()
+        .unwrap();
+
+    let dark = Theme::dark();
+    for color in [
+        &dark.title_color,
+        &dark.text_color,
+        &dark.bg_color,
+        &dark.border_color,
+    ] {
+        assert!(svg.contains(color.as_str()), "Missing color {}", color);
+    }
+    assert!(!svg.contains(&Theme::light().bg_color));
+}
+
+#[test]
+fn test_render_missing_optional_fields() {
+    let mut repository = create_test_repository();
+    repository.description = None;
+    repository.language = None;
+
+    let svg = Card::new(repository, CardOptions::default())
+        .render()
+        .unwrap();
+
+    // Without a language the dot is omitted and the stats shift left
+    assert!(!svg.contains("<circle"));
+    assert!(svg.contains("translate(0, 0)"));
+    assert!(!svg.contains("class=\"description\""));
+    assert!(!svg.contains("{{"));
+}
+
+#[test]
+fn test_render_unknown_language_color() {
+    let mut repository = create_test_repository();
+    repository.language = Some("Not A Real Language".to_string());
+
+    let svg = Card::new(repository, CardOptions::default())
+        .render()
+        .unwrap();
+
+    // Unknown languages fall back to the default language color
+    assert!(svg.contains("#f1e05a"));
+}
+
+#[test]
+fn test_render_unknown_template() {
+    let options = CardOptions {
+        template: "missing".to_string(),
+        ..Default::default()
+    };
+
+    let result = Card::new(create_test_repository(), options).render();
+    assert!(matches!(result, Err(TemplateError::Unknown(name)) if name == "missing"));
+}
+
+#[test]
+fn test_render_escapes_repository_fields() {
+    let mut repository = create_test_repository();
+    repository.name = "<script>alert(1)</script>".to_string();
+    repository.description = Some("Tom & Jerry's \"cards\" </text><svg onload=x>".to_string());
+    repository.language = Some("C<>\u{0}".to_string());
+
+    let svg = Card::new(repository, CardOptions::default())
+        .render()
+        .unwrap();
+
+    assert!(!svg.contains("<script>"));
+    assert!(!svg.contains("<svg onload"));
+    assert!(!svg.contains('\u{0}'));
+    assert!(svg.contains("&lt;script&gt;"));
+    assert!(svg.contains("Tom &amp; Jerry&apos;s &quot;cards&quot;"));
+    assert!(svg.contains(">C&lt;&gt;<"));
+
+    // The output must still be a well-formed SVG document
+    usvg::Tree::from_str(&svg, &usvg::Options::default()).expect("Rendered card should parse");
+}
+
+#[test]
+fn test_render_strips_control_characters() {
+    let mut repository = create_test_repository();
+    repository.description = Some("first\nsecond\u{1b}[0m".to_string());
+
+    let svg = Card::new(repository, CardOptions::default())
+        .render()
+        .unwrap();
+
+    assert!(svg.contains("first second[0m"));
+    assert!(!svg.contains('\u{1b}'));
+}
+
+#[test]
+fn test_render_truncates_long_name() {
+    let mut repository = create_test_repository();
+    repository.name =
+        "an-extremely-long-repository-name-that-cannot-fit-beside-the-icon".to_string();
+
+    let svg = Card::new(repository.clone(), CardOptions::default())
+        .render()
+        .unwrap();
+
+    assert!(!svg.contains(&repository.name));
+    assert!(svg.contains("an-extremely-long"));
+    assert!(svg.contains("…</text>"));
+}
+
+#[test]
+fn test_render_clamps_long_description() {
+    let mut repository = create_test_repository();
+    repository.description = Some("lorem ipsum dolor sit amet ".repeat(20));
+
+    let svg = Card::new(repository, CardOptions::default())
+        .render()
+        .unwrap();
+
+    assert_eq!(svg.matches("<tspan").count(), 4);
+    assert!(svg.contains("…</tspan>"));
+}
+
+#[test]
+fn test_layout_for_description_lines() {
+    // Two lines keep the classic card size
+    let classic = CardLayout::for_description_lines(2);
+    assert_eq!((classic.width, classic.height), (400, 125));
+    assert_eq!(classic.stats_y, 100.0);
+
+    let empty = CardLayout::for_description_lines(0);
+    let single = CardLayout::for_description_lines(1);
+    let long = CardLayout::for_description_lines(4);
+    assert!(empty.height < single.height && single.height < classic.height);
+    assert!(long.height > classic.height);
+    assert!(long.stats_y > classic.stats_y);
+}
+
+#[test]
+fn test_render_height_follows_description() {
+    let mut repository = create_test_repository();
+    repository.description = None;
+    let short = Card::new(repository.clone(), CardOptions::default());
+
+    repository.description = Some("lorem ipsum dolor sit amet ".repeat(20));
+    let long = Card::new(repository, CardOptions::default());
+
+    for card in [short, long] {
+        let layout = card.context().layout;
+        let svg = card.render().unwrap();
+
+        assert!(svg.contains(&format!(r#"height="{}""#, layout.height)));
+        assert!(svg.contains(&format!(r#"viewBox="0 0 400 {}""#, layout.height)));
+        assert!(svg.contains(&format!(r#"height="{}""#, layout.height - 1)));
+
+        let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+        assert_eq!(tree.size().height(), layout.height as f32);
+    }
+}
+
+#[test]
+fn test_layout_parse() {
+    assert_eq!(Layout::parse("card"), Some(Layout::Card));
+    assert_eq!(Layout::parse("Social"), Some(Layout::Social));
+    assert_eq!(Layout::parse("badge"), Some(Layout::Badge));
+    assert_eq!(Layout::parse("ICON"), Some(Layout::Icon));
+    assert_eq!(Layout::parse("banner"), None);
+    assert_eq!(Layout::default(), Layout::Card);
+}
+
+#[test]
+fn test_layout_encode_options() {
+    let card = Layout::Card.encode_options(Some(2.0));
+    assert_eq!(card.scale, Some(2.0));
+    assert!(card.padding);
+
+    // Badges keep the scale but drop the margin
+    let badge = Layout::Badge.encode_options(Some(2.0));
+    assert_eq!(badge.scale, Some(2.0));
+    assert!(!badge.padding);
+
+    // Fixed-size layouts ignore the scale and render without padding
+    let social = Layout::Social.encode_options(Some(2.0));
+    assert_eq!(social.scale, None);
+    assert!(!social.padding);
+}
+
+#[test]
+fn test_render_social_layout() {
+    let mut repository = create_test_repository();
+    repository.owner.login = "octocat".to_string();
+    repository.description = Some("lorem ipsum dolor sit amet ".repeat(20));
+
+    let options = CardOptions {
+        template: Layout::Social.template().unwrap().to_string(),
+        layout: Layout::Social,
+        ..Default::default()
+    };
+    let card = Card::new(repository, options);
+    let layout = card.context().layout;
+    assert_eq!(layout.kind, Layout::Social);
+    assert_eq!((layout.width, layout.height), (1280, 640));
+
+    let svg = card.render().unwrap();
+    assert!(svg.contains(r#"viewBox="0 0 1280 640""#));
+    assert!(svg.contains("octocat /"));
+    assert!(svg.contains(">O</text>"));
+    assert_eq!(svg.matches("<tspan").count(), 3);
+    assert!(svg.contains("…</tspan>"));
+}
+
+#[test]
+fn test_render_badge_layout() {
+    let options = CardOptions {
+        template: Layout::Badge.template().unwrap().to_string(),
+        layout: Layout::Badge,
+        ..Default::default()
+    };
+    let card = Card::new(create_test_repository(), options);
+    let context = card.context();
+    assert_eq!((context.layout.width, context.layout.height), (250, 28));
+    assert!(context.description_lines.is_empty());
+
+    let svg = card.render().unwrap();
+    assert!(svg.contains(r#"viewBox="0 0 250 28""#));
+    assert!(svg.contains(">test-repo</text>"));
+    assert!(svg.contains("#dea584"));
+    assert!(svg.contains(">1.2k</text>"));
+    assert!(!svg.contains("A test repository"));
+}
+
+#[test]
+fn test_render_icon_layout() {
+    let options = CardOptions {
+        template: Layout::Icon.template().unwrap().to_string(),
+        layout: Layout::Icon,
+        show: vec![Section::Languages, Section::Topics],
+        ..Default::default()
+    };
+    let card = Card::new(create_detailed_repository(), options);
+    let context = card.context();
+    assert_eq!((context.layout.width, context.layout.height), (64, 64));
+    assert_eq!(context.initials, "TR");
+    assert!(context.description_lines.is_empty());
+    assert!(context.stats.is_empty());
+    assert!(context.topic_row.is_none());
+    assert!(context.language_bar.is_none());
+    assert!(!card.options.needs_languages());
+
+    // The language dot sits in the corner unless there's an avatar
+    let svg = card.render().unwrap();
+    assert!(svg.contains(r#"viewBox="0 0 64 64""#));
+    assert!(svg.contains(">TR</text>"));
+    assert!(svg.contains("#dea584"));
+    assert!(!svg.contains("A test repository"));
+
+    let avatar = "data:image/png;base64,iVBORw0KGgo=".to_string();
+    let svg = card.clone().with_avatar(Some(avatar)).render().unwrap();
+    assert!(svg.contains("<image"));
+    assert!(!svg.contains("#dea584"));
+
+    assert_eq!(Layout::for_format(ImageFormat::Ico), Layout::Icon);
+    assert_eq!(Layout::for_format(ImageFormat::Png), Layout::Card);
+}
+
+#[test]
+fn test_initials() {
+    let initials = |name: &str| {
+        let repository = Repository {
+            name: name.to_string(),
+            ..create_test_repository()
+        };
+        Card::new(repository, CardOptions::default())
+            .context()
+            .initials
+    };
+
+    assert_eq!(initials("glim"), "G");
+    assert_eq!(initials("rust-lang"), "RL");
+    assert_eq!(initials("TypeScript"), "TS");
+    assert_eq!(initials("my_cool.project-name"), "MC");
+    assert_eq!(initials(".github"), "G");
+    assert_eq!(initials("HTTP"), "H");
+    assert_eq!(initials("---"), "");
+}
+
+#[test]
+fn test_render_owner_avatar() {
+    let avatar = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==";
+
+    let without = Card::new(create_test_repository(), CardOptions::default())
+        .render()
+        .unwrap();
+    assert!(!without.contains("<image"));
+
+    for layout in [Layout::Card, Layout::Social] {
+        let options = CardOptions {
+            template: layout.template().unwrap_or(DEFAULT_TEMPLATE).to_string(),
+            layout,
+            ..Default::default()
+        };
+        let svg = Card::new(create_test_repository(), options)
+            .with_avatar(Some(avatar.to_string()))
+            .render()
+            .unwrap();
+
+        assert!(svg.contains(&format!(r#"<image href="{}""#, avatar)));
+        assert!(svg.contains(r#"clip-path="url(#avatar-clip)""#));
+        let tree = usvg::Tree::from_str(&svg, &usvg::Options::default())
+            .expect("Rendered card should parse");
+        assert!(tree.root().has_children());
+    }
+}
+
+#[test]
+fn test_section_parse_list() {
+    assert_eq!(Section::parse("Languages"), Some(Section::Languages));
+    assert_eq!(Section::parse("star_history"), Some(Section::StarHistory));
+    assert_eq!(
+        Section::parse_list("languages, ,LANGUAGES"),
+        Ok(vec![Section::Languages])
+    );
+    assert_eq!(Section::parse_list(""), Ok(vec![]));
+    assert_eq!(Section::parse_list("languages,bogus"), Err("bogus"));
+}
+
+#[test]
+fn test_language_bar_is_opt_in() {
+    let card = Card::new(create_test_repository(), CardOptions::default())
+        .with_languages(create_test_languages());
+    let context = card.context();
+
+    assert!(context.language_bar.is_none());
+    assert_eq!(context.layout, CardLayout::for_description_lines(1));
+    assert!(!card.render().unwrap().contains("language-bar-clip"));
+}
+
+#[test]
+fn test_render_language_bar() {
+    let card = create_language_card(Layout::Card, 0);
+    let context = card.context();
+    let bar = context.language_bar.as_ref().unwrap();
+
+    let names: Vec<&str> = bar.segments.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["Rust", "Python", "Shell"]);
+    assert_eq!(bar.segments[0].percent, 75.0);
+    assert_eq!(bar.segments[0].x, 0.0);
+    assert_eq!(bar.segments[0].width, 276.0);
+    assert_eq!(bar.segments[1].x, 276.0);
+    // Segments fill the bar exactly
+    let last = bar.segments.last().unwrap();
+    assert_eq!(last.x + last.width, bar.width);
+    assert!(bar.legend.is_empty());
+
+    // The stats row moves down to make room for the bar
+    let plain = CardLayout::for_description_lines(1);
+    assert_eq!(context.layout.stats_y, plain.stats_y + 20.0);
+    assert_eq!(context.layout.height, plain.height + 20);
+
+    let svg = card.render().unwrap();
+    assert!(svg.contains("<title>Python 20.0%</title>"));
+    usvg::Tree::from_str(&svg, &usvg::Options::default()).expect("Rendered card should parse");
+}
+
+#[test]
+fn test_render_language_legend() {
+    let card = create_language_card(Layout::Card, 2);
+    let context = card.context();
+    let bar = context.language_bar.as_ref().unwrap();
+
+    let labels: Vec<&str> = bar.legend.iter().map(|e| e.label.as_str()).collect();
+    assert_eq!(labels, vec!["Rust 75.0%", "Python 20.0%"]);
+    assert!(bar.legend[0].x < bar.legend[1].x);
+    assert_eq!(
+        context.layout.stats_y,
+        CardLayout::for_description_lines(1).stats_y + 40.0
+    );
+
+    let svg = card.render().unwrap();
+    assert!(svg.contains(">Rust 75.0%</text>"));
+    assert!(!svg.contains(">Shell 5.0%</text>"));
+}
+
+#[test]
+fn test_language_bar_groups_small_languages() {
+    let mut languages = vec![("Rust".to_string(), 100_000)];
+    languages.extend((0..4).map(|i| (format!("Tiny{}", i), 100)));
+
+    let mut card = create_language_card(Layout::Card, 0);
+    card.languages = sort_languages(languages);
+    let context = card.context();
+    let segments = &context.language_bar.as_ref().unwrap().segments;
+
+    let names: Vec<&str> = segments.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["Rust", "Other"]);
+    assert_eq!(segments[1].percent, 0.4);
+}
+
+#[test]
+fn test_language_bar_layouts() {
+    // Social previews replace the accent strip along the bottom edge
+    let card = create_language_card(Layout::Social, 3);
+    let context = card.context();
+    let bar = context.language_bar.as_ref().unwrap();
+    assert_eq!((bar.x, bar.y, bar.width), (0.0, 624.0, 1280.0));
+    assert!(bar.legend.is_empty());
+    assert_eq!(context.layout.height, 640);
+
+    // Badges have no room for the bar and don't need the language data
+    let card = create_language_card(Layout::Badge, 3);
+    assert!(card.context().language_bar.is_none());
+    assert!(!card.options.needs_languages());
+}
+
+fn create_star_history_card(layout: Layout) -> Card {
+    let repository = Repository {
+        stargazers_count: 400,
+        ..create_test_repository()
+    };
+    let options = CardOptions {
+        layout,
+        show: vec![Section::StarHistory],
+        ..Default::default()
+    };
+    let star_history = vec![
+        StarPoint {
+            time: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            stars: 1,
+        },
+        StarPoint {
+            time: Utc.with_ymd_and_hms(2024, 7, 1, 0, 0, 0).unwrap(),
+            stars: 200,
+        },
+    ];
+
+    Card::new(repository, options)
+        .with_star_history(star_history)
+        .at(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap())
+}
+
+#[test]
+fn test_render_star_history() {
+    let card = create_star_history_card(Layout::Card);
+    let context = card.context();
+    let history = context.star_history.as_ref().unwrap();
+
+    // The line ends at the current star count, at the top right corner
+    assert_eq!(
+        (history.x, history.width, history.height),
+        (16.0, 368.0, 28.0)
+    );
+    assert!(history.line.starts_with("0,27.9 "));
+    assert!(history.line.ends_with(" 368,0"));
+    assert_eq!(history.area, format!("M0,28 L{} L368,28 Z", history.line));
+    assert_eq!(history.since, "Jan 2024");
+
+    // The stats row moves down to make room for the sparkline
+    let plain = CardLayout::for_description_lines(1);
+    assert_eq!(context.layout.stats_y, plain.stats_y + 42.0);
+    assert_eq!(context.layout.height, plain.height + 42);
+
+    let svg = card.render().unwrap();
+    assert!(svg.contains("<title>Stars since Jan 2024</title>"));
+    usvg::Tree::from_str(&svg, &usvg::Options::default()).expect("Rendered card should parse");
+
+    // Without a history, or when hidden, there is no sparkline and no extra room
+    let card = create_star_history_card(Layout::Card).with_star_history(Vec::new());
+    assert!(card.context().star_history.is_none());
+    assert_eq!(card.context().layout, plain);
+    assert!(!CardOptions::default().needs_star_history());
+}
+
+#[test]
+fn test_star_history_layouts() {
+    // Social previews draw the sparkline behind the stats, above the bottom strip
+    let card = create_star_history_card(Layout::Social);
+    let context = card.context();
+    let history = context.star_history.as_ref().unwrap();
+    assert_eq!((history.x, history.y, history.width), (0.0, 504.0, 1280.0));
+    assert_eq!(context.layout.height, 640);
+
+    // Badges have no room for the sparkline and don't need the history
+    let card = create_star_history_card(Layout::Badge);
+    assert!(card.context().star_history.is_none());
+    assert!(!card.options.needs_star_history());
+}
+
+fn create_activity() -> Vec<CommitWeek> {
+    // Weeks starting Sunday, Dec 29, 2024
+    let start = Utc
+        .with_ymd_and_hms(2024, 12, 29, 0, 0, 0)
+        .unwrap()
+        .timestamp();
+    [4, 0, 2, 8]
+        .into_iter()
+        .enumerate()
+        .map(|(index, total)| CommitWeek {
+            week: start + index as i64 * 7 * 24 * 60 * 60,
+            total,
+        })
+        .collect()
+}
+
+#[test]
+fn test_render_activity_graph() {
+    let options = CardOptions {
+        show: vec![Section::Activity],
+        ..Default::default()
+    };
+    let card = Card::new(create_test_repository(), options).with_activity(create_activity());
+    let context = card.context();
+    let graph = context.activity.as_ref().unwrap();
+
+    assert_eq!(graph.commits, 14);
+    assert_eq!(graph.bars.len(), 4);
+    // Bars are centered in equal slots, scaled to the busiest week
+    let bar = &graph.bars[3];
+    assert_eq!((bar.commits, bar.y, bar.height), (8, 0.0, 28.0));
+    assert_eq!((bar.x, bar.width), (289.8, 64.4));
+    assert_eq!((graph.bars[0].y, graph.bars[0].height), (14.0, 14.0));
+    // Empty weeks keep a sliver of a bar
+    assert_eq!(graph.bars[1].height, 1.0);
+    assert_eq!(graph.bars[0].week, "Dec 29, 2024");
+
+    let plain = CardLayout::for_description_lines(1);
+    assert_eq!(context.layout.stats_y, plain.stats_y + 42.0);
+
+    let svg = card.render().unwrap();
+    assert!(svg.contains("<title>8 commits, week of Jan 19, 2025</title>"));
+    assert!(svg.contains("<title>14 commits in the last year</title>"));
+
+    // Without activity (still being computed) the card has no graph and no extra room
+    let card = card.with_activity(Vec::new());
+    assert!(card.context().activity.is_none());
+    assert_eq!(card.context().layout, plain);
+}
+
+#[test]
+fn test_graphs_stack() {
+    let mut card = create_star_history_card(Layout::Card).with_activity(create_activity());
+    card.options.show.push(Section::Activity);
+    let context = card.context();
+
+    let history = context.star_history.as_ref().unwrap();
+    let graph = context.activity.as_ref().unwrap();
+    assert_eq!(graph.y, history.y + 42.0);
+    assert_eq!(
+        context.layout.stats_y,
+        CardLayout::for_description_lines(1).stats_y + 84.0
+    );
+
+    // Social previews split the space behind the stats, star history on top
+    card.options.layout = Layout::Social;
+    card.options.template = Layout::Social.template().unwrap().to_string();
+    let context = card.context();
+    let history = context.star_history.as_ref().unwrap();
+    let graph = context.activity.as_ref().unwrap();
+    assert_eq!((history.y, history.height), (504.0, 56.0));
+    assert_eq!((graph.y, graph.height), (568.0, 56.0));
+    assert!(history.y + history.height < graph.y);
+    assert_eq!(graph.y + graph.height, 624.0);
+    assert!(history.line.ends_with(" 1280,0"));
+    assert_eq!(context.layout.height, 640);
+
+    let svg = card.render().unwrap();
+    assert!(svg.contains("translate(0.0, 504.0)"));
+    assert!(svg.contains("translate(0.0, 568.0)"));
+    usvg::Tree::from_str(&svg, &usvg::Options::default()).expect("Rendered card should parse");
+
+    // Either graph alone keeps the whole space
+    card.options
+        .show
+        .retain(|section| *section != Section::StarHistory);
+    let graph = card.context().activity.unwrap();
+    assert_eq!((graph.y, graph.height), (504.0, 120.0));
+    assert!(!CardOptions {
+        layout: Layout::Badge,
+        ..card.options.clone()
+    }
+    .needs_activity());
+}
+
+fn create_contributors(count: usize) -> Vec<Contributor> {
+    (0..count)
+        .map(|index| Contributor {
+            login: format!("user{}", index),
+            contributions: 100 - index as u32,
+            avatar: (index % 2 == 0).then(|| "data:image/png;base64,AAAA".to_string()),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn create_contributors_card(layout: Layout, max: usize) -> Card {
+    let options = CardOptions {
+        layout,
+        show: vec![Section::Contributors],
+        contributors: max,
+        ..Default::default()
+    };
+    Card::new(create_test_repository(), options).with_contributors(create_contributors(20))
+}
+
+#[test]
+fn test_render_contributors() {
+    let card = create_contributors_card(Layout::Card, 3);
+    let context = card.context();
+    let row = context.contributor_row.as_ref().unwrap();
+
+    // Limited to the requested number, overlapping left to right
+    let logins: Vec<&str> = row.contributors.iter().map(|c| c.login.as_ref()).collect();
+    assert_eq!(logins, vec!["user0", "user1", "user2"]);
+    let offsets: Vec<f64> = row.contributors.iter().map(|c| c.x).collect();
+    assert_eq!(offsets, vec![0.0, 18.0, 36.0]);
+    assert_eq!((row.x, row.size), (16.0, 24.0));
+
+    // The row sits below the stats, growing the card
+    let plain = CardLayout::for_description_lines(1);
+    assert_eq!(context.layout.stats_y, plain.stats_y);
+    assert_eq!(row.y, f64::from(plain.height) - 25.0 + 14.0);
+    assert_eq!(context.layout.height, (row.y + 24.0 + 16.0).ceil() as u32);
+
+    let svg = card.render().unwrap();
+    assert!(svg.contains("<title>user2 (98 commits)</title>"));
+    // Contributors without an avatar get their initial
+    assert!(svg.contains(">U</text>"));
+    usvg::Tree::from_str(&svg, &usvg::Options::default()).expect("Rendered card should parse");
+
+    // Asking for no contributors skips them entirely
+    let card = create_contributors_card(Layout::Card, 0);
+    assert!(!card.options.needs_contributors());
+    assert!(card.context().contributor_row.is_none());
+}
+
+#[test]
+fn test_contributors_layouts() {
+    // Social previews put the avatars at the end of the stats row
+    let card = create_contributors_card(Layout::Social, 16);
+    let context = card.context();
+    let row = context.contributor_row.as_ref().unwrap();
+    assert_eq!(row.contributors.len(), 16);
+    assert_eq!(row.x + 15.0 * 40.0 + 56.0, 1200.0);
+    assert_eq!(context.layout.height, 640);
+
+    // Badges have no room for contributors
+    let card = create_contributors_card(Layout::Badge, 8);
+    assert!(card.context().contributor_row.is_none());
+    assert!(!card.options.needs_contributors());
+}
+
+#[test]
+fn test_animation_frames() {
+    let first = AnimationFrame::new(0, 5);
+    assert_eq!((first.progress, first.fade), (0.0, 0.0));
+    let middle = AnimationFrame::new(2, 5);
+    // Eased, so the counters are past halfway and the description fully faded in
+    assert_eq!(middle.progress, 0.875);
+    assert_eq!(middle.fade, 1.0);
+    assert_eq!(middle.count(1234), 1080);
+    let last = AnimationFrame::new(4, 5);
+    assert_eq!((last.progress, last.count(1234)), (1.0, 1234));
+
+    // A single frame is the finished card
+    assert_eq!(AnimationFrame::new(0, 1).progress, 1.0);
+}
+
+#[test]
+fn test_render_frames() {
+    let card = Card::new(create_test_repository(), CardOptions::default());
+    let frames = card.render_frames(5).unwrap();
+    assert_eq!(frames.len(), 5);
+
+    // Counters start at zero with the description hidden
+    assert!(frames[0].contains(">0</text>"));
+    assert!(!frames[0].contains("1.2k"));
+    assert!(frames[0].contains(r#"class="description" opacity="0.0""#));
+
+    // The last frame shows the totals
+    assert!(frames[4].contains("1.2k"));
+    assert!(frames[4].contains(r#"class="description" opacity="1.0""#));
+    assert!(!card
+        .render()
+        .unwrap()
+        .contains(r#"class="description" opacity"#));
+
+    // Stats keep their positions while counting up
+    let context = card.context();
+    let stats_x = |svg: &str| {
+        context
+            .stats
+            .iter()
+            .all(|stat| svg.contains(&format!("translate({}, ", stat.x)))
+    };
+    assert!(frames.iter().all(|frame| stats_x(frame)));
+
+    for frame in &frames {
+        usvg::Tree::from_str(frame, &usvg::Options::default()).expect("Frame should parse");
+    }
+}
+
+#[test]
+fn test_content_key() {
+    let mut options = CardOptions::default();
+    assert_eq!(options.content_key(), "");
+
+    // The legend only matters when the language bar is shown
+    options.legend = 3;
+    assert_eq!(options.content_key(), "");
+
+    options.show = vec![Section::Languages];
+    assert_eq!(options.content_key(), "+languages+legend=3");
+
+    options.show = vec![Section::StarHistory];
+    assert_eq!(options.content_key(), "+star_history");
+
+    // The number of contributors only matters when they are shown
+    options.contributors = 4;
+    assert_eq!(options.content_key(), "+star_history");
+    options.show = vec![Section::Contributors];
+    assert_eq!(options.content_key(), "+contributors+max=4");
+}
+
+#[test]
+fn test_section_visibility() {
+    let options = CardOptions {
+        show: vec![Section::Issues, Section::Stars],
+        hide: vec![Section::Forks, Section::Issues],
+        ..Default::default()
+    };
+
+    // Defaults are shown unless hidden, and hiding wins over showing
+    assert!(options.shows(Section::Language));
+    assert!(options.shows(Section::Stars));
+    assert!(!options.shows(Section::Forks));
+    assert!(!options.shows(Section::Issues));
+    assert!(!options.shows(Section::License));
+    assert_eq!(options.content_key(), "-forks");
+
+    let options = CardOptions {
+        show: vec![Section::Updated, Section::License],
+        hide: vec![Section::Language],
+        ..Default::default()
+    };
+    assert_eq!(options.content_key(), "-language+license+updated");
+}
+
+#[test]
+fn test_default_stats() {
+    let card = Card::new(create_detailed_repository(), CardOptions::default());
+    let context = card.context();
+
+    let stats: Vec<(Section, &str, u32)> = context
+        .stats
+        .iter()
+        .map(|stat| (stat.section, stat.label.as_str(), stat.x))
+        .collect();
+    assert_eq!(
+        stats,
+        vec![
+            (Section::Language, "Rust", 0),
+            (Section::Stars, "1.2k", 80),
+            (Section::Forks, "56", 140),
+        ]
+    );
+    assert!(context.topic_row.is_none());
+    assert_eq!(context.layout, CardLayout::for_description_lines(1));
+}
+
+#[test]
+fn test_render_all_stats() {
+    let at = Utc.with_ymd_and_hms(2025, 3, 4, 12, 0, 0).unwrap();
+    let options = CardOptions {
+        show: Section::ALL.to_vec(),
+        ..Default::default()
+    };
+    let card = Card::new(create_detailed_repository(), options).at(at);
+    let context = card.context();
+
+    let sections: Vec<Section> = context.stats.iter().map(|stat| stat.section).collect();
+    assert_eq!(
+        sections,
+        vec![
+            Section::Language,
+            Section::Stars,
+            Section::Forks,
+            Section::Issues,
+            Section::Watchers,
+            Section::License,
+            Section::Branch,
+            Section::Updated,
+        ]
+    );
+    assert_eq!(context.updated, "updated 3 days ago");
+
+    // Stats that don't fit wrap onto a second row, which grows the card
+    let wrapped = context.stats.iter().position(|stat| stat.y > 0).unwrap();
+    assert_eq!(context.stats[wrapped].x, 0);
+    assert_eq!(context.stats.last().unwrap().y, 20);
+    assert!(context.stats.iter().all(|stat| stat.x < 368));
+
+    // Topic chips push the stats row down
+    let topics = context.topic_row.as_ref().unwrap();
+    let chips: Vec<&str> = topics.chips.iter().map(|chip| chip.name.as_str()).collect();
+    assert_eq!(chips, vec!["github", "svg"]);
+    let plain = CardLayout::for_description_lines(1);
+    assert_eq!(context.layout.stats_y, plain.stats_y + 26.0);
+    assert_eq!(context.layout.height, plain.height + 26 + 20);
+
+    let svg = card.render().unwrap();
+    for text in [
+        ">17</text>",
+        ">9</text>",
+        ">MIT</text>",
+        ">main</text>",
+        ">github</text>",
+    ] {
+        assert!(svg.contains(text), "Missing {}", text);
+    }
+    usvg::Tree::from_str(&svg, &usvg::Options::default()).expect("Rendered card should parse");
+}
+
+#[test]
+fn test_hidden_and_missing_stats() {
+    let mut repository = create_detailed_repository();
+    repository.license = Some(License {
+        spdx_id: Some("NOASSERTION".to_string()),
+    });
+    repository.default_branch = String::new();
+
+    let options = CardOptions {
+        show: vec![Section::License, Section::Branch, Section::Issues],
+        hide: vec![Section::Stars],
+        ..Default::default()
+    };
+    let card = Card::new(repository, options);
+    let context = card.context();
+
+    // Unidentified licenses and missing values leave no empty stat behind
+    let sections: Vec<Section> = context.stats.iter().map(|stat| stat.section).collect();
+    assert_eq!(
+        sections,
+        vec![Section::Language, Section::Forks, Section::Issues]
+    );
+    assert_eq!(context.license, "");
+    assert!(!context.show["stars"]);
+    assert!(context.show["issues"]);
+}
+
+#[test]
+fn test_badge_hides_stars() {
+    let options = CardOptions {
+        template: "badge".to_string(),
+        layout: Layout::Badge,
+        hide: vec![Section::Stars],
+        ..Default::default()
+    };
+    let svg = Card::new(create_test_repository(), options)
+        .render()
+        .unwrap();
+
+    assert!(!svg.contains("1.2k"));
+    assert!(svg.contains("<circle"));
+}
+
+#[test]
+fn test_updated_label() {
+    let now = Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap();
+    let ago = |days| now - chrono::Duration::days(days);
+
+    assert_eq!(updated_label(now, now), "updated today");
+    assert_eq!(
+        updated_label(now + chrono::Duration::hours(2), now),
+        "updated today"
+    );
+    assert_eq!(updated_label(ago(1), now), "updated yesterday");
+    assert_eq!(updated_label(ago(12), now), "updated 12 days ago");
+    assert_eq!(updated_label(ago(95), now), "updated 3 months ago");
+    assert_eq!(updated_label(ago(800), now), "updated 2 years ago");
+    assert_eq!(age_label("opened", ago(1), now), "opened yesterday");
+    assert_eq!(age_label("opened", ago(3), now), "opened 3 days ago");
+}
+
+#[test]
+fn test_render_status_pills() {
+    let repository = Repository {
+        archived: true,
+        fork: true,
+        parent: Some(Parent {
+            full_name: "octocat/upstream".to_string(),
+        }),
+        mirror_url: Some("https://example.com/upstream.git".to_string()),
+        ..create_test_repository()
+    };
+    let card = Card::new(repository, CardOptions::default());
+    let context = card.context();
+
+    let status = context.status.as_ref().unwrap();
+    let labels: Vec<&str> = status.pills.iter().map(|pill| pill.label).collect();
+    assert_eq!(labels, vec!["Archived", "Fork", "Mirror"]);
+    assert!(status
+        .pills
+        .windows(2)
+        .all(|pair| pair[0].x + pair[0].width < pair[1].x));
+    assert!(context.mirror && !context.is_template);
+
+    // The "forked from" line pushes the description and stats down
+    assert_eq!(context.forked_from, "forked from octocat/upstream");
+    let plain = CardLayout::for_description_lines(1);
+    assert_eq!(context.layout.description_y, plain.description_y + 18.0);
+    assert_eq!(context.layout.stats_y, plain.stats_y + 18.0);
+    assert_eq!(context.layout.height, plain.height + 18);
+
+    let svg = card.render().unwrap();
+    assert!(svg.contains(">Archived</text>"));
+    assert!(svg.contains(">forked from octocat/upstream</text>"));
+    usvg::Tree::from_str(&svg, &usvg::Options::default()).expect("Rendered card should parse");
+}
+
+#[test]
+fn test_status_pills_shorten_name() {
+    let mut repository = create_test_repository();
+    repository.name = "a-repository-with-a-rather-long-name".to_string();
+    let plain = Card::new(repository.clone(), CardOptions::default());
+
+    repository.is_template = true;
+    repository.fork = true;
+    let card = Card::new(repository, CardOptions::default());
+    let context = card.context();
+
+    // Room is left for the pills, and a fork without a known parent has no subtitle
+    assert!(context.display_name.chars().count() < plain.context().display_name.chars().count());
+    assert!(context.display_name.ends_with('…'));
+    assert_eq!(context.forked_from, "");
+    assert_eq!(context.layout, CardLayout::for_description_lines(1));
+
+    let pills = &context.status.as_ref().unwrap().pills;
+    let last = pills.last().unwrap();
+    assert!(last.x + last.width <= 384);
+}
+
+#[test]
+fn test_status_pills_by_layout() {
+    let repository = Repository {
+        archived: true,
+        ..create_test_repository()
+    };
+
+    let options = CardOptions {
+        template: "social".to_string(),
+        layout: Layout::Social,
+        ..Default::default()
+    };
+    let card = Card::new(repository.clone(), options);
+    let status = card.context().status.unwrap();
+    assert_eq!(status.height, 40.0);
+    assert!(status.pills[0].x > 80);
+
+    let options = CardOptions {
+        template: "badge".to_string(),
+        layout: Layout::Badge,
+        ..Default::default()
+    };
+    assert!(Card::new(repository, options).context().status.is_none());
+
+    let card = Card::new(create_test_repository(), CardOptions::default());
+    assert!(card.context().status.is_none());
+}
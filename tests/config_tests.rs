@@ -1,5 +1,9 @@
-use glim::config::{CliOverrides, Config, GitHubConfig, RateLimitConfig, ServerConfig};
+use glim::config::{
+    CliOverrides, Config, GitHubConfig, RateLimitConfig, ServerConfig, DEFAULT_CACHE_CAPACITY_MIB,
+};
+use glim::errors::ConfigError;
 use std::net::{IpAddr, Ipv4Addr};
+use std::path::PathBuf;
 
 #[test]
 fn test_default_config_values() {
@@ -345,3 +349,141 @@ fn test_config_load_with_mixed_scenarios() {
     std::env::remove_var("HEALTHCHECK_TOKEN");
     std::env::remove_var("HEALTHCHECK_HOST_BYPASS");
 }
+
+#[test]
+fn test_apply_yaml_custom_themes() {
+    let mut config = Config::default();
+    config
+        .apply_yaml(
+            r##"
+theme: ocean
+themes:
+  ocean:
+    title_color: "#4fc3f7"
+    base: dark
+  reef:
+    base: ocean
+    bg_color: "001f3f"
+"##,
+        )
+        .unwrap();
+
+    assert_eq!(config.card.default_theme, Some("ocean".to_string()));
+    assert_eq!(config.card.themes.len(), 2);
+
+    let ocean = &config.card.themes[0];
+    assert_eq!(ocean.name, "ocean");
+    assert_eq!(ocean.title_color, "#4fc3f7");
+    // Unset colors are inherited from the base theme regardless of key order
+    assert_eq!(ocean.bg_color, "#0d1117");
+
+    let reef = &config.card.themes[1];
+    assert_eq!(reef.title_color, "#4fc3f7");
+    assert_eq!(reef.bg_color, "#001f3f");
+}
+
+#[test]
+fn test_apply_yaml_invalid_theme_color() {
+    let mut config = Config::default();
+    let result = config.apply_yaml(
+        r#"
+themes:
+  broken:
+    bg_color: "not-a-color"
+"#,
+    );
+
+    assert!(matches!(result, Err(ConfigError::Theme { name, .. }) if name == "broken"));
+}
+
+#[test]
+fn test_apply_yaml_unquoted_hex_color() {
+    // An unquoted `#` starts a YAML comment, leaving the value empty
+    let mut config = Config::default();
+    let result = config.apply_yaml("themes:\n  broken:\n    bg_color: #000000\n");
+
+    assert!(matches!(result, Err(ConfigError::Invalid { .. })));
+}
+
+#[test]
+fn test_apply_yaml_empty_and_malformed() {
+    let mut config = Config::default();
+    assert!(config.apply_yaml("").is_ok());
+    assert!(config.card.themes.is_empty());
+
+    assert!(matches!(
+        config.apply_yaml("themes: [unclosed"),
+        Err(ConfigError::Parse(_))
+    ));
+}
+
+#[test]
+fn test_apply_yaml_cache() {
+    let mut config = Config::default();
+    assert!(config.cache.is_none());
+
+    config
+        .apply_yaml("cache:\n  path: /var/cache/glim\n  capacity: 512\n")
+        .unwrap();
+    let cache = config.cache.unwrap();
+    assert_eq!(cache.disk_path, "/var/cache/glim");
+    assert_eq!(cache.disk_capacity, 512 * 1024 * 1024);
+
+    let mut config = Config::default();
+    config.apply_yaml("cache:\n  path: /tmp/glim\n").unwrap();
+    assert_eq!(
+        config.cache.unwrap().disk_capacity,
+        DEFAULT_CACHE_CAPACITY_MIB * 1024 * 1024
+    );
+
+    for source in [
+        "cache: /tmp/glim\n",
+        "cache:\n  capacity: 512\n",
+        "cache:\n  path: /tmp/glim\n  capacity: 0\n",
+    ] {
+        let mut config = Config::default();
+        assert!(
+            matches!(config.apply_yaml(source), Err(ConfigError::Invalid { .. })),
+            "{source}"
+        );
+    }
+}
+
+#[test]
+fn test_load_file_missing() {
+    let mut config = Config::default();
+    let result = config.load_file(std::path::Path::new("/nonexistent/glim.yaml"));
+
+    assert!(matches!(result, Err(ConfigError::Read { .. })));
+}
+
+#[test]
+fn test_apply_yaml_templates() {
+    let mut config = Config::default();
+    config
+        .apply_yaml("template: compact\ntemplates: /etc/glim/templates\n")
+        .expect("Template settings should parse");
+
+    assert_eq!(config.card.default_template.as_deref(), Some("compact"));
+    assert_eq!(
+        config.card.templates_dir,
+        Some(PathBuf::from("/etc/glim/templates"))
+    );
+}
+
+#[test]
+fn test_cli_templates_dir_overrides_config_file() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("glim.yaml");
+    std::fs::write(&path, "templates: /from/file\n").unwrap();
+
+    let overrides = CliOverrides::from_cli_args(None, None).with_config_file(Some(path.clone()));
+    let config = Config::try_load(Some(overrides)).unwrap();
+    assert_eq!(config.card.templates_dir, Some(PathBuf::from("/from/file")));
+
+    let overrides = CliOverrides::from_cli_args(None, None)
+        .with_config_file(Some(path))
+        .with_templates_dir(Some(PathBuf::from("/from/cli")));
+    let config = Config::try_load(Some(overrides)).unwrap();
+    assert_eq!(config.card.templates_dir, Some(PathBuf::from("/from/cli")));
+}
@@ -1,8 +1,12 @@
+use glim::card::{Layout, Render};
 use glim::encode::{
-    create_encoder, AvifEncoder, Encoder, EncoderType, GifEncoder, IcoEncoder, ImageFormat,
-    JpegEncoder, PngEncoder, SvgEncoder, WebPEncoder,
+    create_encoder, Animation, AvifEncoder, Compression, EncodeOptions, Encoder, EncoderType,
+    GifEncoder, IcoEncoder, ImageFormat, JpegEncoder, PngEncoder, QualityRange, SvgEncoder,
+    WebPEncoder, ICO_SIZES, MAX_ANIMATION_FRAMES,
 };
+use image::AnimationDecoder;
 use std::io::Cursor;
+use std::time::Duration;
 
 #[tokio::test]
 async fn test_image_format_mime_types() {
@@ -53,7 +57,7 @@ async fn test_encoder_creation() {
     for (format, should_succeed) in test_cases {
         let encoder = create_encoder(format);
         let mut cursor = Cursor::new(Vec::new());
-        let result = encoder.encode("test", &mut cursor, None);
+        let result = encoder.encode("test", &mut cursor, &EncodeOptions::default());
         assert_eq!(result.is_ok(), should_succeed);
     }
 }
@@ -64,7 +68,7 @@ async fn test_svg_encoder() {
     let mut output = Cursor::new(Vec::new());
     let test_svg = "<svg><text>Hello World</text></svg>";
 
-    let result = encoder.encode(test_svg, &mut output, None);
+    let result = encoder.encode(test_svg, &mut output, &EncodeOptions::default());
     assert!(result.is_ok());
 
     let output_data = output.into_inner();
@@ -76,7 +80,11 @@ async fn test_png_encoder_creation() {
     let encoder = PngEncoder::new();
     let mut cursor = Cursor::new(Vec::new());
     assert!(encoder
-        .encode("<invalid>svg</invalid>", &mut cursor, None)
+        .encode(
+            "<invalid>svg</invalid>",
+            &mut cursor,
+            &EncodeOptions::default()
+        )
         .is_err());
 }
 
@@ -85,7 +93,11 @@ async fn test_webp_encoder_creation() {
     let encoder = WebPEncoder::new();
     let mut cursor = Cursor::new(Vec::new());
     assert!(encoder
-        .encode("<invalid>svg</invalid>", &mut cursor, None)
+        .encode(
+            "<invalid>svg</invalid>",
+            &mut cursor,
+            &EncodeOptions::default()
+        )
         .is_err());
 }
 
@@ -94,7 +106,11 @@ async fn test_jpeg_encoder_creation() {
     let encoder = JpegEncoder::new();
     let mut cursor = Cursor::new(Vec::new());
     assert!(encoder
-        .encode("<invalid>svg</invalid>", &mut cursor, None)
+        .encode(
+            "<invalid>svg</invalid>",
+            &mut cursor,
+            &EncodeOptions::default()
+        )
         .is_err());
 }
 
@@ -130,7 +146,11 @@ async fn test_ico_error_handling() {
 
 async fn test_single_encoder_error_handling(encoder: EncoderType, name: &str) {
     let mut output = Cursor::new(Vec::new());
-    let result = encoder.encode("<invalid>svg</invalid>", &mut output, None);
+    let result = encoder.encode(
+        "<invalid>svg</invalid>",
+        &mut output,
+        &EncodeOptions::default(),
+    );
 
     assert!(
         result.is_err(),
@@ -141,3 +161,550 @@ async fn test_single_encoder_error_handling(encoder: EncoderType, name: &str) {
     let error = result.unwrap_err();
     assert!(format!("{:?}", error).contains("Image"));
 }
+
+#[tokio::test]
+async fn test_social_layout_dimensions() {
+    let mut repository = glim::github::Repository {
+        name: "glim".to_string(),
+        ..Default::default()
+    };
+    repository.owner.login = "Xevion".to_string();
+    let options = glim::card::CardOptions {
+        template: "social".to_string(),
+        layout: Layout::Social,
+        ..Default::default()
+    };
+    let svg = glim::card::Card::new(repository, options).render().unwrap();
+
+    // Social previews are exactly 1280x640 regardless of the requested scale
+    let mut output = Cursor::new(Vec::new());
+    create_encoder(ImageFormat::Png)
+        .encode(&svg, &mut output, &Layout::Social.encode_options(Some(2.0)))
+        .unwrap();
+
+    let image = image::load_from_memory(output.get_ref()).unwrap();
+    assert_eq!((image.width(), image.height()), (1280, 640));
+}
+
+#[tokio::test]
+async fn test_badge_layout_dimensions() {
+    let repository = glim::github::Repository {
+        name: "glim".to_string(),
+        language: Some("Rust".to_string()),
+        ..Default::default()
+    };
+    let options = glim::card::CardOptions {
+        template: "badge".to_string(),
+        layout: Layout::Badge,
+        ..Default::default()
+    };
+    let svg = glim::card::Card::new(repository, options).render().unwrap();
+
+    // Badges scale but are rendered without the card margin
+    let mut output = Cursor::new(Vec::new());
+    create_encoder(ImageFormat::Png)
+        .encode(&svg, &mut output, &Layout::Badge.encode_options(Some(2.0)))
+        .unwrap();
+
+    let image = image::load_from_memory(output.get_ref()).unwrap();
+    assert_eq!((image.width(), image.height()), (500, 56));
+}
+
+/// Splits an ICO file into the sizes and PNG data of its entries.
+fn ico_entries(data: &[u8]) -> Vec<(u32, u32, &[u8])> {
+    let count = u16::from_le_bytes([data[4], data[5]]) as usize;
+    (0..count)
+        .map(|i| {
+            let entry = &data[6 + i * 16..6 + (i + 1) * 16];
+            let dimension = |byte: u8| if byte == 0 { 256 } else { u32::from(byte) };
+            let length = u32::from_le_bytes(entry[8..12].try_into().unwrap()) as usize;
+            let offset = u32::from_le_bytes(entry[12..16].try_into().unwrap()) as usize;
+            (
+                dimension(entry[0]),
+                dimension(entry[1]),
+                &data[offset..offset + length],
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_ico_entries() {
+    let square = r##"<svg xmlns="http://www.w3.org/2000/svg" width="64" height="64">
+        <rect width="64" height="64" rx="12" fill="#0969da"/>
+    </svg>"##;
+    let data = encode_with(ImageFormat::Ico, square, Layout::Icon.encode_options(None));
+
+    // Every size is a square PNG entry
+    let entries = ico_entries(&data);
+    assert_eq!(
+        entries.iter().map(|entry| entry.0).collect::<Vec<_>>(),
+        ICO_SIZES
+    );
+    for (width, height, png) in entries {
+        assert_eq!(width, height);
+        let image = image::load_from_memory_with_format(png, image::ImageFormat::Png).unwrap();
+        assert_eq!((image.width(), image.height()), (width, height));
+    }
+
+    // Wide images are fitted within each square and centered
+    let wide = r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100">
+        <rect width="200" height="100" fill="#ff0000"/>
+    </svg>"##;
+    let data = encode_with(ImageFormat::Ico, wide, EncodeOptions::default());
+    let (_, _, png) = ico_entries(&data)[0];
+    let image = image::load_from_memory(png).unwrap().into_rgba8();
+    assert_eq!(image.dimensions(), (16, 16));
+    assert_eq!(image.get_pixel(8, 1)[3], 0);
+    assert_eq!(image.get_pixel(8, 8), &image::Rgba([255, 0, 0, 255]));
+    assert_eq!(image.get_pixel(8, 14)[3], 0);
+}
+
+fn encode_to_image(format: ImageFormat, svg: &str) -> image::RgbaImage {
+    let mut output = Cursor::new(Vec::new());
+    create_encoder(format)
+        .encode(svg, &mut output, &EncodeOptions::default())
+        .unwrap();
+    image::load_from_memory(output.get_ref())
+        .unwrap()
+        .into_rgba8()
+}
+
+#[tokio::test]
+async fn test_gif_encoder_flat_colors() {
+    let mut repository = glim::github::Repository {
+        name: "glim".to_string(),
+        description: Some("Generate beautiful repository cards".to_string()),
+        language: Some("Rust".to_string()),
+        ..Default::default()
+    };
+    repository.owner.login = "Xevion".to_string();
+    let svg = glim::card::Card::new(repository, Default::default())
+        .render()
+        .unwrap();
+
+    let png = encode_to_image(ImageFormat::Png, &svg);
+    let gif = encode_to_image(ImageFormat::Gif, &svg);
+    assert_eq!(gif.dimensions(), png.dimensions());
+
+    // The transparent margin stays transparent
+    assert_eq!(gif.get_pixel(0, 0)[3], 0);
+
+    // Flat areas such as the card background keep their exact color
+    let (x, y) = (png.width() - 40, png.height() / 2);
+    assert_eq!(png.get_pixel(x, y)[3], 255);
+    assert_eq!(gif.get_pixel(x, y), png.get_pixel(x, y));
+}
+
+#[tokio::test]
+async fn test_gif_encoder_dithers_gradients() {
+    let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="400" height="100">
+        <defs><linearGradient id="g"><stop offset="0" stop-color="#ff0000"/><stop offset="0.5" stop-color="#00ff00"/><stop offset="1" stop-color="#0000ff"/></linearGradient></defs>
+        <rect width="400" height="50" fill="url(#g)"/>
+        <rect y="50" width="400" height="50" fill="#336699"/>
+    </svg>"##;
+
+    let png = encode_to_image(ImageFormat::Png, svg);
+    let gif = encode_to_image(ImageFormat::Gif, svg);
+
+    // The gradient has more colors than a GIF palette, but averages out close to the original
+    let average = |image: &image::RgbaImage, x: u32| {
+        let mut sum = [0u32; 3];
+        for dy in 0..8 {
+            for dx in 0..8 {
+                let pixel = image.get_pixel(x + dx, 40 + dy);
+                for c in 0..3 {
+                    sum[c] += pixel[c] as u32;
+                }
+            }
+        }
+        sum.map(|total| total / 64)
+    };
+    for x in [60, 180, 300] {
+        let (expected, actual) = (average(&png, x), average(&gif, x));
+        for c in 0..3 {
+            assert!(
+                expected[c].abs_diff(actual[c]) <= 6,
+                "{expected:?} vs {actual:?}"
+            );
+        }
+    }
+
+    // The flat half still keeps its exact color
+    assert_eq!(
+        gif.get_pixel(380, 90),
+        &image::Rgba([0x33, 0x66, 0x99, 255])
+    );
+}
+
+#[tokio::test]
+async fn test_gif_encoder_antialiased_edges() {
+    let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+        <rect x="10" y="10" width="80" height="80" rx="24" fill="#e06c75"/>
+    </svg>"##;
+
+    let gif = encode_to_image(ImageFormat::Gif, svg);
+
+    // Edge pixels are either transparent or the shape's own color, never darkened towards black
+    let fill = [0xe0u8, 0x6c, 0x75];
+    for pixel in gif.pixels().filter(|pixel| pixel[3] != 0) {
+        assert!(
+            (0..3).all(|c| pixel[c].abs_diff(fill[c]) <= 2),
+            "unexpected edge color {pixel:?}"
+        );
+    }
+}
+
+fn create_animation(count: usize, size: u32) -> Animation {
+    Animation {
+        frames: (0..count)
+            .map(|i| {
+                format!(
+                    r##"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}"><rect width="{size}" height="{size}" fill="rgb({}, 0, 0)"/></svg>"##,
+                    i * 255 / (count - 1).max(1)
+                )
+            })
+            .collect(),
+        duration: Duration::from_millis(300),
+    }
+}
+
+fn decode_frames(format: ImageFormat, data: Vec<u8>) -> Vec<image::Frame> {
+    let cursor = Cursor::new(data);
+    let frames = match format {
+        ImageFormat::Png => image::codecs::png::PngDecoder::new(cursor)
+            .unwrap()
+            .apng()
+            .unwrap()
+            .into_frames(),
+        ImageFormat::Gif => image::codecs::gif::GifDecoder::new(cursor)
+            .unwrap()
+            .into_frames(),
+        ImageFormat::WebP => image::codecs::webp::WebPDecoder::new(cursor)
+            .unwrap()
+            .into_frames(),
+        _ => unreachable!("{:?} has no animation decoder", format),
+    };
+    frames.collect_frames().unwrap()
+}
+
+/// Reads the frame count from an APNG's animation control chunk.
+fn apng_frame_count(data: &[u8]) -> u32 {
+    let start = data
+        .windows(4)
+        .position(|window| window == b"acTL")
+        .expect("missing acTL chunk")
+        + 4;
+    u32::from_be_bytes(data[start..start + 4].try_into().unwrap())
+}
+
+#[tokio::test]
+async fn test_animated_encoders() {
+    let animation = create_animation(4, 40);
+
+    for format in [ImageFormat::Png, ImageFormat::Gif, ImageFormat::WebP] {
+        assert!(format.supports_animation());
+        let mut output = Cursor::new(Vec::new());
+        create_encoder(format)
+            .encode_animation(&animation, &mut output, &EncodeOptions::default())
+            .unwrap();
+
+        let frames = decode_frames(format, output.into_inner());
+        assert_eq!(frames.len(), 4, "{:?}", format);
+
+        // Frames are spread evenly over the duration
+        let delay = Duration::from(frames[0].delay());
+        assert!(delay.abs_diff(Duration::from_millis(100)) <= Duration::from_millis(10));
+
+        // Each frame replaces the last, keeping the transparent margin
+        let first = frames[0].buffer();
+        let last = frames[3].buffer();
+        assert_eq!(last.get_pixel(0, 0)[3], 0, "{:?}", format);
+        assert_eq!(first.get_pixel(40, 40), &image::Rgba([0, 0, 0, 255]));
+        assert_eq!(last.get_pixel(40, 40), &image::Rgba([255, 0, 0, 255]));
+    }
+}
+
+#[tokio::test]
+async fn test_animation_limits() {
+    // Frames beyond the cap are dropped evenly, keeping the last
+    let mut output = Cursor::new(Vec::new());
+    create_encoder(ImageFormat::Gif)
+        .encode_animation(
+            &create_animation(45, 20),
+            &mut output,
+            &EncodeOptions::default(),
+        )
+        .unwrap();
+    let frames = decode_frames(ImageFormat::Gif, output.into_inner());
+    assert_eq!(frames.len(), MAX_ANIMATION_FRAMES);
+    assert_eq!(
+        frames.last().unwrap().buffer().get_pixel(30, 30),
+        &image::Rgba([255, 0, 0, 255])
+    );
+
+    // Large cards get fewer frames to stay within the pixel budget; only the
+    // frame count matters, so skip the optimization pass and decoding
+    let mut output = Cursor::new(Vec::new());
+    create_encoder(ImageFormat::Png)
+        .encode_animation(
+            &create_animation(5, 1960),
+            &mut output,
+            &EncodeOptions {
+                compression: Some(Compression::Fast),
+                optimize: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(apng_frame_count(output.get_ref()), 4);
+
+    // Other formats can't be animated
+    for format in [
+        ImageFormat::Jpeg,
+        ImageFormat::Svg,
+        ImageFormat::Avif,
+        ImageFormat::Ico,
+    ] {
+        assert!(!format.supports_animation());
+        let result = create_encoder(format).encode_animation(
+            &create_animation(2, 20),
+            &mut Cursor::new(Vec::new()),
+            &EncodeOptions::default(),
+        );
+        assert!(format!("{:?}", result.unwrap_err()).contains("Animation"));
+    }
+}
+
+fn encode_with(format: ImageFormat, svg: &str, options: EncodeOptions) -> Vec<u8> {
+    let mut output = Cursor::new(Vec::new());
+    create_encoder(format)
+        .encode(svg, &mut output, &options)
+        .unwrap();
+    output.into_inner()
+}
+
+#[tokio::test]
+async fn test_quality_settings() {
+    let options = |quality: Option<u8>, lossless: Option<bool>| EncodeOptions {
+        quality,
+        lossless,
+        ..Default::default()
+    };
+    assert_eq!(
+        ImageFormat::Jpeg.quality_range(),
+        Some(QualityRange {
+            min: 1,
+            max: 100,
+            default: 90
+        })
+    );
+
+    // Quality must be within the format's range, for formats that have one
+    assert!(options(Some(50), None).check(ImageFormat::Jpeg).is_ok());
+    assert!(options(Some(0), None).check(ImageFormat::Jpeg).is_err());
+    assert!(options(Some(0), None).check(ImageFormat::WebP).is_ok());
+    assert!(options(Some(101), None).check(ImageFormat::Avif).is_err());
+    let error = options(Some(50), None).check(ImageFormat::Png).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Image generation error: Invalid quality settings: PNG has no quality setting"
+    );
+
+    // Lossless can only be chosen where the format allows it
+    assert!(options(None, Some(true)).check(ImageFormat::Png).is_ok());
+    assert!(options(None, Some(false)).check(ImageFormat::Png).is_err());
+    assert!(options(None, Some(true)).check(ImageFormat::Jpeg).is_err());
+    assert!(options(Some(80), Some(true))
+        .check(ImageFormat::WebP)
+        .is_err());
+
+    // WebP is lossless unless a quality is given or lossless is turned off
+    assert!(options(None, None).is_lossless(ImageFormat::WebP));
+    assert!(!options(Some(80), None).is_lossless(ImageFormat::WebP));
+    assert!(!options(None, Some(false)).is_lossless(ImageFormat::WebP));
+    assert_eq!(
+        options(None, Some(false)).quality_for(ImageFormat::WebP),
+        Some(80)
+    );
+
+    // Defaults share a cache key with unset options
+    assert_eq!(options(None, None).quality_key(ImageFormat::Jpeg), "");
+    assert_eq!(options(Some(90), None).quality_key(ImageFormat::Jpeg), "");
+    assert_eq!(
+        options(Some(75), None).quality_key(ImageFormat::Jpeg),
+        "~q75"
+    );
+    assert_eq!(options(None, Some(true)).quality_key(ImageFormat::WebP), "");
+    assert_eq!(
+        options(None, Some(false)).quality_key(ImageFormat::WebP),
+        "~q80"
+    );
+    assert_eq!(options(None, None).quality_key(ImageFormat::Png), "");
+}
+
+#[tokio::test]
+async fn test_lossy_encoding() {
+    let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100">
+        <defs><radialGradient id="g"><stop offset="0" stop-color="#ff8800"/><stop offset="1" stop-color="#0044ff"/></radialGradient></defs>
+        <rect width="200" height="100" fill="#ffffff"/>
+        <circle cx="60" cy="50" r="45" fill="url(#g)"/>
+        <text x="110" y="40" font-size="14">glim</text>
+        <text x="110" y="70" font-size="11">Generate cards</text>
+    </svg>"##;
+    let quality = |quality: u8| EncodeOptions {
+        quality: Some(quality),
+        ..Default::default()
+    };
+
+    // Lower JPEG quality gives smaller files
+    let high = encode_with(ImageFormat::Jpeg, svg, quality(95));
+    let low = encode_with(ImageFormat::Jpeg, svg, quality(20));
+    assert!(low.len() < high.len());
+
+    // Lossy WebP is smaller, but keeps flat areas exact
+    let lossless = encode_with(ImageFormat::WebP, svg, EncodeOptions::default());
+    let lossy = encode_with(ImageFormat::WebP, svg, quality(0));
+    assert!(lossy.len() < lossless.len());
+    let image = image::load_from_memory(&lossy).unwrap().into_rgba8();
+    assert_eq!(image.dimensions(), (240, 140));
+    assert_eq!(
+        image.get_pixel(200, 100),
+        &image::Rgba([255, 255, 255, 255])
+    );
+}
+
+/// Reads the color type and bit depth from a PNG's header.
+fn png_layout(data: &[u8]) -> (png::ColorType, png::BitDepth) {
+    let reader = png::Decoder::new(Cursor::new(data)).read_info().unwrap();
+    (reader.info().color_type, reader.info().bit_depth)
+}
+
+#[tokio::test]
+async fn test_png_optimization() {
+    let flat = r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100">
+        <rect width="200" height="100" fill="#0d1117"/>
+        <rect x="10" y="10" width="80" height="20" fill="#58a6ff"/>
+        <rect x="10" y="50" width="80" height="20" fill="#f1e05a"/>
+    </svg>"##;
+    let gray = r##"<svg xmlns="http://www.w3.org/2000/svg" width="400" height="100">
+        <defs><linearGradient id="g"><stop offset="0" stop-color="#000"/><stop offset="1" stop-color="#fff"/></linearGradient></defs>
+        <rect width="400" height="100" fill="url(#g)"/>
+    </svg>"##;
+    let faded = r##"<svg xmlns="http://www.w3.org/2000/svg" width="400" height="100">
+        <defs><linearGradient id="g"><stop offset="0" stop-color="#000"/><stop offset="1" stop-color="#fff" stop-opacity="0.2"/></linearGradient></defs>
+        <rect width="400" height="100" fill="url(#g)"/>
+    </svg>"##;
+    let colorful = r##"<svg xmlns="http://www.w3.org/2000/svg" width="400" height="100">
+        <defs>
+            <linearGradient id="h"><stop offset="0" stop-color="#ff0000"/><stop offset="1" stop-color="#00ff00"/></linearGradient>
+            <linearGradient id="v" x2="0" y2="1"><stop offset="0" stop-color="#0000ff" stop-opacity="0"/><stop offset="1" stop-color="#0000ff"/></linearGradient>
+        </defs>
+        <rect width="400" height="100" fill="url(#h)"/>
+        <rect width="400" height="100" fill="url(#v)"/>
+    </svg>"##;
+    let options = |padding: bool, optimize: bool| EncodeOptions {
+        padding,
+        optimize,
+        ..Default::default()
+    };
+
+    let test_cases = [
+        (flat, true, png::ColorType::Indexed, png::BitDepth::Two),
+        (flat, false, png::ColorType::Indexed, png::BitDepth::Two),
+        (gray, false, png::ColorType::Grayscale, png::BitDepth::Eight),
+        (
+            faded,
+            false,
+            png::ColorType::GrayscaleAlpha,
+            png::BitDepth::Eight,
+        ),
+        (colorful, false, png::ColorType::Rgb, png::BitDepth::Eight),
+        (colorful, true, png::ColorType::Rgba, png::BitDepth::Eight),
+    ];
+
+    for (svg, padding, color, depth) in test_cases {
+        let optimized = encode_with(ImageFormat::Png, svg, options(padding, true));
+        let plain = encode_with(ImageFormat::Png, svg, options(padding, false));
+        assert_eq!(png_layout(&optimized), (color, depth));
+        assert_eq!(
+            png_layout(&plain),
+            (png::ColorType::Rgba, png::BitDepth::Eight)
+        );
+
+        // Optimization never changes the decoded pixels
+        assert_eq!(
+            image::load_from_memory(&optimized).unwrap().into_rgba8(),
+            image::load_from_memory(&plain).unwrap().into_rgba8()
+        );
+        assert!(optimized.len() <= plain.len());
+    }
+
+    // Animated PNGs share one palette across their frames
+    let mut output = Cursor::new(Vec::new());
+    PngEncoder::new()
+        .encode_animation(&create_animation(3, 40), &mut output, &options(true, true))
+        .unwrap();
+    assert_eq!(png_layout(output.get_ref()).0, png::ColorType::Indexed);
+    assert_eq!(
+        decode_frames(ImageFormat::Png, output.into_inner()).len(),
+        3
+    );
+}
+
+#[tokio::test]
+async fn test_png_compression() {
+    let options = |compression: Option<Compression>, optimize: bool| EncodeOptions {
+        compression,
+        optimize,
+        ..Default::default()
+    };
+    assert_eq!(Compression::parse("BEST"), Some(Compression::Best));
+    assert_eq!(Compression::parse("max"), None);
+
+    // Compression levels only apply to PNG, and ICO's PNG entries
+    assert!(options(Some(Compression::Best), true)
+        .check(ImageFormat::Png)
+        .is_ok());
+    assert!(options(Some(Compression::Best), true)
+        .check(ImageFormat::Ico)
+        .is_ok());
+    let error = options(Some(Compression::Fast), true)
+        .check(ImageFormat::Jpeg)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Image generation error: Invalid compression setting: JPG has no compression setting"
+    );
+
+    // Non-default settings get their own cache key
+    assert_eq!(
+        options(Some(Compression::Default), true).quality_key(ImageFormat::Png),
+        ""
+    );
+    assert_eq!(
+        options(Some(Compression::Best), false).quality_key(ImageFormat::Png),
+        "~best~rgba"
+    );
+    assert_eq!(
+        options(Some(Compression::Best), false).quality_key(ImageFormat::Gif),
+        ""
+    );
+
+    let svg = create_animation(1, 200).frames.remove(0);
+    let fast = encode_with(
+        ImageFormat::Png,
+        &svg,
+        options(Some(Compression::Fast), false),
+    );
+    let best = encode_with(
+        ImageFormat::Png,
+        &svg,
+        options(Some(Compression::Best), false),
+    );
+    assert!(best.len() <= fast.len());
+    assert_eq!(
+        image::load_from_memory(&fast).unwrap().into_rgba8(),
+        image::load_from_memory(&best).unwrap().into_rgba8()
+    );
+}
@@ -1,5 +1,8 @@
 use glim::errors::GitHubError;
-use glim::github::{CacheEntry, Repository};
+use glim::github::{
+    is_valid_login, sort_languages, star_history_pages, top_languages, CacheEntry, CommitWeek,
+    Issue, LanguageBytes, LanguageCount, License, Organization, Release, Repository, User,
+};
 
 // Test fixtures
 fn create_test_repository() -> Repository {
@@ -9,6 +12,7 @@ fn create_test_repository() -> Repository {
         language: Some("Rust".to_string()),
         stargazers_count: 42,
         forks_count: 7,
+        ..Default::default()
     }
 }
 
@@ -35,6 +39,151 @@ async fn test_repository_deserialization() {
     assert_eq!(repo.forks_count, 7);
 }
 
+#[test]
+fn test_repository_details_deserialization() {
+    let json = r#"{
+        "name": "test-repo",
+        "description": null,
+        "language": "Rust",
+        "stargazers_count": 42,
+        "forks_count": 7,
+        "open_issues_count": 3,
+        "watchers_count": 42,
+        "subscribers_count": 5,
+        "license": { "key": "mit", "name": "MIT License", "spdx_id": "MIT" },
+        "topics": ["cli", "svg"],
+        "default_branch": "main",
+        "pushed_at": "2025-03-01T12:00:00Z"
+    }"#;
+
+    let repo: Repository = serde_json::from_str(json).unwrap();
+
+    assert_eq!(repo.open_issues_count, 3);
+    // Watchers come from subscribers_count, not the star-mirroring watchers_count
+    assert_eq!(repo.subscribers_count, 5);
+    assert_eq!(repo.license.as_ref().and_then(License::spdx), Some("MIT"));
+    assert_eq!(repo.topics, vec!["cli", "svg"]);
+    assert_eq!(repo.default_branch, "main");
+    assert_eq!(
+        repo.pushed_at.map(|pushed_at| pushed_at.to_rfc3339()),
+        Some("2025-03-01T12:00:00+00:00".to_string())
+    );
+
+    // Status flags default to false when missing
+    assert!(!repo.archived && !repo.fork && !repo.is_template);
+    assert_eq!(repo.parent, None);
+    assert_eq!(repo.mirror_url, None);
+
+    let unidentified = License {
+        spdx_id: Some("NOASSERTION".to_string()),
+    };
+    assert_eq!(unidentified.spdx(), None);
+}
+
+#[test]
+fn test_repository_status_deserialization() {
+    let json = r#"{
+        "name": "fork-repo",
+        "description": null,
+        "language": null,
+        "stargazers_count": 0,
+        "forks_count": 0,
+        "archived": true,
+        "fork": true,
+        "parent": { "full_name": "octocat/upstream", "name": "upstream" },
+        "is_template": true,
+        "mirror_url": "https://example.com/upstream.git"
+    }"#;
+
+    let repo: Repository = serde_json::from_str(json).unwrap();
+
+    assert!(repo.archived && repo.fork && repo.is_template);
+    assert_eq!(
+        repo.parent.map(|parent| parent.full_name),
+        Some("octocat/upstream".to_string())
+    );
+    assert_eq!(
+        repo.mirror_url.as_deref(),
+        Some("https://example.com/upstream.git")
+    );
+}
+
+#[test]
+fn test_release_deserialization() {
+    let json = r#"{
+        "tag_name": "v1.2.0",
+        "name": "Faster rendering",
+        "draft": false,
+        "prerelease": false,
+        "published_at": "2025-01-05T12:00:00Z",
+        "assets": [
+            { "name": "glim-linux.tar.gz", "download_count": 1200 },
+            { "name": "glim-macos.tar.gz", "download_count": 34 }
+        ]
+    }"#;
+
+    let release: Release = serde_json::from_str(json).unwrap();
+
+    assert_eq!(release.tag_name, "v1.2.0");
+    assert_eq!(release.name.as_deref(), Some("Faster rendering"));
+    assert_eq!(
+        release.published_at.map(|date| date.to_rfc3339()),
+        Some("2025-01-05T12:00:00+00:00".to_string())
+    );
+    assert_eq!(release.assets.len(), 2);
+    assert_eq!(release.download_count(), 1234);
+
+    // Releases without a title or assets
+    let release: Release =
+        serde_json::from_str(r#"{ "tag_name": "v0.1.0", "name": null }"#).unwrap();
+    assert_eq!(release.name, None);
+    assert_eq!(release.download_count(), 0);
+}
+
+#[test]
+fn test_issue_deserialization() {
+    let json = r#"{
+        "number": 42,
+        "title": "Cards render blank on Safari",
+        "state": "closed",
+        "state_reason": "not_planned",
+        "user": { "login": "octocat", "avatar_url": "https://avatars.githubusercontent.com/u/583231" },
+        "labels": [{ "id": 1, "name": "bug", "color": "d73a4a", "default": true }],
+        "comments": 3,
+        "created_at": "2025-01-01T00:00:00Z",
+        "updated_at": "2025-01-02T00:00:00Z"
+    }"#;
+
+    let issue: Issue = serde_json::from_str(json).unwrap();
+
+    assert_eq!(issue.number, 42);
+    assert_eq!(issue.state_reason.as_deref(), Some("not_planned"));
+    assert_eq!(
+        issue.user.as_ref().map(|user| user.login.as_str()),
+        Some("octocat")
+    );
+    assert_eq!(issue.labels[0].name, "bug");
+    assert_eq!(issue.labels[0].color, "d73a4a");
+    assert_eq!(issue.comments, 3);
+    assert!(!issue.is_pull_request());
+
+    // The issues endpoint marks pull requests, with their draft and merged state
+    let json = r#"{
+        "number": 7,
+        "title": "Add release cards",
+        "state": "closed",
+        "draft": false,
+        "user": null,
+        "pull_request": { "url": "https://api.github.com/repos/o/r/pulls/7", "merged_at": "2025-01-03T00:00:00Z" }
+    }"#;
+
+    let pull_request: Issue = serde_json::from_str(json).unwrap();
+
+    assert!(pull_request.is_pull_request());
+    assert!(pull_request.is_merged());
+    assert!(pull_request.user.is_none());
+}
+
 #[tokio::test]
 async fn test_repository_with_null_fields() {
     let json = r#"{
@@ -494,3 +643,157 @@ async fn test_cache_concurrent_access() {
         assert!(result.unwrap());
     }
 }
+
+#[tokio::test]
+async fn test_user_deserialization() {
+    let json = r#"{
+        "login": "octocat",
+        "name": "The Octocat",
+        "avatar_url": "https://avatars.githubusercontent.com/u/583231?v=4",
+        "bio": null,
+        "followers": 4200,
+        "public_repos": 8
+    }"#;
+
+    let user: User = serde_json::from_str(json).unwrap();
+
+    assert_eq!(user.login, "octocat");
+    assert_eq!(user.name, Some("The Octocat".to_string()));
+    assert_eq!(user.bio, None);
+    assert_eq!(user.followers, 4200);
+    assert_eq!(user.public_repos, 8);
+    assert!(user.top_languages.is_empty());
+}
+
+#[tokio::test]
+async fn test_org_deserialization() {
+    let json = r#"{
+        "login": "github",
+        "name": "GitHub",
+        "description": "How people build software.",
+        "avatar_url": "https://avatars.githubusercontent.com/u/9919?v=4",
+        "public_repos": 512,
+        "followers": 40000
+    }"#;
+
+    let organization: Organization = serde_json::from_str(json).unwrap();
+
+    assert_eq!(organization.login, "github");
+    assert_eq!(organization.name, Some("GitHub".to_string()));
+    assert_eq!(
+        organization.description,
+        Some("How people build software.".to_string())
+    );
+    assert_eq!(organization.public_repos, 512);
+    // Aggregates are filled from the repository listing, not the profile
+    assert_eq!(organization.stars, 0);
+    assert!(organization.top_languages.is_empty());
+}
+
+#[test]
+fn test_top_languages() {
+    let languages = top_languages([
+        "Rust", "Python", "Rust", "Go", "Python", "Rust", "C", "Shell", "HTML",
+    ]);
+
+    let names: Vec<&str> = languages.iter().map(|l| l.name.as_str()).collect();
+    // Most used first, ties broken alphabetically, at most five
+    assert_eq!(names, vec!["Rust", "Python", "C", "Go", "HTML"]);
+    assert_eq!(
+        languages[0],
+        LanguageCount {
+            name: "Rust".to_string(),
+            repositories: 3
+        }
+    );
+    assert!(top_languages([]).is_empty());
+}
+
+#[test]
+fn test_sort_languages() {
+    let languages = sort_languages([
+        ("Shell".to_string(), 300),
+        ("Rust".to_string(), 52_000),
+        ("C".to_string(), 0),
+        ("Dockerfile".to_string(), 300),
+    ]);
+
+    // Largest first, ties broken alphabetically, empty languages dropped
+    assert_eq!(
+        languages,
+        vec![
+            LanguageBytes {
+                name: "Rust".to_string(),
+                bytes: 52_000
+            },
+            LanguageBytes {
+                name: "Dockerfile".to_string(),
+                bytes: 300
+            },
+            LanguageBytes {
+                name: "Shell".to_string(),
+                bytes: 300
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_commit_activity_deserialization() {
+    let json = r#"[
+        {"days": [0, 3, 26, 20, 39, 1, 0], "total": 89, "week": 1336280400},
+        {"days": [0, 0, 0, 0, 0, 0, 0], "total": 0, "week": 1336885200}
+    ]"#;
+
+    let weeks: Vec<CommitWeek> = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        weeks,
+        vec![
+            CommitWeek {
+                week: 1336280400,
+                total: 89
+            },
+            CommitWeek {
+                week: 1336885200,
+                total: 0
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_star_history_pages() {
+    assert!(star_history_pages(0).is_empty());
+    assert_eq!(star_history_pages(1), vec![1]);
+    assert_eq!(star_history_pages(250), vec![1, 2, 3]);
+    assert_eq!(star_history_pages(1_500), (1..=15).collect::<Vec<_>>());
+
+    // Larger repositories are sampled evenly from the first to the last page
+    let pages = star_history_pages(1_501);
+    assert_eq!(pages.len(), 15);
+    assert_eq!((pages[0], pages[14]), (1, 16));
+    assert!(pages.windows(2).all(|pair| pair[0] < pair[1]));
+
+    // Sampling stops at the last page the API serves
+    let pages = star_history_pages(100_000);
+    assert_eq!(pages.len(), 15);
+    assert_eq!((pages[0], pages[14]), (1, 400));
+}
+
+#[test]
+fn test_is_valid_login() {
+    for login in ["octocat", "Xevion", "a", "my-org-2"] {
+        assert!(is_valid_login(login), "{} should be valid", login);
+    }
+    for login in [
+        "",
+        "-octocat",
+        "octocat-",
+        "octo--cat",
+        "octo/cat",
+        "..",
+        &"a".repeat(40),
+    ] {
+        assert!(!is_valid_login(login), "{} should be invalid", login);
+    }
+}
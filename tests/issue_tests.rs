@@ -0,0 +1,187 @@
+use chrono::{TimeZone, Utc};
+use glim::card::{CardOptions, Render};
+use glim::github::{Issue, Label, Owner, PullRequestRef, Repository};
+use glim::issue::{IssueCard, IssueState};
+use glim::template::ISSUE_TEMPLATE;
+
+// Test fixtures
+fn create_test_repository() -> Repository {
+    Repository {
+        name: "glim".to_string(),
+        owner: Owner {
+            login: "Xevion".to_string(),
+            avatar_url: String::new(),
+        },
+        ..Default::default()
+    }
+}
+
+fn create_test_issue() -> Issue {
+    Issue {
+        number: 42,
+        title: "Cards render blank on Safari".to_string(),
+        state: "open".to_string(),
+        user: Some(Owner {
+            login: "octocat".to_string(),
+            avatar_url: String::new(),
+        }),
+        labels: vec![
+            Label {
+                name: "bug".to_string(),
+                color: "d73a4a".to_string(),
+            },
+            Label {
+                name: "good first issue".to_string(),
+                color: "7057ff".to_string(),
+            },
+            Label {
+                name: "docs".to_string(),
+                color: "fef2c0".to_string(),
+            },
+        ],
+        comments: 1234,
+        created_at: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).single(),
+        ..Default::default()
+    }
+}
+
+fn create_test_card(issue: Issue) -> IssueCard {
+    let options = CardOptions {
+        template: ISSUE_TEMPLATE.to_string(),
+        ..Default::default()
+    };
+    IssueCard::new(create_test_repository(), issue, options)
+        .at(Utc.with_ymd_and_hms(2025, 1, 4, 12, 0, 0).unwrap())
+}
+
+fn pull_request(issue: Issue, merged: bool) -> Issue {
+    Issue {
+        pull_request: Some(PullRequestRef {
+            merged_at: merged.then(|| Utc.with_ymd_and_hms(2025, 1, 2, 0, 0, 0).unwrap()),
+        }),
+        ..issue
+    }
+}
+
+#[test]
+fn test_render_issue_fields() {
+    let svg = create_test_card(create_test_issue()).render().unwrap();
+
+    assert!(svg.contains(">Open</text>"));
+    assert!(svg.contains("Xevion/glim #42"));
+    assert!(svg.contains(">Cards render blank on Safari</tspan>"));
+    assert!(svg.contains(">octocat</tspan> opened 3 days ago"));
+    assert!(svg.contains(">1.2k</text>"));
+    assert!(svg.contains(">good first issue</text>"));
+    assert!(svg.contains("#1f883d"));
+    assert!(!svg.contains("{{"));
+}
+
+#[test]
+fn test_issue_states() {
+    let state = |issue: &Issue| IssueState::of(issue);
+    let closed = |reason: Option<&str>| Issue {
+        state: "closed".to_string(),
+        state_reason: reason.map(str::to_string),
+        ..create_test_issue()
+    };
+
+    assert_eq!(state(&create_test_issue()), IssueState::Open);
+    assert_eq!(state(&closed(Some("completed"))), IssueState::Closed);
+    assert_eq!(state(&closed(Some("not_planned"))), IssueState::NotPlanned);
+
+    let open_pull = pull_request(create_test_issue(), false);
+    let draft = Issue {
+        draft: true,
+        ..open_pull.clone()
+    };
+    assert_eq!(state(&open_pull), IssueState::Open);
+    assert_eq!(state(&draft), IssueState::Draft);
+    assert_eq!(state(&pull_request(closed(None), true)), IssueState::Merged);
+    assert_eq!(
+        state(&pull_request(closed(None), false)),
+        IssueState::Closed
+    );
+
+    // Closed issues are purple like merged pull requests; closed pull requests are red
+    assert_eq!(IssueState::Closed.color(false), "#8250df");
+    assert_eq!(IssueState::Closed.color(true), "#cf222e");
+    assert_eq!(IssueState::Closed.icon(true), "pull_closed");
+
+    let card = create_test_card(draft);
+    let context = card.context();
+    assert!(context.is_pull_request);
+    assert_eq!(context.state.label, "Draft");
+    assert_eq!(context.state.icon, "pull_draft");
+}
+
+#[test]
+fn test_label_colors() {
+    let card = create_test_card(create_test_issue());
+    let context = card.context();
+
+    let colors: Vec<(&str, &str)> = context
+        .labels
+        .iter()
+        .map(|label| (label.color.as_str(), label.text_color))
+        .collect();
+    assert_eq!(
+        colors,
+        vec![
+            ("#d73a4a", "#ffffff"),
+            ("#7057ff", "#ffffff"),
+            ("#fef2c0", "#1f2328")
+        ]
+    );
+    assert!(context
+        .labels
+        .windows(2)
+        .all(|pair| pair[0].x + pair[0].width < pair[1].x));
+}
+
+#[test]
+fn test_layout_follows_title_and_labels() {
+    let card = create_test_card(create_test_issue());
+    let context = card.context();
+    assert_eq!(context.title_lines.len(), 1);
+    assert_eq!(context.labels_y, 82.0);
+    assert_eq!(context.layout.stats_y, 126.0);
+
+    // Without labels the footer moves up
+    let issue = Issue {
+        labels: Vec::new(),
+        ..create_test_issue()
+    };
+    let card = create_test_card(issue);
+    assert_eq!(card.context().layout.stats_y, 98.0);
+
+    // Long titles wrap, clamped to three lines
+    let issue = Issue {
+        title: "word ".repeat(100),
+        ..create_test_issue()
+    };
+    let card = create_test_card(issue);
+    let context = card.context();
+    assert_eq!(context.title_lines.len(), 3);
+    assert!(context.title_lines[2].ends_with('…'));
+    assert!(context.layout.stats_y > 126.0);
+}
+
+#[test]
+fn test_render_escapes_issue_fields() {
+    let issue = Issue {
+        title: "Escape <script> & friends".to_string(),
+        labels: vec![Label {
+            name: "<label>".to_string(),
+            color: "not a color".to_string(),
+        }],
+        ..create_test_issue()
+    };
+
+    let svg = create_test_card(issue).render().unwrap();
+
+    assert!(svg.contains("Escape &lt;script&gt; &amp; friends"));
+    assert!(svg.contains("&lt;label&gt;"));
+    assert!(svg.contains("#ededed"));
+    assert!(!svg.contains("<script>"));
+}
@@ -0,0 +1,178 @@
+use glim::card::{CardOptions, Render};
+use glim::github::{top_languages, Organization, User};
+use glim::profile::{OrgCard, UserCard};
+use glim::template::{ORG_TEMPLATE, USER_TEMPLATE};
+
+// Test fixtures
+fn create_test_user() -> User {
+    User {
+        login: "octocat".to_string(),
+        name: Some("The Octocat".to_string()),
+        bio: Some("Lives in the cloud".to_string()),
+        followers: 4200,
+        public_repos: 8,
+        top_languages: top_languages(["Rust", "Rust", "Python"]),
+        ..Default::default()
+    }
+}
+
+fn create_test_org() -> Organization {
+    Organization {
+        login: "github".to_string(),
+        name: Some("GitHub".to_string()),
+        description: Some("How people build software.".to_string()),
+        public_repos: 512,
+        stars: 123_456,
+        top_languages: top_languages(["Go", "Ruby", "Ruby"]),
+        ..Default::default()
+    }
+}
+
+fn create_test_org_card(organization: Organization) -> OrgCard {
+    let options = CardOptions {
+        template: ORG_TEMPLATE.to_string(),
+        ..Default::default()
+    };
+    OrgCard::new(organization, options)
+}
+
+fn create_test_card(user: User) -> UserCard {
+    let options = CardOptions {
+        template: USER_TEMPLATE.to_string(),
+        ..Default::default()
+    };
+    UserCard::new(user, options)
+}
+
+#[test]
+fn test_render_user_fields() {
+    let svg = create_test_card(create_test_user()).render().unwrap();
+
+    assert!(svg.contains(">The Octocat</text>"));
+    assert!(svg.contains("@octocat"));
+    assert!(svg.contains("Lives in the cloud"));
+    assert!(svg.contains("4.2k followers"));
+    assert!(svg.contains("8 repositories"));
+    assert!(svg.contains(">Rust</text>"));
+    assert!(svg.contains(">Python</text>"));
+    assert!(svg.contains("#dea584"));
+    assert!(!svg.contains("{{"));
+
+    usvg::Tree::from_str(&svg, &usvg::Options::default()).expect("Rendered card should parse");
+}
+
+#[test]
+fn test_name_falls_back_to_login() {
+    let mut user = create_test_user();
+    user.name = Some("  ".to_string());
+
+    let card = create_test_card(user);
+    let context = card.context();
+    assert_eq!(context.name, "octocat");
+    assert_eq!(context.display_name, "octocat");
+}
+
+#[test]
+fn test_languages_fit_one_row() {
+    let mut user = create_test_user();
+    user.top_languages = top_languages([
+        "Jupyter Notebook",
+        "Jupyter Notebook",
+        "Visual Basic .NET",
+        "Visual Basic .NET",
+        "Standard ML",
+        "TypeScript",
+        "JavaScript",
+    ]);
+
+    let card = create_test_card(user);
+    let context = card.context();
+    assert!(!context.languages.is_empty());
+    assert!(context.languages.len() < 5);
+    assert_eq!(context.languages[0].x, 0.0);
+    assert!(context
+        .languages
+        .windows(2)
+        .all(|pair| pair[0].x < pair[1].x));
+}
+
+#[test]
+fn test_render_escapes_user_fields() {
+    let mut user = create_test_user();
+    user.name = Some("<b>Octo</b>".to_string());
+    user.bio = Some("Tom & Jerry </text><svg onload=x>\u{1b}".to_string());
+
+    let svg = create_test_card(user).render().unwrap();
+
+    assert!(svg.contains("&lt;b&gt;Octo&lt;/b&gt;"));
+    assert!(svg.contains("Tom &amp; Jerry"));
+    assert!(!svg.contains("<svg onload"));
+    assert!(!svg.contains('\u{1b}'));
+    usvg::Tree::from_str(&svg, &usvg::Options::default()).expect("Rendered card should parse");
+}
+
+#[test]
+fn test_height_follows_bio_and_languages() {
+    let mut user = create_test_user();
+    user.bio = None;
+    user.top_languages.clear();
+    let short = create_test_card(user.clone()).context().layout;
+
+    user.top_languages = top_languages(["Rust"]);
+    let with_languages = create_test_card(user.clone()).context().layout;
+
+    user.bio = Some("lorem ipsum dolor sit amet ".repeat(20));
+    let card = create_test_card(user);
+    let long = card.context();
+
+    assert!(short.height < with_languages.height);
+    assert!(with_languages.height < long.layout.height);
+    assert_eq!(long.bio_lines.len(), 3);
+    assert!(long.bio_lines[2].ends_with('…'));
+}
+
+#[test]
+fn test_render_org_fields() {
+    let svg = create_test_org_card(create_test_org()).render().unwrap();
+
+    assert!(svg.contains(">GitHub</text>"));
+    assert!(svg.contains("@github"));
+    assert!(svg.contains("How people build software."));
+    assert!(svg.contains("512 repositories"));
+    assert!(svg.contains("123k stars"));
+    assert!(svg.contains(">Ruby</text>"));
+    assert!(svg.contains(">Go</text>"));
+    assert!(!svg.contains("{{"));
+
+    usvg::Tree::from_str(&svg, &usvg::Options::default()).expect("Rendered card should parse");
+}
+
+#[test]
+fn test_render_org_truncated_stars() {
+    let organization = Organization {
+        truncated: true,
+        ..create_test_org()
+    };
+    let svg = create_test_org_card(organization).render().unwrap();
+
+    // A partial total is marked rather than presented as complete
+    assert!(svg.contains("123k+ stars"));
+}
+
+#[test]
+fn test_org_matches_user_geometry() {
+    let mut organization = create_test_org();
+    organization.name = None;
+    organization.description = Some("Lives in the cloud".to_string());
+    organization.top_languages = top_languages(["Rust", "Rust", "Python"]);
+
+    let org_card = create_test_org_card(organization);
+    let org = org_card.context();
+    let user_card = create_test_card(create_test_user());
+    let user = user_card.context();
+
+    assert_eq!(org.display_name, "github");
+    assert_eq!(org.layout.height, user.layout.height);
+    assert_eq!(org.layout.stats_y, user.layout.stats_y);
+    assert_eq!(org.languages_y, user.languages_y);
+}
@@ -0,0 +1,125 @@
+use chrono::{TimeZone, Utc};
+use glim::card::{CardOptions, Render};
+use glim::github::{Owner, Release, ReleaseAsset, Repository};
+use glim::release::ReleaseCard;
+use glim::template::RELEASE_TEMPLATE;
+
+// Test fixtures
+fn create_test_repository() -> Repository {
+    Repository {
+        name: "glim".to_string(),
+        owner: Owner {
+            login: "Xevion".to_string(),
+            avatar_url: String::new(),
+        },
+        ..Default::default()
+    }
+}
+
+fn create_test_release() -> Release {
+    Release {
+        tag_name: "v1.2.0".to_string(),
+        name: Some("Faster rendering".to_string()),
+        published_at: Utc.with_ymd_and_hms(2025, 1, 5, 12, 0, 0).single(),
+        assets: vec![
+            ReleaseAsset {
+                name: "glim-linux.tar.gz".to_string(),
+                download_count: 1200,
+            },
+            ReleaseAsset {
+                name: "glim-macos.tar.gz".to_string(),
+                download_count: 34,
+            },
+        ],
+    }
+}
+
+fn create_test_card(release: Option<Release>) -> ReleaseCard {
+    let options = CardOptions {
+        template: RELEASE_TEMPLATE.to_string(),
+        ..Default::default()
+    };
+    ReleaseCard::new(create_test_repository(), release, options)
+}
+
+#[test]
+fn test_render_release_fields() {
+    let svg = create_test_card(Some(create_test_release()))
+        .render()
+        .unwrap();
+
+    assert!(svg.contains(">Xevion/glim</text>"));
+    assert!(svg.contains("Latest release"));
+    assert!(svg.contains(">v1.2.0</text>"));
+    assert!(svg.contains(">Faster rendering</text>"));
+    assert!(svg.contains(">Jan 5, 2025</text>"));
+    assert!(svg.contains(">2 assets</text>"));
+    assert!(svg.contains(">1.2k downloads</text>"));
+    assert!(!svg.contains("{{"));
+}
+
+#[test]
+fn test_stats_follow_title() {
+    let card = create_test_card(Some(create_test_release()));
+    let context = card.context();
+
+    assert_eq!(context.layout.stats_y, 150.0);
+    assert_eq!(context.layout.height, 175);
+    assert_eq!(
+        context
+            .stats
+            .iter()
+            .map(|stat| stat.kind)
+            .collect::<Vec<_>>(),
+        vec!["published", "assets", "downloads"]
+    );
+    assert_eq!(context.stats[0].x, 0.0);
+    assert!(context.stats[1].x > context.stats[0].x);
+
+    // A title matching the tag name is not repeated, and the stats move up
+    let mut release = create_test_release();
+    release.name = Some("v1.2.0".to_string());
+    release.published_at = None;
+    release.assets.truncate(1);
+    let card = create_test_card(Some(release));
+    let context = card.context();
+
+    assert_eq!(context.title, "");
+    assert_eq!(context.layout.stats_y, 128.0);
+    assert_eq!(
+        context
+            .stats
+            .iter()
+            .map(|stat| stat.label.as_str())
+            .collect::<Vec<_>>(),
+        vec!["1 asset", "1.2k downloads"]
+    );
+}
+
+#[test]
+fn test_render_without_releases() {
+    let card = create_test_card(None);
+    let context = card.context();
+
+    assert!(!context.has_release);
+    assert!(context.stats.is_empty());
+    assert_eq!(context.layout.height, 80);
+
+    let svg = card.render().unwrap();
+    assert!(svg.contains(">Xevion/glim</text>"));
+    assert!(svg.contains("No releases yet"));
+    assert!(!svg.contains("class=\"tag\""));
+}
+
+#[test]
+fn test_render_escapes_release_fields() {
+    let mut release = create_test_release();
+    release.tag_name = "<v2>".to_string();
+    release.name = Some("Fixes & <improvements>".to_string());
+
+    let svg = create_test_card(Some(release)).render().unwrap();
+
+    assert!(svg.contains("&lt;v2&gt;"));
+    assert!(svg.contains("Fixes &amp; &lt;improvements&gt;"));
+    assert!(!svg.contains("<v2>"));
+}
@@ -105,6 +105,23 @@ fn test_error_response_structure() {
     assert!(json.contains("404"));
 }
 
+#[test]
+fn test_format_count() {
+    let test_cases = [
+        ("820", "820"),
+        ("1200", "1.2k"),
+        ("12345", "12k"),
+        ("999999", "999k"),
+        ("2500000", "2.5M"),
+        ("12345678", "12M"),
+        ("n/a", "n/a"),
+    ];
+
+    for (count, expected) in test_cases {
+        assert_eq!(image::format_count(count), expected);
+    }
+}
+
 #[test]
 fn test_parse_scale_parameter() {
     use glim::server::{parse_scale_parameter, ImageQuery};
@@ -113,12 +130,14 @@ fn test_parse_scale_parameter() {
     let query = ImageQuery {
         scale: Some("1.5".to_string()),
         s: None,
+        ..Default::default()
     };
     assert_eq!(parse_scale_parameter(&query), Some(1.5));
 
     let query = ImageQuery {
         scale: None,
         s: Some("2.0".to_string()),
+        ..Default::default()
     };
     assert_eq!(parse_scale_parameter(&query), Some(2.0));
 
@@ -126,6 +145,7 @@ fn test_parse_scale_parameter() {
     let query = ImageQuery {
         scale: None,
         s: Some("1.2".to_string()),
+        ..Default::default()
     };
     assert_eq!(parse_scale_parameter(&query), Some(1.2));
 
@@ -133,18 +153,21 @@ fn test_parse_scale_parameter() {
     let query = ImageQuery {
         scale: Some("0.05".to_string()), // Below minimum - gets clamped to 0.1
         s: None,
+        ..Default::default()
     };
     assert_eq!(parse_scale_parameter(&query), Some(0.1));
 
     let query = ImageQuery {
         scale: Some("12345678901".to_string()), // Too long after trimming (>10 chars)
         s: None,
+        ..Default::default()
     };
     assert_eq!(parse_scale_parameter(&query), None);
 
     let query = ImageQuery {
         scale: Some("abc".to_string()), // Invalid number
         s: None,
+        ..Default::default()
     };
     assert_eq!(parse_scale_parameter(&query), None);
 
@@ -152,6 +175,7 @@ fn test_parse_scale_parameter() {
     let query = ImageQuery {
         scale: None,
         s: None,
+        ..Default::default()
     };
     assert_eq!(parse_scale_parameter(&query), None);
 }
@@ -164,6 +188,7 @@ fn test_scale_parameter_length_validation() {
     let query = ImageQuery {
         scale: Some("1.2000".to_string()),
         s: None,
+        ..Default::default()
     };
     assert_eq!(parse_scale_parameter(&query), Some(1.2));
 
@@ -171,6 +196,7 @@ fn test_scale_parameter_length_validation() {
     let query = ImageQuery {
         scale: Some("1.2345678901".to_string()),
         s: None,
+        ..Default::default()
     };
     assert_eq!(parse_scale_parameter(&query), None);
 }
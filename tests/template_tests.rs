@@ -0,0 +1,327 @@
+use glim::card::{Card, CardOptions, Render};
+use glim::config::CardConfig;
+use glim::errors::TemplateError;
+use glim::github::Repository;
+use glim::template::{
+    truncate, TemplateEngine, TemplateKind, BADGE_TEMPLATE, DEFAULT_TEMPLATE, ICON_TEMPLATE,
+    ISSUE_TEMPLATE, ORG_TEMPLATE, RELEASE_TEMPLATE, SOCIAL_TEMPLATE, USER_TEMPLATE,
+};
+use std::fs;
+use tempfile::TempDir;
+
+// Test fixtures
+const COMPACT_TEMPLATE: &str = r#"<svg width="200" height="40" xmlns="http://www.w3.org/2000/svg">
+    <rect width="200" height="40" fill="{{ bg_color }}" />
+    <text x="8" y="24" fill="{{ title_color }}">{{ name | truncate(12) }} {{ stars | format_count }}</text>
+</svg>"#;
+
+fn create_test_card(template: &str) -> Card {
+    let repository = Repository {
+        name: "a-very-long-repository-name".to_string(),
+        description: None,
+        language: Some("Rust".to_string()),
+        stargazers_count: 12345,
+        forks_count: 0,
+        ..Default::default()
+    };
+    let options = CardOptions {
+        template: template.to_string(),
+        ..Default::default()
+    };
+
+    Card::new(repository, options)
+}
+
+#[test]
+fn test_builtin_engine() {
+    let engine = TemplateEngine::new();
+
+    assert!(engine.contains(DEFAULT_TEMPLATE));
+    assert_eq!(
+        engine.names().collect::<Vec<_>>(),
+        vec![
+            BADGE_TEMPLATE,
+            DEFAULT_TEMPLATE,
+            ICON_TEMPLATE,
+            ISSUE_TEMPLATE,
+            ORG_TEMPLATE,
+            RELEASE_TEMPLATE,
+            SOCIAL_TEMPLATE,
+            USER_TEMPLATE
+        ]
+    );
+    assert_eq!(engine.resolve(None).unwrap(), DEFAULT_TEMPLATE);
+}
+
+#[test]
+fn test_resolve_unknown_template() {
+    let engine = TemplateEngine::new();
+
+    let result = engine.resolve(Some("compact"));
+    assert!(matches!(result, Err(TemplateError::Unknown(name)) if name == "compact"));
+}
+
+#[test]
+fn test_add_and_render_template() {
+    let mut engine = TemplateEngine::new();
+    engine
+        .add("compact", COMPACT_TEMPLATE.to_string())
+        .expect("Template should be valid");
+
+    assert_eq!(engine.resolve(Some("compact")).unwrap(), "compact");
+
+    let svg = create_test_card("compact").render_with(&engine).unwrap();
+    assert!(svg.contains("a-very-long… 12k"));
+}
+
+#[test]
+fn test_add_rejects_syntax_error() {
+    let mut engine = TemplateEngine::new();
+
+    let result = engine.add("broken", "<svg>{% if name %}</svg>".to_string());
+    assert!(matches!(result, Err(TemplateError::Syntax { .. })));
+    assert!(!engine.contains("broken"));
+}
+
+#[test]
+fn test_add_rejects_undefined_variable() {
+    let mut engine = TemplateEngine::new();
+
+    let result = engine.add(
+        "typo",
+        r#"<svg xmlns="http://www.w3.org/2000/svg"><text>{{ nmae }}</text></svg>"#.to_string(),
+    );
+    assert!(matches!(result, Err(TemplateError::Render { .. })));
+}
+
+#[test]
+fn test_add_rejects_undefined_filter_input() {
+    let mut engine = TemplateEngine::new();
+
+    let result = engine.add(
+        "typo",
+        r#"<svg xmlns="http://www.w3.org/2000/svg"><text>{{ starz | format_count }}</text></svg>"#
+            .to_string(),
+    );
+    assert!(matches!(result, Err(TemplateError::Render { .. })));
+}
+
+#[test]
+fn test_add_rejects_invalid_svg() {
+    let mut engine = TemplateEngine::new();
+
+    let result = engine.add("text", "{{ name }}".to_string());
+    assert!(matches!(result, Err(TemplateError::InvalidSvg { .. })));
+    assert!(!engine.contains("text"));
+}
+
+#[test]
+fn test_add_rejects_unescaped_output() {
+    let mut engine = TemplateEngine::new();
+
+    // The sample description contains markup, so printing it raw breaks the SVG
+    let result = engine.add(
+        "unsafe",
+        r#"<svg xmlns="http://www.w3.org/2000/svg"><text>{{ description | safe }}</text></svg>"#
+            .to_string(),
+    );
+    assert!(matches!(result, Err(TemplateError::InvalidSvg { .. })));
+}
+
+#[test]
+fn test_escape_filter_without_autoescape() {
+    let mut engine = TemplateEngine::new();
+    engine
+        .add(
+            "manual",
+            r#"<svg xmlns="http://www.w3.org/2000/svg">{% autoescape false %}<text>{{ description | e }}</text>{% endautoescape %}</svg>"#
+                .to_string(),
+        )
+        .expect("Escaped output should validate");
+
+    let mut card = create_test_card("manual");
+    card.repository.description = Some("a & b".to_string());
+    let svg = card.render_with(&engine).unwrap();
+    assert!(svg.contains("<text>a &amp; b</text>"));
+}
+
+#[test]
+fn test_set_default() {
+    let mut engine = TemplateEngine::new();
+    engine.add("compact", COMPACT_TEMPLATE.to_string()).unwrap();
+
+    assert!(matches!(
+        engine.set_default("missing"),
+        Err(TemplateError::Unknown(_))
+    ));
+
+    engine.set_default("compact").unwrap();
+    assert_eq!(engine.resolve(None).unwrap(), "compact");
+}
+
+#[test]
+fn test_load_dir() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("compact.svg"), COMPACT_TEMPLATE).unwrap();
+    fs::write(dir.path().join("notes.txt"), "not a template").unwrap();
+
+    let mut engine = TemplateEngine::new();
+    engine.load_dir(dir.path()).expect("Directory should load");
+
+    assert_eq!(
+        engine.names().collect::<Vec<_>>(),
+        vec![
+            BADGE_TEMPLATE,
+            "compact",
+            DEFAULT_TEMPLATE,
+            ICON_TEMPLATE,
+            ISSUE_TEMPLATE,
+            ORG_TEMPLATE,
+            RELEASE_TEMPLATE,
+            SOCIAL_TEMPLATE,
+            USER_TEMPLATE
+        ]
+    );
+}
+
+#[test]
+fn test_load_dir_overrides_default() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("default.svg"), COMPACT_TEMPLATE).unwrap();
+
+    let mut engine = TemplateEngine::new();
+    engine.load_dir(dir.path()).unwrap();
+
+    let svg = create_test_card(DEFAULT_TEMPLATE)
+        .render_with(&engine)
+        .unwrap();
+    assert!(svg.contains(r#"width="200""#));
+}
+
+#[test]
+fn test_load_dir_invalid_template() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("broken.svg"), "<svg>{{ </svg>").unwrap();
+
+    let mut engine = TemplateEngine::new();
+    let result = engine.load_dir(dir.path());
+    assert!(matches!(result, Err(TemplateError::Syntax { name, .. }) if name == "broken"));
+}
+
+#[test]
+fn test_load_dir_missing() {
+    let mut engine = TemplateEngine::new();
+
+    let result = engine.load_dir(std::path::Path::new("/nonexistent/templates"));
+    assert!(matches!(result, Err(TemplateError::Read { .. })));
+}
+
+#[test]
+fn test_from_config() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("compact.svg"), COMPACT_TEMPLATE).unwrap();
+
+    let config = CardConfig {
+        default_template: Some("compact".to_string()),
+        templates_dir: Some(dir.path().to_path_buf()),
+        ..Default::default()
+    };
+    let engine = TemplateEngine::from_config(&config).unwrap();
+    assert_eq!(engine.resolve(None).unwrap(), "compact");
+
+    // The default template must exist
+    let config = CardConfig {
+        default_template: Some("compact".to_string()),
+        ..Default::default()
+    };
+    assert!(matches!(
+        TemplateEngine::from_config(&config),
+        Err(TemplateError::Unknown(_))
+    ));
+}
+
+#[test]
+fn test_template_kinds() {
+    let mut engine = TemplateEngine::new();
+    engine.add("compact", COMPACT_TEMPLATE.to_string()).unwrap();
+    // Uses only variables shared by every kind of card
+    engine
+        .add(
+            "plain",
+            r#"<svg xmlns="http://www.w3.org/2000/svg"><text fill="{{ title_color }}">{{ name }}</text></svg>"#
+                .to_string(),
+        )
+        .unwrap();
+
+    // Organizations also have a name and stars, but users don't
+    assert_eq!(
+        engine.kinds("compact"),
+        Some(&[TemplateKind::Repository, TemplateKind::Org][..])
+    );
+    assert_eq!(engine.kinds("plain"), Some(&TemplateKind::ALL[..]));
+    assert_eq!(engine.kinds(USER_TEMPLATE), Some(&[TemplateKind::User][..]));
+    assert_eq!(engine.kinds(ORG_TEMPLATE), Some(&[TemplateKind::Org][..]));
+    assert_eq!(
+        engine.kinds(RELEASE_TEMPLATE),
+        Some(&[TemplateKind::Release][..])
+    );
+    assert_eq!(
+        engine.kinds(ISSUE_TEMPLATE),
+        Some(&[TemplateKind::Issue][..])
+    );
+
+    assert_eq!(
+        engine.resolve_kind(TemplateKind::User, None).unwrap(),
+        USER_TEMPLATE
+    );
+    assert_eq!(
+        engine
+            .resolve_kind(TemplateKind::User, Some("plain"))
+            .unwrap(),
+        "plain"
+    );
+    assert!(matches!(
+        engine.resolve_kind(TemplateKind::User, Some("compact")),
+        Err(TemplateError::Incompatible { name, kind: "user" }) if name == "compact"
+    ));
+    // User templates can't be the default repository template
+    assert!(matches!(
+        engine.set_default(USER_TEMPLATE),
+        Err(TemplateError::Incompatible { .. })
+    ));
+    assert_eq!(
+        engine.names_for(TemplateKind::User).collect::<Vec<_>>(),
+        vec!["plain", USER_TEMPLATE]
+    );
+    assert_eq!(
+        engine.resolve_kind(TemplateKind::Org, None).unwrap(),
+        ORG_TEMPLATE
+    );
+    assert!(matches!(
+        engine.resolve_kind(TemplateKind::Org, Some(USER_TEMPLATE)),
+        Err(TemplateError::Incompatible {
+            kind: "organization",
+            ..
+        })
+    ));
+    assert_eq!(
+        engine.resolve_kind(TemplateKind::Release, None).unwrap(),
+        RELEASE_TEMPLATE
+    );
+    assert_eq!(
+        engine.resolve_kind(TemplateKind::Issue, None).unwrap(),
+        ISSUE_TEMPLATE
+    );
+}
+
+#[test]
+fn test_truncate() {
+    assert_eq!(truncate("glim", 10, "…"), "glim");
+    assert_eq!(truncate("glim", 4, "…"), "glim");
+    assert_eq!(truncate("repository", 5, "…"), "repo…");
+    assert_eq!(truncate("repository", 6, "..."), "rep...");
+    // Trailing whitespace before the marker is dropped
+    assert_eq!(truncate("a long name", 3, "…"), "a…");
+    // Lengths are counted in characters, not bytes
+    assert_eq!(truncate("日本語のリポジトリ", 4, "…"), "日本語…");
+}
@@ -0,0 +1,139 @@
+use glim::text::{escape_xml, strip_control_chars, FontStyle, TextMeasurer, ELLIPSIS};
+use std::borrow::Cow;
+use std::sync::Arc;
+
+// Test fixtures
+const TEST_FONT: FontStyle = FontStyle {
+    family: "Inter",
+    size: 10.0,
+    weight: 400,
+};
+
+/// Measurer without fonts, so every width comes from the deterministic estimate
+/// (6px per Latin character and 10px per CJK character at 10px).
+fn create_estimating_measurer() -> TextMeasurer {
+    TextMeasurer::new(Arc::new(usvg::fontdb::Database::new()))
+}
+
+#[test]
+fn test_escape_xml_markup() {
+    assert_eq!(
+        escape_xml(r#"<script>alert("x") & 'y'</script>"#),
+        "&lt;script&gt;alert(&quot;x&quot;) &amp; &apos;y&apos;&lt;/script&gt;"
+    );
+}
+
+#[test]
+fn test_escape_xml_borrows_clean_text() {
+    assert!(matches!(escape_xml("plain text ✨"), Cow::Borrowed(_)));
+}
+
+#[test]
+fn test_escape_xml_drops_invalid_chars() {
+    assert_eq!(escape_xml("a\u{0}b\u{1b}[31mc\u{fffe}"), "ab[31mc");
+    // Whitespace control characters are valid XML
+    assert_eq!(escape_xml("a\tb\nc"), "a\tb\nc");
+}
+
+#[test]
+fn test_strip_control_chars() {
+    assert_eq!(
+        strip_control_chars("line one\nline\ttwo\r"),
+        "line one line two "
+    );
+    assert_eq!(
+        strip_control_chars("bell\u{7} del\u{7f} c1\u{85}"),
+        "bell del c1"
+    );
+    assert_eq!(strip_control_chars("\u{ffff}ok"), "ok");
+}
+
+#[test]
+fn test_strip_control_chars_keeps_markup_and_unicode() {
+    // Escaping is left to the output stage
+    let text = "<b>日本語</b> & émoji 🚀";
+    assert!(matches!(strip_control_chars(text), Cow::Borrowed(t) if t == text));
+}
+
+#[test]
+fn test_wrapped_lines_escape() {
+    let measurer = create_estimating_measurer();
+    let text = strip_control_chars("Fast & <safe>\u{0}");
+
+    // Wrapping works on plain text; each line is escaped on output
+    let lines = measurer.wrap(&text, &TEST_FONT, 400.0, 2);
+    assert_eq!(lines, vec!["Fast & <safe>"]);
+    assert_eq!(escape_xml(&lines[0]), "Fast &amp; &lt;safe&gt;");
+}
+
+#[test]
+fn test_measure_estimated_width() {
+    let measurer = create_estimating_measurer();
+
+    assert_eq!(measurer.width("abcd", &TEST_FONT), 24.0);
+    assert_eq!(measurer.width("日本", &TEST_FONT), 20.0);
+    assert_eq!(measurer.width("", &TEST_FONT), 0.0);
+}
+
+#[test]
+fn test_wrap_at_whitespace() {
+    let measurer = create_estimating_measurer();
+
+    let lines = measurer.wrap("aaa bbb ccc", &TEST_FONT, 42.0, 5);
+    assert_eq!(lines, vec!["aaa bbb", "ccc"]);
+}
+
+#[test]
+fn test_wrap_breaks_text_without_spaces() {
+    let measurer = create_estimating_measurer();
+
+    let lines = measurer.wrap("日本語のリポジトリ", &TEST_FONT, 30.0, 5);
+    assert_eq!(lines, vec!["日本語", "のリポ", "ジトリ"]);
+
+    let lines = measurer.wrap("abcdefghij", &TEST_FONT, 30.0, 5);
+    assert_eq!(lines, vec!["abcde", "fghij"]);
+}
+
+#[test]
+fn test_wrap_clamps_with_ellipsis() {
+    let measurer = create_estimating_measurer();
+
+    let lines = measurer.wrap("one two three four five six", &TEST_FONT, 30.0, 2);
+    assert_eq!(lines, vec!["one".to_string(), format!("two{}", ELLIPSIS)]);
+}
+
+#[test]
+fn test_wrap_blank_and_zero_lines() {
+    let measurer = create_estimating_measurer();
+
+    assert!(measurer.wrap("   ", &TEST_FONT, 100.0, 3).is_empty());
+    assert!(measurer.wrap("text", &TEST_FONT, 100.0, 0).is_empty());
+}
+
+#[test]
+fn test_truncate_to_width() {
+    let measurer = create_estimating_measurer();
+
+    assert_eq!(measurer.truncate("abcde", &TEST_FONT, 30.0), "abcde");
+    assert_eq!(
+        measurer.truncate("abcdefghij", &TEST_FONT, 30.0),
+        format!("abcd{}", ELLIPSIS)
+    );
+}
+
+#[test]
+fn test_wrap_fits_with_installed_fonts() {
+    let measurer = TextMeasurer::shared();
+    let text = "Generate beautiful GitHub repository cards 🚀 with 日本語 support and a very long tail of words";
+
+    let lines = measurer.wrap(text, &TEST_FONT, 120.0, 3);
+    assert_eq!(lines.len(), 3);
+    assert!(lines.last().unwrap().ends_with(ELLIPSIS));
+    for line in &lines {
+        assert!(
+            measurer.width(line, &TEST_FONT) <= 120.0,
+            "{:?} overflows",
+            line
+        );
+    }
+}
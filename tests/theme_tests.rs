@@ -0,0 +1,169 @@
+use glim::errors::ThemeError;
+use glim::theme::{
+    contrast_ratio, parse_hex_color, ColorOverrides, Theme, ThemeRegistry, DEFAULT_THEME,
+    WCAG_AA_CONTRAST,
+};
+
+#[test]
+fn test_builtin_themes_registered() {
+    let registry = ThemeRegistry::new();
+    let names: Vec<&str> = registry.names().collect();
+
+    assert_eq!(names, vec!["dark", "high-contrast", "light"]);
+    assert_eq!(registry.get("dark").unwrap(), &Theme::dark());
+}
+
+#[test]
+fn test_theme_lookup_case_insensitive() {
+    let registry = ThemeRegistry::new();
+
+    assert_eq!(registry.get("DARK").unwrap().name, "dark");
+    assert_eq!(registry.get("High-Contrast").unwrap().name, "high-contrast");
+}
+
+#[test]
+fn test_resolve_default_and_unknown_theme() {
+    let registry = ThemeRegistry::new();
+
+    assert_eq!(registry.resolve(None).unwrap().name, DEFAULT_THEME);
+    assert!(matches!(
+        registry.resolve(Some("solarized")),
+        Err(ThemeError::Unknown(name)) if name == "solarized"
+    ));
+}
+
+#[test]
+fn test_set_default_theme() {
+    let mut registry = ThemeRegistry::new();
+    registry.set_default("dark").unwrap();
+
+    assert_eq!(registry.resolve(None).unwrap().name, "dark");
+    assert!(registry.set_default("missing").is_err());
+}
+
+#[test]
+fn test_parse_hex_color() {
+    let test_cases = [
+        ("#0366D6", Some("#0366d6")),
+        ("0366d6", Some("#0366d6")),
+        ("#fff", Some("#ffffff")),
+        ("abc", Some("#aabbcc")),
+        ("#12345", None),
+        ("#gggggg", None),
+        ("red", None),
+        ("", None),
+        ("#fff\" onload=\"x", None),
+    ];
+
+    for (input, expected) in test_cases {
+        assert_eq!(
+            parse_hex_color(input).as_deref(),
+            expected,
+            "Failed for {:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_set_color_validation() {
+    let mut theme = Theme::light();
+
+    theme.set_color("bg_color", "000").unwrap();
+    assert_eq!(theme.bg_color, "#000000");
+
+    assert!(matches!(
+        theme.set_color("bg_color", "<script>"),
+        Err(ThemeError::InvalidColor { .. })
+    ));
+    assert!(matches!(
+        theme.set_color("shadow_color", "#000000"),
+        Err(ThemeError::UnknownField(_))
+    ));
+}
+
+#[test]
+fn test_contrast_ratio() {
+    let ratio = contrast_ratio("#000000", "#ffffff").unwrap();
+    assert!((ratio - 21.0).abs() < 0.01);
+
+    let ratio = contrast_ratio("#ffffff", "#ffffff").unwrap();
+    assert!((ratio - 1.0).abs() < 0.01);
+
+    assert_eq!(contrast_ratio("nope", "#ffffff"), None);
+}
+
+#[test]
+fn test_builtin_themes_meet_wcag_aa() {
+    for theme in [Theme::light(), Theme::dark(), Theme::high_contrast()] {
+        let mut adjusted = theme.clone();
+        assert!(
+            adjusted.ensure_contrast().is_empty(),
+            "{} theme should not need contrast adjustment",
+            theme.name
+        );
+    }
+}
+
+#[test]
+fn test_ensure_contrast_adjusts_low_contrast_text() {
+    let mut theme = Theme::light();
+    theme.set_color("text_color", "#eeeeee").unwrap();
+
+    let adjusted = theme.ensure_contrast();
+    assert_eq!(adjusted, vec!["text_color"]);
+    assert!(contrast_ratio(&theme.text_color, &theme.bg_color).unwrap() >= WCAG_AA_CONTRAST);
+}
+
+#[test]
+fn test_color_overrides_apply() {
+    let overrides = ColorOverrides {
+        bg_color: Some("000000".to_string()),
+        border_color: Some("#333".to_string()),
+        ..Default::default()
+    };
+    assert!(!overrides.is_empty());
+    assert!(ColorOverrides::default().is_empty());
+
+    let theme = overrides.apply(&Theme::dark()).unwrap();
+    assert_eq!(theme.name, "dark");
+    assert_eq!(theme.bg_color, "#000000");
+    assert_eq!(theme.border_color, "#333333");
+    assert_eq!(theme.title_color, Theme::dark().title_color);
+}
+
+#[test]
+fn test_color_overrides_reject_invalid_values() {
+    let invalid_values = ["red", "#12", "#1234567", "fff;}</style>", "url(#x)"];
+
+    for value in invalid_values {
+        let overrides = ColorOverrides {
+            title_color: Some(value.to_string()),
+            ..Default::default()
+        };
+        assert!(
+            matches!(
+                overrides.apply(&Theme::light()),
+                Err(ThemeError::InvalidColor { .. })
+            ),
+            "Expected {:?} to be rejected",
+            value
+        );
+    }
+}
+
+#[test]
+fn test_registry_build_enforces_contrast() {
+    let registry = ThemeRegistry::new();
+    let overrides = ColorOverrides {
+        text_color: Some("#ffffff".to_string()),
+        ..Default::default()
+    };
+
+    let theme = registry.build(None, &overrides).unwrap();
+    assert_ne!(theme.text_color, "#ffffff");
+    assert!(contrast_ratio(&theme.text_color, &theme.bg_color).unwrap() >= WCAG_AA_CONTRAST);
+
+    // Themes differing only by overrides have distinct keys
+    assert_ne!(theme.key(), Theme::light().key());
+}